@@ -0,0 +1,158 @@
+// Compares parsing into a bump arena with building an owned `JsonValue`,
+// counting allocator calls and timing both on the same documents.
+// Run with `cargo run --release --features arena --example arena`.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
+
+use json_parser::{ArenaValue, Bump, DuplicateKeyPolicy, ParseErrorKind, Parser, ParserOptions};
+
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const DOCUMENTS: usize = 200;
+
+// Allocator calls and seconds taken by `parse` over every document.
+fn measure(documents: &[String], mut parse: impl FnMut(&str)) -> (usize, f64) {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for document in documents {
+        parse(document);
+    }
+    (ALLOCATIONS.load(Ordering::Relaxed) - before, start.elapsed().as_secs_f64())
+}
+
+fn main() {
+    let documents: Vec<String> = (0..DOCUMENTS)
+        .map(|d| {
+            let rows: Vec<String> = (0..200)
+                .map(|i| {
+                    format!(
+                        r#"{{"id": {}, "name": "user {}", "tags": ["a", "b\n"], "score": {}.5, "active": {}}}"#,
+                        i,
+                        d,
+                        i,
+                        i % 2 == 0
+                    )
+                })
+                .collect();
+            format!(r#"{{"rows": [{}]}}"#, rows.join(", "))
+        })
+        .collect();
+
+    let (owned_allocations, owned_time) = measure(&documents, |d| {
+        Parser::new(d).parse().unwrap();
+    });
+    let mut bump = Bump::new();
+    let (arena_allocations, arena_time) = measure(&documents, |d| {
+        Parser::parse_in_arena(d, &bump).unwrap();
+        bump.reset();
+    });
+    println!(
+        "owned: {} allocations, {:.1} ms; arena: {} allocations, {:.1} ms",
+        owned_allocations,
+        owned_time * 1e3,
+        arena_allocations,
+        arena_time * 1e3
+    );
+    // What the arena still allocates is mostly the input's chars and the
+    // decoding of strings with escapes, one per row here.
+    if arena_allocations * 2 < owned_allocations {
+        println!("✓ The arena made {:.0}x fewer allocations", owned_allocations as f64 / arena_allocations as f64);
+    } else {
+        println!("✗ The arena did not cut allocations");
+    }
+
+    let same = documents.iter().take(5).all(|d| {
+        let bump = Bump::new();
+        Parser::parse_in_arena(d, &bump).unwrap().to_owned() == Parser::new(d).parse().unwrap()
+    });
+    if same {
+        println!("✓ Arena values convert back to the owned tree");
+    } else {
+        println!("✗ Arena and owned trees differ");
+    }
+
+    // Repeated keys follow the default policy, last wins.
+    let bump = Bump::new();
+    match Parser::parse_in_arena(r#"{"a": 1, "a": [2], "b": "é"}"#, &bump) {
+        Ok(doc)
+            if doc.get("a") == Some(&ArenaValue::Array(&[ArenaValue::Number(2.0)]))
+                && doc.get("b") == Some(&ArenaValue::String("é")) =>
+        {
+            println!("✓ Duplicate keys resolved and escapes decoded in the arena")
+        }
+        other => println!("✗ Unexpected arena value: {:?}", other),
+    }
+    let collect = ParserOptions {
+        duplicate_keys: DuplicateKeyPolicy::Collect,
+        ..Default::default()
+    };
+    let input = r#"{"a": 1, "a": [2], "a": 3, "b": {"c": 4, "c": 5}}"#;
+    match Parser::parse_in_arena_with(input, collect.clone(), &bump) {
+        Ok(doc) if doc.to_owned() == Parser::with_options(input, collect).parse().unwrap() => {
+            println!("✓ Collect gathered repeats in the arena as in the owned tree: {}", doc.to_owned())
+        }
+        other => println!("✗ Collect differs in the arena: {:?}", other),
+    }
+
+    // Wide objects look repeated keys up through an index, not a scan of
+    // every member so far.
+    let members: Vec<String> = (0..20_000).map(|i| format!(r#""k{}": {}"#, i, i)).collect();
+    let wide = format!(r#"{{{}, "k7": "again", "k19999": "last"}}"#, members.join(", "));
+    let (_, owned_time) = measure(std::slice::from_ref(&wide), |d| {
+        Parser::new(d).parse().unwrap();
+    });
+    let (_, arena_time) = measure(std::slice::from_ref(&wide), |d| {
+        Parser::parse_in_arena(d, &bump).unwrap();
+    });
+    let policies = [DuplicateKeyPolicy::FirstWins, DuplicateKeyPolicy::LastWins, DuplicateKeyPolicy::Collect];
+    let same = policies.iter().all(|&duplicate_keys| {
+        let options = ParserOptions {
+            duplicate_keys,
+            ..Default::default()
+        };
+        let arena = Parser::parse_in_arena_with(&wide, options.clone(), &bump).map(|v| v.to_owned());
+        arena.ok() == Parser::with_options(&wide, options).parse().ok()
+    });
+    if same && arena_time < owned_time * 5.0 {
+        println!(
+            "✓ A {}-member object resolved repeats as the owned tree does, in {:.1} ms against {:.1} ms",
+            members.len(),
+            arena_time * 1e3,
+            owned_time * 1e3
+        );
+    } else {
+        println!("✗ Wide arena object wrong or slow ({}): {:.1} ms against {:.1} ms", same, arena_time * 1e3, owned_time * 1e3);
+    }
+
+    let too_deep = "[".repeat(100_000);
+    match Parser::parse_in_arena(&too_deep, &bump) {
+        Err(e) if e.kind == ParseErrorKind::DepthExceeded && e.position == 512 => {
+            println!("✓ Nesting deeper than 512 is rejected in the arena too")
+        }
+        other => println!("✗ Deep nesting not rejected in the arena: {:?}", other.map(|v| v.to_owned())),
+    }
+}
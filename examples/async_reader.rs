@@ -0,0 +1,64 @@
+// Demonstrates parsing from a tokio `AsyncRead`.
+// Run with `cargo run --features tokio --example async_reader`.
+use json_parser::{ParseError, ParseErrorKind, Parser, ParserOptions, parse_from_async_reader, parse_from_async_reader_with_options};
+use tokio::io::AsyncWriteExt;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    let text = r#"{"events": [{"id": 1, "kind": "login"}, {"id": 2, "kind": "café \"visit\""}], "total": 2}"#;
+    let expected = Parser::new(text).parse().unwrap();
+
+    // A small duplex buffer forces the document through in 3-byte writes
+    // that the reading side has to await.
+    let (mut writer, reader) = tokio::io::duplex(3);
+    let feed = tokio::spawn(async move {
+        for chunk in text.as_bytes().chunks(3) {
+            writer.write_all(chunk).await.unwrap();
+        }
+    });
+    let parsed = parse_from_async_reader(reader).await;
+    feed.await.unwrap();
+    match parsed {
+        Ok(value) if value == expected => println!("✓ Parsed a document fed in 3-byte async chunks"),
+        other => println!("✗ Async parse gave {:?}", other),
+    }
+
+    let (mut writer, reader) = tokio::io::duplex(2);
+    tokio::spawn(async move {
+        writer.write_all(b"[1, 2, \xff]").await.unwrap();
+    });
+    match parse_from_async_reader(reader).await {
+        Err(e) if e.position == 7 => println!("✓ Invalid UTF-8 reported at stream position {}", e.position),
+        other => println!("✗ Expected an encoding error at 7, got {:?}", other),
+    }
+
+    // An endless stream of whitespace would never finish reading without a
+    // limit; with one, reading stops a byte past it.
+    let limited = ParserOptions { max_input_length: Some(64 * 1024), ..ParserOptions::default() };
+    match parse_from_async_reader_with_options(tokio::io::repeat(b' '), limited.clone()).await {
+        Err(e) if e.kind == ParseErrorKind::InputTooLong && e.byte_offset == 64 * 1024 => {
+            println!("✓ Endless async input stopped at max_input_length ({} bytes)", e.byte_offset)
+        }
+        other => println!("✗ Expected InputTooLong at 65536, got {:?}", other),
+    }
+    let long = format!("[{}]", "1, ".repeat(30_000));
+    let sync = Parser::with_reader(long.as_bytes(), limited.clone()).parse();
+    let at = |e: &ParseError| (e.kind, e.position, e.byte_offset);
+    match parse_from_async_reader_with_options(long.as_bytes(), limited).await {
+        Err(e) if sync.as_ref().err().map(at) == Some(at(&e)) => println!("✓ Async input limit matches from_reader: {}", e.message),
+        other => println!("✗ Async limit gave {:?}, from_reader {:?}", other, sync),
+    }
+
+    // The options reach the parser too, as does the default depth limit.
+    let deep = "[".repeat(10_000);
+    let shallow = ParserOptions { max_depth: Some(3), ..ParserOptions::default() };
+    match (
+        parse_from_async_reader(deep.as_bytes()).await,
+        parse_from_async_reader_with_options(&b"[[[[1]]]]"[..], shallow).await,
+    ) {
+        (Err(a), Err(b)) if a.kind == ParseErrorKind::DepthExceeded && (a.position, b.position) == (512, 3) => {
+            println!("✓ Async parsing applies max_depth")
+        }
+        other => println!("✗ Expected DepthExceeded at 512 and 3, got {:?}", other),
+    }
+}
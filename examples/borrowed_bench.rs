@@ -0,0 +1,36 @@
+// Compares `Parser::parse_borrowed` against `Parser::parse` on a string-heavy
+// document. Run with `cargo run --release --example borrowed_bench`.
+use std::time::Instant;
+
+use json_parser::Parser;
+
+fn fixture(records: usize) -> String {
+    let mut out = String::from("[");
+    for i in 0..records {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push_str(&format!(
+            "{{\"first_name\": \"name number {}\", \"last_name\": \"surname\", \"email\": \"user{}@example.com\", \"bio\": \"likes \\\"quotes\\\" sometimes\"}}",
+            i, i
+        ));
+    }
+    out.push(']');
+    out
+}
+
+fn main() {
+    let input = fixture(200_000);
+    println!("fixture: {} bytes", input.len());
+
+    let start = Instant::now();
+    Parser::parse_borrowed(&input).expect("fixture should parse");
+    let borrowed_time = start.elapsed();
+
+    let start = Instant::now();
+    Parser::new(&input).parse().expect("fixture should parse");
+    let owned_time = start.elapsed();
+
+    println!("parse_borrowed: {:?}", borrowed_time);
+    println!("parse:          {:?}", owned_time);
+}
@@ -0,0 +1,131 @@
+// Checks CBOR encoding and decoding against the examples in RFC 8949
+// appendix A, and round-trips values through both.
+// Run with `cargo run --features cbor --example cbor`.
+use json_parser::{CborError, JsonValue, Parser, from_cbor, to_cbor};
+
+fn hex(text: &str) -> Vec<u8> {
+    (0..text.len()).step_by(2).map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap()).collect()
+}
+
+fn main() {
+    // (CBOR, the value as JSON, whether `to_cbor` produces exactly these bytes)
+    let fixtures = [
+        ("00", "0", true),
+        ("17", "23", true),
+        ("1818", "24", true),
+        ("1903e8", "1000", true),
+        ("1b000000e8d4a51000", "1000000000000", true),
+        ("1bffffffffffffffff", "18446744073709551615", false),
+        ("20", "-1", true),
+        ("3903e7", "-1000", true),
+        ("3bffffffffffffffff", "-18446744073709551616", true),
+        ("f90000", "0", false),
+        ("f93e00", "1.5", false),
+        ("f97bff", "65504", false),
+        ("f90001", "5.960464477539063e-8", false),
+        ("fa47c35000", "100000", false),
+        ("fb3ff199999999999a", "1.1", true),
+        ("f4", "false", true),
+        ("f6", "null", true),
+        ("f7", "null", false),
+        ("62c3bc", "\"\u{fc}\"", true),
+        ("83010203", "[1, 2, 3]", true),
+        ("a26161016162820203", r#"{"a": 1, "b": [2, 3]}"#, true),
+        ("7f657374726561646d696e67ff", r#""streaming""#, false),
+        ("bf6346756ef563416d7421ff", r#"{"Fun": true, "Amt": -2}"#, false),
+    ];
+    let mut failed = 0;
+    for (cbor, json, canonical) in fixtures {
+        let expected = Parser::new(json).parse().unwrap();
+        let decoded = from_cbor(&hex(cbor));
+        let matches = match &decoded {
+            Ok(value) => value == &expected || value.to_string() == json,
+            Err(_) => false,
+        };
+        let encoded_ok = !canonical || to_cbor(&expected) == hex(cbor);
+        if !matches || !encoded_ok {
+            failed += 1;
+            println!("✗ Fixture {} ({}): decoded {:?}, encoded {:02x?}", cbor, json, decoded, to_cbor(&expected));
+        }
+    }
+    if failed == 0 {
+        println!("✓ {} RFC 8949 fixtures decoded, and encoded where canonical", fixtures.len());
+    }
+
+    let rejected = [
+        ("4401020304", "byte string"),
+        ("c074323031332d30332d32315432303a30343a30305a", "tag"),
+        ("a10102", "map key"),
+        ("f0", "simple value"),
+        ("1c", "reserved"),
+        ("1903", "end"),
+        ("0000", "after"),
+        ("ff", "break"),
+        ("7f01ff", "chunk"),
+    ];
+    let mut messages = Vec::new();
+    for (cbor, expected) in rejected {
+        match from_cbor(&hex(cbor)) {
+            Err(e) if e.to_string().contains(expected) => messages.push(e.to_string()),
+            other => println!("✗ {} should have been rejected ({}): {:?}", cbor, expected, other),
+        }
+    }
+    if messages.len() == rejected.len() {
+        println!("✓ Unsupported and malformed CBOR rejected:\n    {}", messages.join("\n    "));
+    }
+
+    let nested = [0x81].repeat(10_000);
+    match from_cbor(&nested) {
+        Err(CborError::TooDeep { offset: 512 }) => println!("✓ Deep nesting rejected without overflowing the stack"),
+        other => println!("✗ Expected deep nesting to be rejected: {:?}", other),
+    }
+
+    let value = Parser::new(
+        r#"{"name": "caché", "sizes": [0, -0.0, 1.5, 1e300, -9007199254740993, 4294967296, -1e19],
+            "flags": {"a": true, "b": null}, "empty": [{}, [], ""]}"#,
+    )
+    .parse()
+    .unwrap();
+    let bytes = to_cbor(&value);
+    // `cmp` rather than `==`, which takes -0.0 for 0.0.
+    match from_cbor(&bytes) {
+        Ok(back) if back.total_cmp(&value).is_eq() && value.to_string().len() > bytes.len() => {
+            println!("✓ Round-tripped through {} bytes of CBOR ({} as JSON)", bytes.len(), value.to_string().len())
+        }
+        other => println!("✗ CBOR round trip changed the value: {:?}", other),
+    }
+    let exact = JsonValue::RawNumber("18446744073709551615".to_string());
+    match from_cbor(&to_cbor(&JsonValue::Array(vec![exact.clone()]))) {
+        Ok(JsonValue::Array(items)) if items == [exact] => {
+            println!("✓ 64-bit integers beyond f64 precision survive as raw numbers")
+        }
+        other => println!("✗ Large integer not preserved: {:?}", other),
+    }
+
+    // Negative raw numbers, as parsed with `numbers_as_strings`, encode by
+    // their value and decode back to the same number.
+    let raw_negatives = [
+        ("-0", "fa80000000"),
+        ("-1", "20"),
+        ("-1000", "3903e7"),
+        ("-9223372036854775808", "3b7fffffffffffffff"),
+        ("-18446744073709551616", "3bffffffffffffffff"),
+    ];
+    let mut failed = 0;
+    for (raw, cbor) in raw_negatives {
+        let encoded = to_cbor(&JsonValue::RawNumber(raw.to_string()));
+        let decoded = from_cbor(&encoded);
+        let same = match &decoded {
+            Ok(JsonValue::Number(n)) => n.to_bits() == raw.parse::<f64>().unwrap().to_bits(),
+            Ok(JsonValue::RawNumber(s)) => s == raw,
+            _ => false,
+        };
+        if encoded != hex(cbor) || !same {
+            failed += 1;
+            println!("✗ Raw number {}: encoded {:02x?}, decoded {:?}", raw, encoded, decoded);
+        }
+    }
+    if failed == 0 {
+        println!("✓ {} negative raw numbers round-tripped, -0 keeping its sign", raw_negatives.len());
+    }
+}
@@ -0,0 +1,2225 @@
+use std::{borrow::Cow, collections::BTreeMap, sync::Arc};
+
+use json_parser::{ArrayBuilder, ArrayMergeStrategy, CachingParser, Child, CsvError, CsvOptions, DuplicateKeyPolicy, Encoding, EscapePolicy, ExponentFormat, FieldExtractor, Format, FromJson, JsonError, JsonValue, JsonValueRef, KeyInterner, Lexer, MergeOptions, NonFinitePolicy, NullMergePolicy, ObjectBuilder, OverflowPolicy, ParseErrorKind, Parser, ParserOptions, SerializeError, SharedJson, SpannedKind, SurrogatePolicy, ToJson, Token, TotalOrd, WalkControl, WriteOptions, detect_encoding, from_reader, fuzz_parse, is_valid, minify, parse_bytes, parse_into, parse_json_seq, parse_partial, parse_stream, write_json_seq};
+
+struct Address {
+    city: String,
+}
+
+impl ToJson for Address {
+    fn to_json(&self) -> JsonValue {
+        ObjectBuilder::new().insert_json("city", &self.city).build()
+    }
+}
+
+struct Company {
+    address: Address,
+    staff: Vec<Address>,
+    motto: Option<String>,
+}
+
+impl ToJson for Company {
+    fn to_json(&self) -> JsonValue {
+        ObjectBuilder::new()
+            .insert_json("address", &self.address)
+            .insert_json("staff", &self.staff)
+            .insert_json_some("motto", &self.motto)
+            .build()
+    }
+}
+
+struct Person {
+    name: String,
+    age: u32,
+    tags: Vec<String>,
+}
+
+impl FromJson for Person {
+    fn from_json(value: &JsonValue) -> Result<Self, JsonError> {
+        let JsonValue::Object(obj) = value else {
+            return Err(JsonError::custom("expected a person object"));
+        };
+        let field = |key: &str| obj.get(key).ok_or_else(|| JsonError::custom(format!("missing field '{}'", key)));
+        Ok(Person {
+            name: String::from_json(field("name")?)?,
+            age: u32::from_json(field("age")?)?,
+            tags: Vec::from_json(field("tags")?)?,
+        })
+    }
+}
+
+fn main() {
+    println!("Testing basic JSON parser...\n");
+    
+    let mut parser = Parser::new("null");
+    match parser.parse() {
+        Ok(JsonValue::Null) => println!("✓ null parsed correctly"),
+        Ok(other) => println!("✗ Expected Null, got: {:?}", other),
+        Err(e) => println!("✗ Failed to parse null: {}", e),
+    }
+    
+    let mut parser = Parser::new("true");
+    match parser.parse() {
+        Ok(JsonValue::Boolean(true)) => println!("✓ true parsed correctly"),
+        Ok(other) => println!("✗ Expected Boolean(true), got: {:?}", other),
+        Err(e) => println!("✗ Failed to parse true: {}", e),
+    }
+    
+    // Test 3: false
+    let mut parser = Parser::new("false");
+    match parser.parse() {
+        Ok(JsonValue::Boolean(false)) => println!("✓ false parsed correctly"),
+        Ok(other) => println!("✗ Expected Boolean(false), got: {:?}", other),
+        Err(e) => println!("✗ Failed to parse false: {}", e),
+    }
+    
+    // Test 4: Invalid
+    let mut parser = Parser::new("nope");
+    match parser.parse() {
+        Err(_) => println!("✓ Correctly rejected invalid input"),
+        Ok(val) => println!("✗ Should have failed, got: {:?}", val),
+    }
+    
+    // Test 5: Basic string
+    println!("\n--- Testing String Parsing ---");
+    let mut parser = Parser::new("\"hello world\"");
+    match parser.parse() {
+        Ok(JsonValue::String(s)) => println!("✓ String parsed correctly: '{}'", s),
+        Ok(other) => println!("✗ Expected String, got: {:?}", other),
+        Err(e) => println!("✗ Failed to parse string: {}", e),
+    }
+    
+    // Test 6: String with escapes
+    let mut parser = Parser::new("\"hello\\nworld\\t!\"");
+    match parser.parse() {
+        Ok(JsonValue::String(s)) => println!("✓ String with escapes parsed: '{}'", s),
+        Ok(other) => println!("✗ Expected String, got: {:?}", other),
+        Err(e) => println!("✗ Failed to parse string with escapes: {}", e),
+    }
+    
+    // Test 7: Unterminated string (should fail)
+    let mut parser = Parser::new("\"hello");
+    match parser.parse() {
+        Err(_) => println!("✓ Correctly rejected unterminated string"),
+        Ok(val) => println!("✗ Should have failed, got: {:?}", val),
+    }
+    
+    // Test 8: Test Display formatting with escapes
+    println!("\n--- Testing Display Formatting ---");
+    let test_string = JsonValue::String("hello\nworld\t\"quote\"\\backslash".to_string());
+    println!("✓ Display formatting: {}", test_string);
+
+    // Test 9: Testing Number Parsing
+    let tests: Vec<(&str, f64)> = vec![
+        ("42", 42.0),
+        ("-17", -17.0),
+        ("0", 0.0),
+        ("123", 123.0),
+    ];
+
+    for (input, expected) in tests {
+        let mut parser = Parser::new(input);
+        match parser.parse() {
+            Ok(JsonValue::Number(n)) if (n - expected).abs() < f64::EPSILON => {
+                println!("number '{}'parsed correctly: {}", input, n);
+            }
+            Ok(other) => println!("expected number ({}), got: {:?}", expected, other),
+            Err(e) => println!("failed to parse '{}': {}", input, e),
+        }
+    }
+
+    // 3.14 is the literal text under test, not an approximation of pi.
+    #[allow(clippy::approx_constant)]
+    let decimal_tests: Vec<(&str, f64)> = vec![
+        ("3.14", 3.14),
+        ("-0.5", -0.5),
+        ("0.123", 0.123),
+    ];
+
+    for (input, expected) in decimal_tests {
+        let mut parser = Parser::new(input);
+        match parser.parse() {
+            Ok(JsonValue::Number(n)) if (n - expected).abs() <f64::EPSILON => {
+                println!("decimal '{}' parsed coreectly: {}", input, n);
+            }
+            Ok(other) => println!("expected number({}), got: {:?}", expected, other),
+            Err(e) => println!("failed to parse '{}': {}", input, e),
+        }
+    }
+
+    let sci_tests: Vec<(&str, f64)> = vec![
+("1e2", 100.0),
+("1E-2", 0.01),
+("-2e+3", -2000.0),
+    ];
+
+    for (input, expected) in sci_tests {
+        let mut parser = Parser::new(input);
+        match parser.parse() {
+            Ok(JsonValue::Number(n)) if (n - expected).abs() < f64::EPSILON => {
+                println!("scientific '{}' parsed correctly: {}", input, n);
+            }
+            Ok(other) => println!("expected number ({}), got: {:?}", expected, other),
+            Err(e) => println!("failed to parse '{}': {}", input, e),
+        }
+    }
+
+    // Test empty array
+    let mut parser = Parser::new("[]");
+    match parser.parse() {
+        Ok(JsonValue::Array(arr)) if arr.is_empty() => println!("✓ Empty array parsed correctly"),
+        Ok(other) => println!("✗ Expected empty array, got: {:?}", other),
+        Err(e) => println!("✗ Failed to parse empty array: {}", e),
+    }
+
+    // Test simple array
+    let mut parser = Parser::new("[1, 2, 3]");
+    match parser.parse() {
+        Ok(JsonValue::Array(arr)) if arr.len() == 3 => println!("✓ Simple array parsed correctly: {:?}", arr),
+        Ok(other) => println!("✗ Expected array with 3 elements, got: {:?}", other),
+        Err(e) => println!("✗ Failed to parse simple array: {}", e),
+    }
+
+    // Test mixed array
+    let mut parser = Parser::new("[null, true, \"hello\", 42]");
+    match parser.parse() {
+        Ok(JsonValue::Array(arr)) if arr.len() == 4 => println!("✓ Mixed array parsed correctly: {:?}", arr),
+        Ok(other) => println!("✗ Expected mixed array, got: {:?}", other),
+        Err(e) => println!("✗ Failed to parse mixed array: {}", e),
+    }
+
+    // Test nested array
+    let mut parser = Parser::new("[[1, 2], [3, 4]]");
+    match parser.parse() {
+        Ok(JsonValue::Array(_)) => println!("✓ Nested array parsed correctly"),
+        Ok(other) => println!("✗ Expected nested array, got: {:?}", other),
+        Err(e) => println!("✗ Failed to parse nested array: {}", e),
+    }
+
+    // Test empty object
+    let mut parser = Parser::new("{}");
+    match parser.parse() {
+        Ok(JsonValue::Object(obj)) if obj.is_empty() => println!("✓ Empty object parsed correctly"),
+        Ok(other) => println!("✗ Expected empty object, got: {:?}", other),
+        Err(e) => println!("✗ Failed to parse empty object: {}", e),
+    }
+
+    // Test simple object
+    let mut parser = Parser::new("{\"name\": \"John\", \"age\": 30}");
+    match parser.parse() {
+         Ok(JsonValue::Object(obj)) if obj.len() == 2 => {
+            println!("✓ Simple object parsed correctly: {:?}", obj);
+        }
+        Ok(other) => println!("✗ Expected object with 2 keys, got: {:?}", other),
+        Err(e) => println!("✗ Failed to parse simple object: {}", e),
+    }
+
+    // Test nested object
+    let mut parser = Parser::new("{\"person\": {\"name\": \"Alice\"}, \"active\": true}");
+    match parser.parse() {
+        Ok(JsonValue::Object(_)) => println!("✓ Nested object parsed correctly"),
+        Ok(other) => println!("✗ Expected nested object, got: {:?}", other),
+        Err(e) => println!("✗ Failed to parse nested object: {}", e),
+    }
+
+    // Test object with array
+    let mut parser = Parser::new("{\"numbers\": [1, 2, 3], \"valid\": true}");
+    match parser.parse() {
+        Ok(JsonValue::Object(_)) => println!("✓ Object with array parsed correctly"),
+        Ok(other) => println!("✗ Expected object with array, got: {:?}", other),
+        Err(e) => println!("✗ Failed to parse object with array: {}", e),
+    }
+
+    // Tolerant parsing: three independent mistakes, three errors
+    let mut parser = Parser::new("{\"name\": \"bad \\q escape\", \"list\": [1 2], \"nested\": [[1, 2] oops]}");
+    let (value, errors) = parser.parse_tolerant();
+    if errors.len() == 3 {
+        println!("✓ Tolerant parse reported 3 errors, best effort: {}", value);
+        for e in &errors {
+            println!("    {}", e);
+        }
+    } else {
+        println!("✗ Expected 3 errors, got {}: {:?}", errors.len(), errors);
+    }
+
+    // Typed conversion with FromJson
+    match parse_into::<Person>("{\"name\": \"Alice\", \"age\": 30, \"tags\": [\"admin\"]}") {
+        Ok(p) => println!("✓ parse_into built {} ({}) with tags {:?}", p.name, p.age, p.tags),
+        Err(e) => println!("✗ parse_into failed: {}", e),
+    }
+    match parse_into::<Person>("{\"name\": \"Bob\", \"age\": 2.5, \"tags\": []}") {
+        Err(e) => println!("✓ parse_into rejected fractional age: {}", e),
+        Ok(_) => println!("✗ parse_into should have rejected a fractional age"),
+    }
+    // Past 2^53 an f64 can't say which integer it was, but raw digits can
+    let past_safe = JsonValue::Number(9007199254740993.0);
+    let raw = |s: &str| JsonValue::RawNumber(s.to_string());
+    let wide = (
+        i64::from_json(&JsonValue::Number(9007199254740991.0)).ok(),
+        i64::from_json(&JsonValue::Number(-9007199254740991.0)).ok(),
+        i64::from_json(&past_safe).is_err()
+            && u64::from_json(&past_safe).is_err()
+            && i64::from_json(&JsonValue::Number(-9007199254740993.0)).is_err(),
+        u64::from_json(&raw("18446744073709551615")).ok(),
+        i64::from_json(&raw("-9007199254740993")).ok(),
+        u8::from_json(&raw("256")).is_err() && i64::from_json(&raw("9007199254740993.0")).is_err(),
+    );
+    if wide == (Some(9007199254740991), Some(-9007199254740991), true, Some(u64::MAX), Some(-9007199254740993), true) {
+        println!("✓ i64 and u64 stop at 2^53 - 1 for f64s but read raw digits exactly");
+    } else {
+        println!("✗ Unexpected wide integer conversions: {:?}", wide);
+    }
+
+    // validate() must agree with parse() on what is accepted
+    let inputs = [
+        "null", "[1, 2, {\"a\": \"b\\n\"}]", "-0.5e+3", "\"bad \\x\"", "[1, 2,]",
+        "{\"a\" 1}", "01", "1.", "[1] x", "{\"k\": [true, false, null]}",
+    ];
+    let mismatches: Vec<&str> = inputs
+        .iter()
+        .copied()
+        .filter(|input| is_valid(input) != Parser::new(input).parse().is_ok())
+        .collect();
+    if mismatches.is_empty() {
+        println!("✓ validate agrees with parse on {} inputs", inputs.len());
+    } else {
+        println!("✗ validate disagrees with parse on: {:?}", mismatches);
+    }
+
+    // FieldExtractor reports every bad field at once
+    let value = Parser::new("{\"name\": 7, \"age\": \"old\"}").parse().unwrap();
+    let mut fields = FieldExtractor::new(&value).unwrap();
+    let _ = fields.require_str("name");
+    let _ = fields.optional_f64("age");
+    let _ = fields.require_bool("admin");
+    match fields.finish() {
+        Err(e) => println!("✓ FieldExtractor collected problems: {}", e),
+        Ok(()) => println!("✗ FieldExtractor should have reported errors"),
+    }
+
+    // minify keeps tokens verbatim
+    let pretty = "{\n  \"price\": 1.50,\n  \"big\": 1e+2,\n  \"list\": [ \"a b\", -0.0 ]\n}\n";
+    match minify(pretty) {
+        Ok(out) if out == "{\"price\":1.50,\"big\":1e+2,\"list\":[\"a b\",-0.0]}"
+            && Parser::new(&out).parse().ok() == Parser::new(pretty).parse().ok() =>
+        {
+            println!("✓ minify preserved tokens: {}", out)
+        }
+        Ok(out) => println!("✗ Unexpected minify output: {}", out),
+        Err(e) => println!("✗ Failed to minify: {}", e),
+    }
+
+    // Raw control characters are rejected unless explicitly allowed
+    let mut parser = Parser::new("\"line one\nline two\"");
+    match parser.parse() {
+        Err(e) => println!("✓ Rejected raw newline in string: {}", e),
+        Ok(val) => println!("✗ Should have rejected raw newline, got: {:?}", val),
+    }
+    let options = ParserOptions {
+        allow_control_characters: true,
+        ..Default::default()
+    };
+    let mut parser = Parser::with_options("\"line one\nline two\"", options);
+    match parser.parse() {
+        Ok(JsonValue::String(s)) if s == "line one\nline two" => println!("✓ Lenient mode kept raw newline"),
+        Ok(other) => println!("✗ Expected string with newline, got: {:?}", other),
+        Err(e) => println!("✗ Lenient mode failed: {}", e),
+    }
+
+    // Numbers serialize to text that parses back to the identical f64
+    let tricky = [
+        0.1, 1.0 / 3.0, -0.0, 1e300, -1e-300, 5e-324, f64::MAX, f64::MIN_POSITIVE,
+        9007199254740993.0, 123456789012345680000.0, 1e21, 1e-7, 0.000001,
+    ];
+    let broken: Vec<String> = tricky
+        .iter()
+        .filter_map(|&n| {
+            let text = JsonValue::Number(n).to_string();
+            match Parser::new(&text).parse() {
+                Ok(JsonValue::Number(back)) if back.to_bits() == n.to_bits() => None,
+                other => Some(format!("{} -> {} -> {:?}", n, text, other)),
+            }
+        })
+        .collect();
+    if broken.is_empty() {
+        println!("✓ {} tricky numbers round-tripped bit-for-bit", tricky.len());
+    } else {
+        println!("✗ Numbers changed on round trip: {:?}", broken);
+    }
+
+    // Checked integer accessors refuse to truncate or guess
+    let cases: Vec<(f64, Option<i64>, Option<u64>)> = vec![
+        (42.0, Some(42), Some(42)),
+        (-7.0, Some(-7), None),
+        (3.7, None, None),
+        (9007199254740991.0, Some(9007199254740991), Some(9007199254740991)),
+        (-9007199254740991.0, Some(-9007199254740991), None),
+        (9007199254740992.0, None, None),
+        (1e20, None, None),
+    ];
+    let wrong: Vec<f64> = cases
+        .iter()
+        .filter(|(n, i, u)| {
+            let value = JsonValue::Number(*n);
+            value.as_i64() != *i || value.as_u64() != *u || value.as_f64() != Some(*n)
+        })
+        .map(|(n, _, _)| *n)
+        .collect();
+    if wrong.is_empty() {
+        println!("✓ as_i64/as_u64 handled {} boundary cases", cases.len());
+    } else {
+        println!("✗ Integer accessors wrong for: {:?}", wrong);
+    }
+
+    // Parsing from any io::Read source
+    match from_reader("[1, {\"a\": true}]".as_bytes()) {
+        Ok(JsonValue::Array(arr)) if arr.len() == 2 => println!("✓ from_reader parsed a byte slice"),
+        Ok(other) => println!("✗ Expected 2-element array, got: {:?}", other),
+        Err(e) => println!("✗ from_reader failed: {}", e),
+    }
+    match from_reader(&[b'"', 0xff, b'"'][..]) {
+        Err(e) => println!("✓ from_reader rejected invalid UTF-8: {}", e),
+        Ok(val) => println!("✗ Should have rejected invalid UTF-8, got: {:?}", val),
+    }
+
+    // flatten produces path/leaf pairs
+    let value = Parser::new("{\"a\": {\"b\": 1}, \"c\": [2, 3]}").parse().unwrap();
+    let mut pairs: Vec<String> = value
+        .flatten()
+        .iter()
+        .map(|(path, leaf)| format!("{}={}", path, leaf))
+        .collect();
+    pairs.sort();
+    if pairs == ["a.b=1", "c.0=2", "c.1=3"] {
+        println!("✓ flatten produced {:?}", pairs);
+    } else {
+        println!("✗ Unexpected flatten output: {:?}", pairs);
+    }
+
+    // Dotted path access
+    let mut doc = Parser::new("{\"servers\": [{\"host\": \"a\"}, {\"host\": \"b\"}], \"a.b\": 1, \"a\": {\"b\": 2}}")
+        .parse()
+        .unwrap();
+    let checks = [
+        doc.get_path("servers[1].host") == Some(&JsonValue::String("b".to_string())),
+        doc.get_path("[\"a.b\"]") == Some(&JsonValue::Number(1.0)),
+        doc.get_path("a.b") == Some(&JsonValue::Number(2.0)),
+        doc.get_path("a[0]").is_none(),
+        doc.get_path("servers[").is_none(),
+    ];
+    if checks.iter().all(|ok| *ok) {
+        println!("✓ get_path handled nested, quoted and mismatched lookups");
+    } else {
+        println!("✗ get_path checks failed: {:?}", checks);
+    }
+    if let Some(host) = doc.get_path_mut("servers[0].host") {
+        *host = JsonValue::String("c".to_string());
+    }
+    match doc.try_get_path("servers[0].port") {
+        Err(e) if doc.get_path("servers[0].host") == Some(&JsonValue::String("c".to_string())) => {
+            println!("✓ get_path_mut edited in place; try_get_path reported: {}", e)
+        }
+        other => println!("✗ Unexpected try_get_path result: {:?}", other),
+    }
+
+    // unflatten rebuilds nesting and rejects conflicting paths
+    let original = Parser::new("{\"a\": {\"b\": 1}, \"c\": [2, [3, {}]], \"d\": []}").parse().unwrap();
+    match JsonValue::unflatten(&original.flatten()) {
+        Ok(rebuilt) if rebuilt == original => println!("✓ unflatten(flatten(v)) == v"),
+        Ok(rebuilt) => println!("✗ unflatten changed the value: {}", rebuilt),
+        Err(e) => println!("✗ unflatten failed: {}", e),
+    }
+    let conflicting = vec![
+        ("a".to_string(), JsonValue::Number(1.0)),
+        ("a.b".to_string(), JsonValue::Number(2.0)),
+    ];
+    match JsonValue::unflatten(&conflicting) {
+        Err(e) => println!("✓ unflatten rejected conflict: {}", e),
+        Ok(val) => println!("✗ Should have rejected conflicting paths, got: {}", val),
+    }
+
+    // Builders produce the same value as the parsed document
+    let nickname: Option<&str> = None;
+    let built = ObjectBuilder::new()
+        .insert("method", "POST")
+        .insert("retries", 3)
+        .insert_some("nickname", nickname)
+        .insert_if(false, "debug", true)
+        .insert(
+            "body",
+            ObjectBuilder::new()
+                .insert("ids", ArrayBuilder::new().push(1).push(2).build())
+                .insert("note", JsonValue::Null)
+                .build(),
+        )
+        .build();
+    let parsed = Parser::new("{\"method\": \"POST\", \"retries\": 3, \"body\": {\"ids\": [1, 2], \"note\": null}}")
+        .parse()
+        .unwrap();
+    if built == parsed {
+        println!("✓ ObjectBuilder/ArrayBuilder matched the parsed payload");
+    } else {
+        println!("✗ Builder output differs: {}", built);
+    }
+
+    // Word count using only the entry API
+    let words = Parser::new("[\"a\", \"b\", \"a\", \"c\", \"a\"]").parse().unwrap();
+    let mut doc = JsonValue::Null;
+    if let JsonValue::Array(items) = &words {
+        for word in items {
+            if let JsonValue::String(word) = word {
+                doc.entry("stats")
+                    .unwrap()
+                    .or_insert_with(|| JsonValue::Object(Default::default()))
+                    .entry(word.as_str())
+                    .unwrap()
+                    .and_modify(|n| *n = JsonValue::Number(n.as_f64().unwrap_or(0.0) + 1.0))
+                    .or_insert(JsonValue::Number(1.0));
+            }
+        }
+    }
+    if doc.get_path("stats.a") == Some(&3.into()) && doc.get_path("stats.c") == Some(&1.into()) {
+        println!("✓ entry API counted words: {}", doc);
+    } else {
+        println!("✗ Unexpected word counts: {}", doc);
+    }
+
+    // parse_one leaves the parser just past the value
+    let text = "{\"név\": \"é\"} trailing text";
+    let mut parser = Parser::new(text);
+    match parser.parse_one() {
+        Ok(_) if &text[parser.byte_position()..] == " trailing text" && parser.position() == 12 => {
+            println!("✓ parse_one stopped at char {} / byte {}", parser.position(), parser.byte_position())
+        }
+        Ok(_) => println!("✗ Unexpected positions: {} / {}", parser.position(), parser.byte_position()),
+        Err(e) => println!("✗ parse_one failed: {}", e),
+    }
+
+    // Case-insensitive literals are opt-in
+    let legacy = "[TRUE, Null, False]";
+    let options = ParserOptions {
+        case_insensitive_literals: true,
+        ..Default::default()
+    };
+    match (Parser::new(legacy).parse(), Parser::with_options(legacy, options).parse()) {
+        (Err(_), Ok(value)) if value == JsonValue::from(vec![JsonValue::from(true), JsonValue::Null, false.into()]) => {
+            println!("✓ Mixed-case literals rejected by default, accepted when enabled")
+        }
+        other => println!("✗ Unexpected case-insensitive results: {:?}", other),
+    }
+
+    // take/remove move values out without cloning
+    let mut doc = Parser::new("{\"user\": {\"name\": \"Ann\"}, \"items\": [1, 2, 3]}").parse().unwrap();
+    let name = doc.get_path_mut("user.name").map(JsonValue::take);
+    let removed = doc.remove("items").and_then(|mut items| items.remove_index(1).zip(Some(items)));
+    let wrong_variant = (doc.remove_index(0), JsonValue::Null.remove("x"));
+    match (name, removed, wrong_variant) {
+        (Some(JsonValue::String(name)), Some((JsonValue::Number(n), rest)), (None, None))
+            if name == "Ann" && n == 2.0 && rest == JsonValue::from(vec![1, 3])
+                && doc.get_path("user.name") == Some(&JsonValue::Null)
+                && doc.get_path("items").is_none() =>
+        {
+            println!("✓ take/remove/remove_index left the document as documented: {}", doc)
+        }
+        other => println!("✗ Unexpected take/remove results: {:?}", other),
+    }
+
+    // Structural metrics
+    let value = Parser::new("{\"a\": [1, [2, {}]], \"b\": null}").parse().unwrap();
+    if value.node_count() == 7 && value.depth() == 4 && JsonValue::Null.depth() == 0 {
+        println!("✓ node_count = {}, depth = {}", value.node_count(), value.depth());
+    } else {
+        println!("✗ Unexpected metrics: node_count = {}, depth = {}", value.node_count(), value.depth());
+    }
+
+    // Borrowed parsing only allocates strings that needed unescaping
+    let text = "{\"plain\": \"héllo\", \"esc\\n\": \"a\\tb\", \"list\": [\"x\", 1]}";
+    match Parser::parse_borrowed(text) {
+        Ok(JsonValueRef::Object(map)) => {
+            let borrowed_plain = matches!(map.get("plain"), Some(JsonValueRef::String(Cow::Borrowed("héllo"))));
+            let owned_escaped = matches!(map.get("esc\n"), Some(JsonValueRef::String(Cow::Owned(s))) if s == "a\tb");
+            let key_kinds = map.keys().all(|k| matches!(k, Cow::Owned(_)) == (k == "esc\n"));
+            let same = JsonValueRef::Object(map).into_owned() == Parser::new(text).parse().unwrap();
+            if borrowed_plain && owned_escaped && key_kinds && same {
+                println!("✓ parse_borrowed borrowed plain strings and owned escaped ones");
+            } else {
+                println!("✗ parse_borrowed chose wrongly: {} {} {} {}", borrowed_plain, owned_escaped, key_kinds, same);
+            }
+        }
+        other => println!("✗ Expected borrowed object, got: {:?}", other),
+    }
+
+    // Single-quoted strings are opt-in
+    let json5ish = "{'name': 'it\\'s \"quoted\"', \"list\": ['a', \"b\"]}";
+    let options = ParserOptions {
+        allow_single_quotes: true,
+        ..Default::default()
+    };
+    match (Parser::new(json5ish).parse(), Parser::with_options(json5ish, options).parse()) {
+        (Err(_), Ok(value)) if value.get_path("name") == Some(&"it's \"quoted\"".into()) => {
+            println!("✓ Single quotes rejected by default, accepted when enabled")
+        }
+        other => println!("✗ Unexpected single-quote results: {:?}", other),
+    }
+
+    // Unquoted identifier keys are opt-in
+    let config = "{name: \"x\", $id: 1, _max2: [], \"quoted\": true}";
+    let options = ParserOptions {
+        allow_unquoted_keys: true,
+        ..Default::default()
+    };
+    let lenient = Parser::with_options(config, options.clone()).parse();
+    match (Parser::new(config).parse(), lenient, Parser::with_options("{2x: 1}", options).parse()) {
+        (Err(_), Ok(value), Err(_)) if value.get_path("$id") == Some(&1.into()) && value.node_count() == 5 => {
+            println!("✓ Unquoted keys accepted only when enabled: {}", value)
+        }
+        other => println!("✗ Unexpected unquoted-key results: {:?}", other),
+    }
+
+    // ToJson serializes nested structs through manual impls
+    let company = Company {
+        address: Address { city: "Oslo".to_string() },
+        staff: vec![Address { city: "Bergen".to_string() }],
+        motto: None,
+    };
+    let text = company.to_json().to_string();
+    let single = company.address.to_json().to_string();
+    match Parser::new(&text).parse() {
+        Ok(value) if single == "{\"city\": \"Oslo\"}"
+            && value.get_path("staff[0].city") == Some(&"Bergen".into())
+            && value.get_path("motto").is_none() =>
+        {
+            println!("✓ ToJson produced {}", text)
+        }
+        other => println!("✗ Unexpected ToJson output {}: {:?}", text, other),
+    }
+
+    // Hex numbers are opt-in and need at least one digit
+    let options = ParserOptions {
+        allow_hex_numbers: true,
+        ..Default::default()
+    };
+    let hex = Parser::with_options("[0xFF, -0x1a, 0X0]", options.clone()).parse();
+    match (Parser::new("0xFF").parse(), hex, Parser::with_options("0x", options).parse()) {
+        (Err(_), Ok(value), Err(e)) if value == JsonValue::from(vec![255, -26, 0]) => {
+            println!("✓ Hex numbers parsed when enabled; bare 0x rejected: {}", e)
+        }
+        other => println!("✗ Unexpected hex results: {:?}", other),
+    }
+
+    // JSON5 number forms are rejected in strict mode and accepted in JSON5 mode
+    let forms: Vec<(&str, f64)> = vec![("+5", 5.0), (".5", 0.5), ("5.", 5.0), ("-.25e1", -2.5), ("+0x10", 16.0)];
+    let strict_rejects = forms.iter().all(|(input, _)| Parser::new(input).parse().is_err());
+    let json5_accepts = forms.iter().all(|(input, expected)| {
+        Parser::with_options(input, ParserOptions::json5()).parse().ok() == Some(JsonValue::Number(*expected))
+    });
+    let lone_dot = Parser::with_options(".", ParserOptions::json5()).parse().is_err();
+    if strict_rejects && json5_accepts && lone_dot {
+        println!("✓ JSON5 number forms gated behind the JSON5 options");
+    } else {
+        println!("✗ JSON5 numbers: strict rejects {}, json5 accepts {}, lone dot rejected {}", strict_rejects, json5_accepts, lone_dot);
+    }
+
+    // Number text matches what JavaScript's JSON.stringify produces
+    let expected = [
+        (0.1, "0.1"),
+        (1.0, "1"),
+        (-2.5, "-2.5"),
+        (1234567.0, "1234567"),
+        (0.000001, "0.000001"),
+        (1e-7, "1e-7"),
+        (1e21, "1e+21"),
+        (5e-324, "5e-324"),
+        (1.7976931348623157e308, "1.7976931348623157e+308"),
+        (123456789012345680000.0, "123456789012345680000"),
+    ];
+    let mismatched: Vec<String> = expected
+        .iter()
+        .map(|&(n, want)| (JsonValue::Number(n).to_string(), want))
+        .filter(|(got, want)| got != want)
+        .map(|(got, want)| format!("{} (expected {})", got, want))
+        .collect();
+    if mismatched.is_empty() {
+        println!("✓ Numbers use shortest JavaScript-compatible text");
+    } else {
+        println!("✗ Number text differs from JavaScript: {:?}", mismatched);
+    }
+
+    // The caching parser shares results for repeated inputs and evicts the
+    // least recently used document once full
+    let cache = CachingParser::new(2);
+    let object = cache.parse(r#"{"a": 1}"#).unwrap();
+    let array = cache.parse("[1, 2]").unwrap();
+    let object_again = cache.parse(r#"{"a": 1}"#).unwrap();
+    cache.parse("true").unwrap(); // evicts "[1, 2]", the least recently used
+    let array_again = cache.parse("[1, 2]").unwrap();
+    if Arc::ptr_eq(&object, &object_again) && !Arc::ptr_eq(&array, &array_again) && cache.len() == 2 && cache.parse("[1,").is_err() {
+        println!("✓ Caching parser reused and evicted entries as expected");
+    } else {
+        println!("✗ Caching parser misbehaved (len {})", cache.len());
+    }
+
+    // Number formatting knobs apply the same way in compact and pretty output
+    let numbers = JsonValue::Array(vec![
+        JsonValue::Number(1234567.0),
+        JsonValue::Number(0.000012),
+        JsonValue::Number(1e21),
+    ]);
+    let styles = [
+        (WriteOptions::default(), "[1234567, 0.000012, 1e+21]"),
+        (
+            WriteOptions { max_significant_digits: Some(3), ..Default::default() },
+            "[1230000, 0.000012, 1e+21]",
+        ),
+        (
+            WriteOptions { exponent: ExponentFormat::Always, ..Default::default() },
+            "[1.234567e+6, 1.2e-5, 1e+21]",
+        ),
+        (
+            WriteOptions { exponent: ExponentFormat::Never, ..Default::default() },
+            "[1234567, 0.000012, 1000000000000000000000]",
+        ),
+        (
+            WriteOptions { exponent: ExponentFormat::Auto { small: 1e-3, large: 1e6 }, ..Default::default() },
+            "[1.234567e+6, 1.2e-5, 1e+21]",
+        ),
+        (
+            WriteOptions { integral_trailing_zero: true, ..Default::default() },
+            "[1234567.0, 0.000012, 1e+21]",
+        ),
+    ];
+    let wrong: Vec<String> = styles
+        .iter()
+        .filter_map(|(options, want)| {
+            let compact = numbers.to_string_with(options);
+            let pretty = numbers.to_string_with(&WriteOptions { indent: Some(2), ..options.clone() });
+            let pretty_flat = pretty.replace("\n  ", " ").replace("[ ", "[").replace("\n]", "]");
+            (compact != *want || pretty_flat != *want).then(|| format!("{} / {:?} (expected {})", compact, pretty, want))
+        })
+        .collect();
+    let default_round_trips = matches!(
+        Parser::new(&numbers.to_string_with(&WriteOptions::default())).parse(),
+        Ok(ref back) if *back == numbers
+    );
+    if wrong.is_empty() && default_round_trips {
+        println!("✓ Number formatting options applied in compact and pretty output");
+    } else {
+        println!("✗ Number formatting options misapplied: {:?}", wrong);
+    }
+
+    // Shared values clone without copying and can be read from other threads
+    let document = Parser::new(r#"{"users": [{"name": "Ann"}, {"name": "Bo"}], "version": 3}"#)
+        .parse()
+        .unwrap();
+    let shared = document.clone().into_shared();
+    let handles: Vec<_> = (0..2)
+        .map(|i| {
+            let shared = shared.clone();
+            std::thread::spawn(move || shared.get("users").and_then(|u| u.get_index(i)).cloned())
+        })
+        .collect();
+    let names: Vec<Option<JsonValue>> = handles
+        .into_iter()
+        .map(|h| h.join().unwrap().map(|user| user.to_owned()))
+        .collect();
+    let same_array = match (shared.get("users"), shared.clone().get("users")) {
+        (Some(SharedJson::Array(a)), Some(SharedJson::Array(b))) => Arc::ptr_eq(a, b),
+        _ => false,
+    };
+    if same_array && shared.to_owned() == document && names.iter().all(Option::is_some) {
+        println!("✓ SharedJson clones share structure across threads");
+    } else {
+        println!("✗ SharedJson misbehaved: {:?}", names);
+    }
+
+    // Writing to an io::Write produces the same bytes as the string forms
+    let value = Parser::new(r#"{"name": "tab\there", "list": [1, 2.5, true, null], "empty": {}}"#)
+        .parse()
+        .unwrap();
+    let mut compact = Vec::new();
+    let mut pretty = std::io::BufWriter::new(Vec::new());
+    let written = value.to_writer(&mut compact).and_then(|_| value.to_writer_pretty(&mut pretty, 2));
+    match (written, pretty.into_inner()) {
+        (Ok(()), Ok(pretty)) if compact == value.to_string().into_bytes() && pretty == value.to_string_pretty(2).into_bytes() => {
+            println!("✓ to_writer output matches to_string and to_string_pretty")
+        }
+        (result, _) => println!("✗ to_writer output differed ({:?})", result),
+    }
+
+    // Fluent builders start from JsonValue::array() and JsonValue::object()
+    let rows = ["alpha", "beta"]
+        .iter()
+        .enumerate()
+        .fold(JsonValue::array(), |rows, (id, name)| {
+            rows.push(JsonValue::object().set("id", id as u32).set("name", *name).build())
+        })
+        .build();
+    let expected = Parser::new(r#"[{"id": 0, "name": "alpha"}, {"id": 1, "name": "beta"}]"#).parse().unwrap();
+    if rows == expected {
+        println!("✓ JsonValue::array()/object() built nested values");
+    } else {
+        println!("✗ Fluent builders produced {}", rows);
+    }
+
+    // Reader input is parsed incrementally; tokens split across reads of a
+    // few bytes each still parse, and errors carry absolute stream positions
+    struct Trickle<'a> {
+        bytes: &'a [u8],
+        step: usize,
+    }
+    impl std::io::Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.step = self.step % 3 + 1;
+            let n = self.step.min(self.bytes.len()).min(buf.len());
+            buf[..n].copy_from_slice(&self.bytes[..n]);
+            self.bytes = &self.bytes[n..];
+            Ok(n)
+        }
+    }
+    let text = r#"{"escaped": "line\nbreak \"quoted\"", "number": -12345.678e-2, "accents": "héllo wörld ✓", "nested": [true, null, 0x]}"#;
+    let fixed = text.replace("0x", "0");
+    let expected = Parser::new(&fixed).parse().unwrap();
+    let parsed = (0..3)
+        .map(|step| from_reader(Trickle { bytes: fixed.as_bytes(), step }))
+        .collect::<Result<Vec<_>, _>>();
+    let bad_token = from_reader(Trickle { bytes: text.as_bytes(), step: 0 });
+    let bad_utf8 = from_reader(Trickle { bytes: b"[\"ok\", \"\xff\"]", step: 0 });
+    let truncated = from_reader(Trickle { bytes: "\"caf\u{e9}".as_bytes(), step: 0 });
+    match (parsed, bad_token, bad_utf8, truncated) {
+        (Ok(values), Err(token), Err(utf8), Err(end))
+            if values.iter().all(|v| *v == expected)
+                && token.position == Parser::new(text).parse().unwrap_err().position
+                && utf8.position == 8
+                && end.message == "unexpected end of input in string" =>
+        {
+            println!("✓ Incremental reader parsed split tokens and reported stream positions")
+        }
+        other => println!("✗ Incremental reader misbehaved: {:?}", other),
+    }
+
+    // contains() matches object subsets and in-order array subsequences
+    let response = Parser::new(r#"{"id": 7, "user": {"name": "Ann", "roles": ["admin", "dev", "ops"]}, "ok": true}"#)
+        .parse()
+        .unwrap();
+    let subset = |text: &str| Parser::new(text).parse().unwrap();
+    let matches = [
+        (r#"{}"#, true),
+        (r#"{"ok": true}"#, true),
+        (r#"{"user": {"roles": ["admin", "ops"]}}"#, true),
+        (r#"{"user": {"roles": ["ops", "admin"]}}"#, false),
+        (r#"{"user": {"name": "Bo"}}"#, false),
+        (r#"{"missing": null}"#, false),
+        (r#"{"id": "7"}"#, false),
+    ];
+    let wrong: Vec<&str> = matches
+        .iter()
+        .filter(|(text, want)| response.contains(&subset(text)) != *want)
+        .map(|(text, _)| *text)
+        .collect();
+    if wrong.is_empty() {
+        println!("✓ contains() matched {} subsets as expected", matches.len());
+    } else {
+        println!("✗ contains() wrong for {:?}", wrong);
+    }
+
+    // Schema validation reports every violation with a JSON Pointer path
+    let schema = Parser::new(
+        r#"{
+            "type": "object",
+            "required": ["id", "name", "tags"],
+            "properties": {
+                "id": {"type": "integer", "minimum": 1},
+                "name": {"type": "string", "minLength": 2, "maxLength": 8},
+                "status": {"enum": ["active", "retired"]},
+                "tags": {"type": "array", "items": {"type": ["string", "null"]}},
+                "a/b": {"type": "boolean"}
+            }
+        }"#,
+    )
+    .parse()
+    .unwrap();
+    let good = Parser::new(r#"{"id": 3, "name": "Ann", "status": "active", "tags": ["x", null]}"#).parse().unwrap();
+    let bad = Parser::new(r#"{"id": 0.5, "name": "A", "status": "gone", "tags": ["x", 2], "a/b": 1}"#).parse().unwrap();
+    let mut paths: Vec<String> = bad
+        .validate_schema(&schema)
+        .unwrap_err()
+        .into_iter()
+        .map(|e| e.path)
+        .collect();
+    paths.sort();
+    let expected_paths = ["/a~1b", "/id", "/id", "/name", "/status", "/tags/1"];
+    if good.validate_schema(&schema).is_ok() && paths == expected_paths {
+        println!("✓ Schema validation collected {} violations", paths.len());
+    } else {
+        println!("✗ Schema validation reported {:?}", paths);
+    }
+
+    // A leading byte order mark is skipped; positions still count it
+    let with_bom = Parser::new("\u{FEFF}{\"a\": 1}").parse();
+    let from_bytes = from_reader(&b"\xEF\xBB\xBF[true]"[..]);
+    let only_bom = Parser::new("\u{FEFF}").parse();
+    let middle_bom = Parser::new("[1, \u{FEFF}2]").parse();
+    match (with_bom, from_bytes, only_bom, middle_bom) {
+        (Ok(JsonValue::Object(_)), Ok(JsonValue::Array(_)), Err(end), Err(middle))
+            if end.message == "unexpected end of input; expected a value" && end.position == 0 && middle.position == 4 =>
+        {
+            println!("✓ Leading BOM skipped, other BOMs rejected at their position")
+        }
+        other => println!("✗ BOM handling wrong: {:?}", other),
+    }
+
+    // parse_bytes detects UTF-8/16/32 in either byte order, with or without BOM
+    let text = "{\"name\": \"Zoë 🎉\", \"n\": [1, 2]}";
+    let expected = Parser::new(text).parse().unwrap();
+    let encode = |encoding: Encoding, bom: bool| -> Vec<u8> {
+        let chars = bom.then_some('\u{FEFF}').into_iter().chain(text.chars());
+        match encoding {
+            Encoding::Utf8 => chars.collect::<String>().into_bytes(),
+            Encoding::Utf16Le | Encoding::Utf16Be => chars
+                .collect::<String>()
+                .encode_utf16()
+                .flat_map(|u| if encoding == Encoding::Utf16Le { u.to_le_bytes() } else { u.to_be_bytes() })
+                .collect(),
+            Encoding::Utf32Le | Encoding::Utf32Be => chars
+                .flat_map(|c| if encoding == Encoding::Utf32Le { (c as u32).to_le_bytes() } else { (c as u32).to_be_bytes() })
+                .collect(),
+        }
+    };
+    let encodings = [Encoding::Utf8, Encoding::Utf16Le, Encoding::Utf16Be, Encoding::Utf32Le, Encoding::Utf32Be];
+    let failures: Vec<String> = encodings
+        .iter()
+        .flat_map(|&encoding| [(encoding, false), (encoding, true)])
+        .filter_map(|(encoding, bom)| {
+            let bytes = encode(encoding, bom);
+            match (detect_encoding(&bytes), parse_bytes(&bytes)) {
+                (detected, Ok(value)) if detected == encoding && value == expected => None,
+                (detected, result) => Some(format!("{} (bom: {}) detected as {}: {:?}", encoding, bom, detected, result)),
+            }
+        })
+        .collect();
+    let odd = parse_bytes(b"[\x001\x00]");
+    match odd {
+        Err(e) if failures.is_empty() && e.message.contains("UTF-16LE") => {
+            println!("✓ parse_bytes decoded all {} encodings with and without BOM", encodings.len())
+        }
+        other => println!("✗ Encoding detection failed: {:?} / {:?}", failures, other),
+    }
+
+    // Mixed arrays sort deterministically, and objects sort by a pointer
+    let mut mixed = vec![
+        JsonValue::String("b".into()),
+        JsonValue::Number(f64::NAN),
+        JsonValue::Array(vec![]),
+        JsonValue::Number(0.0),
+        JsonValue::Boolean(true),
+        JsonValue::Number(-0.0),
+        JsonValue::Null,
+        JsonValue::String("a".into()),
+        JsonValue::Number(-3.0),
+        JsonValue::Boolean(false),
+    ];
+    mixed.sort_by(JsonValue::total_cmp);
+    let order: Vec<String> = mixed.iter().map(|v| format!("{:?}", v)).collect();
+    let expected_order = [
+        "Null", "Boolean(false)", "Boolean(true)", "Number(-3.0)", "Number(-0.0)", "Number(0.0)", "Number(NaN)",
+        "String(\"a\")", "String(\"b\")", "Array([])",
+    ];
+    let mut people = match Parser::new(r#"[{"n": {"age": 30}}, {"n": {"age": "?"}}, {"n": {}}, {"n": {"age": 4}}]"#).parse() {
+        Ok(JsonValue::Array(people)) => people,
+        _ => vec![],
+    };
+    people.sort_by(|a, b| a.compare_by_pointer(b, "/n/age"));
+    let ages: Vec<Option<String>> = people.iter().map(|p| p.pointer("/n/age").map(|a| a.to_string())).collect();
+    let expected_ages = [None, Some("4".to_string()), Some("30".to_string()), Some("\"?\"".to_string())];
+    if order == expected_order && ages == expected_ages {
+        println!("✓ total_cmp and compare_by_pointer sorted deterministically");
+    } else {
+        println!("✗ Sorting gave {:?} and {:?}", order, ages);
+    }
+
+    // numbers_as_strings keeps the exact text of every number
+    let options = ParserOptions {
+        numbers_as_strings: true,
+        ..Default::default()
+    };
+    let text = "[12345678901234567890123, 0.1000, -1E+2, 1e400]";
+    let raw = Parser::with_options(text, options.clone()).parse();
+    let json5 = Parser::with_options("[+1, .5, 0x10]", ParserOptions { numbers_as_strings: true, ..ParserOptions::json5() }).parse();
+    match (raw, json5) {
+        (Ok(raw), Ok(json5))
+            if raw.to_string() == text
+                && Parser::with_options(&raw.to_string_pretty(2), options).parse().ok() == Some(raw.clone())
+                && raw.get_path("[2]").and_then(JsonValue::as_f64) == Some(-100.0)
+                && matches!(raw.get_path("[0]"), Some(JsonValue::RawNumber(s)) if s == "12345678901234567890123")
+                && json5.to_string() == "[1, 0.5, 16]" =>
+        {
+            println!("✓ numbers_as_strings passed number text through unchanged")
+        }
+        other => println!("✗ numbers_as_strings misbehaved: {:?}", other),
+    }
+
+    // \u escapes decode, surrogate pairs combine, and lone surrogates follow
+    // the configured policy
+    let decoded = Parser::new(r#""caf\u00e9 \ud83c\udf89 \u0041""#).parse();
+    let lossy = ParserOptions {
+        lone_surrogates: SurrogatePolicy::Lossy,
+        ..Default::default()
+    };
+    let cases = [
+        (r#""x\ud83cy""#, "x\u{FFFD}y", 2),
+        (r#""\udead""#, "\u{FFFD}", 1),
+        (r#""\ud83c\u0041""#, "\u{FFFD}A", 1),
+        (r#""\ud83c\ud83c\udf89""#, "\u{FFFD}🎉", 1),
+    ];
+    let wrong: Vec<&str> = cases
+        .iter()
+        .filter(|(input, replaced, position)| {
+            let strict_ok = matches!(Parser::new(input).parse(), Err(e) if e.position == *position);
+            let lossy_ok = matches!(Parser::with_options(input, lossy.clone()).parse(), Ok(JsonValue::String(s)) if s == *replaced);
+            !(strict_ok && lossy_ok)
+        })
+        .map(|(input, _, _)| *input)
+        .collect();
+    let control = JsonValue::String("bell\u{7}\u{1f}".into());
+    let control_round_trip = Parser::new(&control.to_string()).parse().ok() == Some(control.clone());
+    match decoded {
+        Ok(JsonValue::String(s)) if s == "café 🎉 A" && wrong.is_empty() && control_round_trip => {
+            println!("✓ Unicode escapes and lone surrogate policies behaved")
+        }
+        other => println!("✗ Unicode escapes wrong: {:?} {:?} {}", other, wrong, control),
+    }
+
+    // Only space, tab, LF and CR are whitespace unless Unicode whitespace is allowed
+    let strict_ok = Parser::new(" \t\r\n[1,\n 2]\r\n").parse().is_ok();
+    let exotic = ["[1,\u{A0}2]", "\u{0C}true", "null\u{2003}", "[\u{85}]"];
+    let rejected = exotic.iter().all(|input| Parser::new(input).parse().is_err());
+    let unicode = ParserOptions {
+        allow_unicode_whitespace: true,
+        ..Default::default()
+    };
+    let accepted = exotic.iter().all(|input| Parser::with_options(input, unicode.clone()).parse().is_ok());
+    if strict_ok && rejected && accepted {
+        println!("✓ Strict whitespace rejected Unicode spaces; the option accepted them");
+    } else {
+        println!("✗ Whitespace handling wrong (strict {}, rejected {}, accepted {})", strict_ok, rejected, accepted);
+    }
+
+    // Input and string length limits reject oversized input while scanning
+    let input_limit = ParserOptions {
+        max_input_length: Some(16),
+        ..Default::default()
+    };
+    let string_limit = ParserOptions {
+        max_string_length: Some(5),
+        ..Default::default()
+    };
+    let limited = |input: &str, options: &ParserOptions| Parser::with_options(input, options.clone()).parse();
+    let under_ok = limited("[1, 2, 3, 4, 5] ", &input_limit).is_ok()
+        && limited(r#"["12345", "\u00e9\u00e9x"]"#, &string_limit).is_ok();
+    let over = [
+        ("[1, 2, 3, 4, 5]  ", &input_limit, 16),
+        (r#"["123456"]"#, &string_limit, 1),
+        (r#"{"\u00e9\u00e9\u00e9": 1}"#, &string_limit, 1),
+    ];
+    let over_ok = over
+        .iter()
+        .all(|(input, options, position)| matches!(limited(input, options), Err(e) if e.position == *position));
+    // Endless readers stop at the limits instead of buffering forever
+    let endless_string = Parser::with_reader(std::io::Read::chain(&b"\""[..], std::io::repeat(b'a')), string_limit).parse();
+    let endless_space = Parser::with_reader(std::io::repeat(b' '), input_limit).parse();
+    match (under_ok, over_ok, endless_string, endless_space) {
+        (true, true, Err(string), Err(space)) if string.message.starts_with("string") && space.position == 16 => {
+            println!("✓ Input and string length limits enforced while scanning")
+        }
+        other => println!("✗ Length limits wrong: {:?}", other),
+    }
+
+    // Container size and node count limits
+    let options = ParserOptions {
+        max_array_length: Some(3),
+        max_object_members: Some(2),
+        max_nodes: Some(7),
+        ..Default::default()
+    };
+    let limited = |input: &str| Parser::with_options(input, options.clone()).parse();
+    let at_caps = limited(r#"[1, 2, {"a": 3, "b": 4}]"#);
+    let long_array = limited("[[], [1, 2, 3, 4]]");
+    let wide_object = limited(r#"{"a": 1, "b": 2, "c": 3}"#);
+    let too_many = limited("[[1], [2], [3, 4]]");
+    match (at_caps, long_array, wide_object, too_many) {
+        (Ok(_), Err(array), Err(object), Err(nodes))
+            if array.position == 5 && object.position == 0 && nodes.message.contains("maximum of 7") =>
+        {
+            println!("✓ Array, object and node count limits enforced")
+        }
+        other => println!("✗ Container limits wrong: {:?}", other),
+    }
+
+    // Errors carry a kind that can be matched on
+    let kinds: Vec<_> = [r#""abc"#, "[1, 2,]", "1x", "-x", "[1} ", "{} x", r#"{1: 2}"#, r#""\q""#]
+        .iter()
+        .map(|input| Parser::new(input).parse().map_err(|e| e.kind))
+        .collect();
+    let expected = [
+        Err(ParseErrorKind::UnterminatedString),
+        Err(ParseErrorKind::TrailingComma),
+        Err(ParseErrorKind::TrailingData),
+        Err(ParseErrorKind::InvalidNumber),
+        Err(ParseErrorKind::UnexpectedChar('}')),
+        Err(ParseErrorKind::TrailingData),
+        Err(ParseErrorKind::InvalidKey),
+        Err(ParseErrorKind::InvalidEscape),
+    ];
+    let utf8 = from_reader(&b"[\"\xff\"]"[..]).map_err(|e| e.kind);
+    if kinds == expected && utf8 == Err(ParseErrorKind::InvalidEncoding(Encoding::Utf8)) {
+        println!("✓ Parse errors report a matchable kind")
+    } else {
+        println!("✗ Parse error kinds wrong: {:?} {:?}", kinds, utf8);
+    }
+
+    // Wrong-case literals get a suggestion pointing at the word
+    let hints: Vec<_> = ["True", "[1, FALSE]", r#"{"a": Null}"#, "banana"]
+        .iter()
+        .map(|input| Parser::new(input).parse().map_err(|e| (e.message, e.position)))
+        .collect();
+    let expected_hints = [
+        Err(("found 'True'; JSON literals are lowercase: did you mean 'true'?".to_string(), 0)),
+        Err(("found 'FALSE'; JSON literals are lowercase: did you mean 'false'?".to_string(), 4)),
+        Err(("found 'Null'; JSON literals are lowercase: did you mean 'null'?".to_string(), 6)),
+        Err(("unexpected character: b".to_string(), 0)),
+    ];
+    if hints == expected_hints {
+        println!("✓ Wrong-case literals suggest the lowercase spelling")
+    } else {
+        println!("✗ Literal suggestions wrong: {:?}", hints);
+    }
+
+    // Errors work as `dyn Error`, with parse errors as the source
+    let boxed: Result<JsonValue, Box<dyn std::error::Error>> = (|| Ok(Parser::new("[1,").parse()?))();
+    let wrapped = JsonError::from(Parser::new("{").parse().unwrap_err());
+    let source = std::error::Error::source(&wrapped).map(ToString::to_string);
+    match (boxed, source) {
+        (Err(e), Some(source)) if e.to_string().contains("position 2") && source.contains("position 0") => {
+            println!("✓ Error types implement std::error::Error")
+        }
+        other => println!("✗ Error trait impls wrong: {:?}", other),
+    }
+
+    // Number overflow policy
+    let with_policy = |policy| ParserOptions {
+        number_overflow: policy,
+        ..Default::default()
+    };
+    let overflow = |input: &str, policy| Parser::with_options(input, with_policy(policy)).parse();
+    let saturated = overflow("[1e400, -1e400]", OverflowPolicy::SaturateToInfinity);
+    let clamped = overflow("[1e400, -1e400]", OverflowPolicy::ClampToMax);
+    let rejected = overflow("[1, 1e400]", OverflowPolicy::Error).map_err(|e| (e.kind, e.position));
+    let validated = Parser::with_options("1e400", with_policy(OverflowPolicy::Error)).validate().is_err();
+    let saturated_ok = saturated.is_ok_and(|v| v == JsonValue::from(vec![f64::INFINITY, f64::NEG_INFINITY]));
+    let clamped_ok = clamped.is_ok_and(|v| v == JsonValue::from(vec![f64::MAX, f64::MIN]));
+    if saturated_ok && clamped_ok && rejected == Err((ParseErrorKind::NumberOutOfRange, 4)) && validated {
+        println!("✓ Overflowing numbers saturate, clamp or fail as configured")
+    } else {
+        println!("✗ Overflow policy wrong: {:?}", rejected);
+    }
+
+    // Error byte offsets slice the input cleanly after multi-byte chars
+    let emoji_inputs = [r#"{"🎉": "ü", "x": @}"#, r#"["日本語", "🎉🎉", 01]"#, "[\"🎉\", [1, 2, 3, 4]]"];
+    let offsets_ok = emoji_inputs.iter().all(|input| {
+        let options = ParserOptions {
+            max_array_length: Some(3),
+            ..Default::default()
+        };
+        let from_str = Parser::with_options(input, options.clone()).parse().unwrap_err();
+        let from_reader = Parser::with_reader(input.as_bytes(), options).parse().unwrap_err();
+        let rest = &input[from_str.byte_offset..];
+        from_str.byte_offset == from_reader.byte_offset
+            && input[..from_str.byte_offset].chars().count() == from_str.position
+            && (rest.starts_with('@') || rest.starts_with('0') || rest.starts_with('['))
+    });
+    if offsets_ok {
+        println!("✓ Error byte offsets point into the original input")
+    } else {
+        println!("✗ Error byte offsets wrong");
+    }
+
+    // Display escapes object keys, so odd keys round-trip
+    let odd_keys = ObjectBuilder::new()
+        .insert("he said \"hi\"", 1)
+        .insert("back\\slash", 2)
+        .insert("tab\there", 3)
+        .build();
+    match Parser::new(&odd_keys.to_string()).parse() {
+        Ok(parsed) if parsed == odd_keys => println!("✓ Keys with quotes, backslashes and tabs round-trip"),
+        other => println!("✗ Key escaping wrong: {} -> {:?}", odd_keys, other),
+    }
+
+    // Owning iteration over arrays and objects
+    let owned_items: Vec<JsonValue> = Parser::new(r#"[1, "two", [3]]"#).parse().unwrap().into_elements().collect();
+    let mut children: Vec<Child> = Parser::new(r#"{"b": [2], "a": 1}"#).parse().unwrap().into_iter().collect();
+    children.sort_by(|x, y| x.key().cmp(&y.key()));
+    let mut owned_entries: Vec<(String, JsonValue)> =
+        Parser::new(r#"{"b": 2, "a": 1}"#).parse().unwrap().into_entries().collect();
+    owned_entries.sort_by(|x, y| x.0.cmp(&y.0));
+    let scalars_empty = JsonValue::from(5).into_iter().next().is_none()
+        && JsonValue::from("s").into_entries().next().is_none()
+        && JsonValue::object().build().into_elements().next().is_none();
+    let array_children: Vec<Child> = Parser::new("[true, null]").parse().unwrap().into_iter().collect();
+    if owned_items.len() == 3
+        && owned_items[1] == JsonValue::from("two")
+        && owned_entries == [("a".to_string(), JsonValue::from(1)), ("b".to_string(), JsonValue::from(2))]
+        && children
+            == [
+                Child::Member("a".to_string(), JsonValue::from(1)),
+                Child::Member("b".to_string(), JsonValue::Array(vec![2.into()])),
+            ]
+        && array_children == [Child::Element(true.into()), Child::Element(JsonValue::Null)]
+        && scalars_empty
+    {
+        println!("✓ Arrays and objects can be consumed by iteration")
+    } else {
+        println!("✗ Owning iteration wrong: {:?} {:?}", owned_items, owned_entries);
+    }
+
+    // transform rewrites every matching node in one pass
+    let mut scrubbed = Parser::new(r#"{"user": {"email": "a@b.c", "name": "Al"}, "cc": ["x@y.z", 3]}"#).parse().unwrap();
+    let mut visited = 0;
+    scrubbed.transform(|v| {
+        visited += 1;
+        match v {
+            JsonValue::String(s) if s.contains('@') => *v = JsonValue::from("REDACTED"),
+            JsonValue::Number(n) => *n *= 2.0,
+            _ => {}
+        }
+    });
+    let expected_scrub = Parser::new(r#"{"user": {"email": "REDACTED", "name": "Al"}, "cc": ["REDACTED", 6]}"#).parse().unwrap();
+    if scrubbed == expected_scrub && visited == 7 {
+        println!("✓ transform visits and rewrites every node")
+    } else {
+        println!("✗ transform wrong: {} ({} visits)", scrubbed, visited);
+    }
+
+    // Non-finite numbers: an error by default, or null or JSON5 tokens
+    let non_finite = JsonValue::from(vec![f64::NAN, f64::INFINITY, f64::NEG_INFINITY]);
+    let policy_output = |policy, indent| {
+        let options = WriteOptions {
+            non_finite: policy,
+            indent,
+            ..Default::default()
+        };
+        non_finite.to_json_string_with(&options)
+    };
+    let strict_fails = [None, Some(2)]
+        .iter()
+        .all(|&indent| matches!(policy_output(NonFinitePolicy::Error, indent), Err(SerializeError::NonFinite(n)) if n.is_nan()));
+    let nulls = policy_output(NonFinitePolicy::Null, None).ok();
+    let literals = policy_output(NonFinitePolicy::Literal, None).ok();
+    let pretty_literals = policy_output(NonFinitePolicy::Literal, Some(2)).ok();
+    let mut sink = Vec::new();
+    let writer_fails = non_finite.to_writer(&mut sink).is_err_and(|e| e.kind() == std::io::ErrorKind::InvalidData);
+    if strict_fails
+        && nulls.as_deref() == Some("[null, null, null]")
+        && literals.as_deref() == Some("[NaN, Infinity, -Infinity]")
+        && pretty_literals.as_deref() == Some("[\n  NaN,\n  Infinity,\n  -Infinity\n]")
+        && non_finite.to_string() == "[null, null, null]"
+        && non_finite.to_string_pretty(2) == "[\n  null,\n  null,\n  null\n]"
+        && writer_fails
+    {
+        println!("✓ Non-finite numbers fail, become null or JSON5 tokens by policy")
+    } else {
+        println!("✗ Non-finite policy wrong: {:?} {:?} {:?}", nulls, literals, pretty_literals);
+    }
+
+    // Out-of-range numbers are rejected by default; underflow is fine
+    let huge_integer = format!("[1, {}]", "9".repeat(400));
+    let out_of_range = ["1e400", "-1e400", &huge_integer]
+        .iter()
+        .map(|input| Parser::new(input).parse().map_err(|e| (e.kind, e.position, e.message)))
+        .collect::<Vec<_>>();
+    let range_errors_ok = out_of_range
+        .iter()
+        .zip([0, 0, 4])
+        .all(|(result, position)| {
+            matches!(result, Err((ParseErrorKind::NumberOutOfRange, p, message)) if *p == position && message == "number out of range")
+        });
+    let underflow = Parser::new("1e-400").parse();
+    if range_errors_ok && matches!(underflow, Ok(JsonValue::Number(n)) if n == 0.0) && !is_valid("1e400") {
+        println!("✓ Numbers out of f64 range are errors; underflow parses as zero")
+    } else {
+        println!("✗ Out-of-range numbers wrong: {:?} {:?}", out_of_range, underflow);
+    }
+    // Validation only converts numbers near the edge of the range, and
+    // agrees with parsing on either side of it
+    let edge_integer = format!("{}.5", "9".repeat(308));
+    let edges = [
+        "1e308", "1.7976931348623157e308", "1.8e308", "0.1e309", "10e308", "1e-400", "0e99999999999999999999",
+        "123456e-99999999999999999999", &edge_integer, &huge_integer,
+    ];
+    let disagreeing: Vec<_> = edges
+        .iter()
+        .filter(|input| {
+            let parsed = Parser::new(input).parse().map(drop).map_err(|e| (e.kind, e.position));
+            parsed != Parser::new(input).validate().map_err(|e| (e.kind, e.position))
+        })
+        .collect();
+    if disagreeing.is_empty() && !is_valid("1.8e308") && is_valid("1.7976931348623157e308") {
+        println!("✓ validate finds out-of-range numbers where parse does")
+    } else {
+        println!("✗ validate and parse disagree on range: {:?}", disagreeing);
+    }
+
+    // Concatenated values parse one at a time
+    let streamed: Vec<_> = parse_stream(r#"{"a": 1}{"b": 2}[] 3 "x"null"#).collect();
+    let stream_ok = streamed.len() == 6 && streamed.iter().all(Result::is_ok) && parse_stream("  \n ").next().is_none();
+    let broken: Vec<_> = parse_stream("[1] [2,] [3]").collect();
+    match (stream_ok, broken.as_slice()) {
+        (true, [Ok(_), Err(e)]) if e.kind == ParseErrorKind::TrailingComma => {
+            println!("✓ Concatenated JSON values are parsed lazily, stopping at an error")
+        }
+        other => println!("✗ parse_stream wrong: {:?} {:?}", other, streamed),
+    }
+
+    // Errors at the end of the input point at the last char read and say what was cut off
+    let ends: Vec<_> = ["\"abc", "[1, 2, ", "{\"a\": ", "{\"a\": 1\n", "[-", "[1, tru", "[\"\u{e9}"]
+        .iter()
+        .map(|input| Parser::new(input).parse().map_err(|e| (e.message, e.position, e.byte_offset)))
+        .collect();
+    let expected = [
+        ("unexpected end of input in string", 3, 3),
+        ("unexpected end of input in array", 5, 5),
+        ("unexpected end of input in object", 4, 4),
+        ("unexpected end of input in object", 6, 6),
+        ("unexpected end of input in number; a digit must follow the sign", 1, 1),
+        ("unexpected end of input in 'true'", 6, 6),
+        ("unexpected end of input in string", 2, 2),
+    ];
+    let matches = ends.iter().zip(expected).all(|(end, (message, position, byte_offset))| {
+        matches!(end, Err((m, p, b)) if m == message && *p == position && *b == byte_offset)
+    });
+    if matches {
+        println!("✓ End-of-input errors point inside the input and name the construct");
+    } else {
+        println!("✗ End-of-input errors wrong: {:?}", ends);
+    }
+
+    // A zero followed by more digits is reported as a leading zero, not as trailing data
+    let zeros: Vec<_> = ["0123", "-0123", "[1, 0123]", "{\"a\": -007}"]
+        .iter()
+        .map(|input| Parser::new(input).parse().map_err(|e| (e.kind, e.message, e.position)))
+        .collect();
+    let leading_zero = |position| Err((ParseErrorKind::InvalidNumber, "leading zeros are not allowed in numbers".to_string(), position));
+    let allowed = ["0", "-0", "0.5", "[0, 10]"].iter().all(|input| Parser::new(input).parse().is_ok());
+    if zeros == [leading_zero(0), leading_zero(1), leading_zero(4), leading_zero(7)] && allowed {
+        println!("✓ Leading zeros rejected at the zero");
+    } else {
+        println!("✗ Leading zeros handled wrong: {:?}", zeros);
+    }
+
+    // Malformed number starts get an error saying what JSON requires instead
+    let cases = [
+        (".5", ParseErrorKind::UnexpectedChar('.'), 0, "numbers may not start with a decimal point (write 0.5)"),
+        ("-.25", ParseErrorKind::UnexpectedChar('.'), 1, "numbers may not start with a decimal point (write -0.25)"),
+        ("[1, .]", ParseErrorKind::UnexpectedChar('.'), 4, "numbers may not start with a decimal point"),
+        ("+1", ParseErrorKind::UnexpectedChar('+'), 0, "leading '+' is not allowed in numbers"),
+        ("{\"a\": +1}", ParseErrorKind::UnexpectedChar('+'), 6, "leading '+' is not allowed in numbers"),
+        ("-", ParseErrorKind::UnexpectedEnd, 0, "unexpected end of input in number; a digit must follow the sign"),
+        ("-x", ParseErrorKind::InvalidNumber, 1, "expected a digit after '-', found 'x'"),
+    ];
+    let wrong: Vec<_> = cases
+        .iter()
+        .filter(|(input, kind, position, message)| {
+            !matches!(Parser::new(input).parse(), Err(e) if e.kind == *kind && e.position == *position && e.message == *message)
+        })
+        .collect();
+    let json5_ok = [".5", "+1", "-.5"].iter().all(|input| Parser::with_options(input, ParserOptions::json5()).parse().is_ok());
+    if wrong.is_empty() && json5_ok {
+        println!("✓ Malformed number starts explain what JSON requires");
+    } else {
+        println!("✗ Malformed number diagnostics wrong for {:?}", wrong);
+    }
+
+    // Display sorts object keys, so equal objects always print the same
+    let forward = Parser::new(r#"{"b": 1, "a": {"d": 2, "c": 3}, "e": [{"g": 4, "f": 5}]}"#).parse().unwrap();
+    let backward = Parser::new(r#"{"e": [{"f": 5, "g": 4}], "a": {"c": 3, "d": 2}, "b": 1}"#).parse().unwrap();
+    let sorted = r#"{"a": {"c": 3, "d": 2}, "b": 1, "e": [{"f": 5, "g": 4}]}"#;
+    if forward.to_string() == sorted && backward.to_string() == sorted {
+        println!("✓ Display writes object keys in sorted order");
+    } else {
+        println!("✗ Display key order wrong: {} / {}", forward, backward);
+    }
+
+    // The fuzz entry point survives every prefix and single-byte corruption of a document
+    let sample = "\u{FEFF}{\"k\u{e9}y\": [1, -2.5e3, \"\\ud83c\\udf89\", true, null], \"o\": {\"x\": \"\\n\"}}".as_bytes();
+    let mut runs = 0;
+    for end in 0..=sample.len() {
+        fuzz_parse(&sample[..end]);
+        runs += 1;
+    }
+    for i in 0..sample.len() {
+        for byte in [b'"', b'\\', b'0', b'[', b'}', 0x00, 0xFF] {
+            let mut corrupted = sample.to_vec();
+            corrupted[i] = byte;
+            fuzz_parse(&corrupted);
+            runs += 1;
+        }
+    }
+    println!("✓ fuzz_parse accepted {} generated inputs without panicking", runs);
+
+    // Nesting past max_depth is an error on every path, not a stack overflow
+    let too_deep = format!("{}{}", "[{\"a\": ".repeat(100_000), "[".repeat(100_000));
+    fuzz_parse(too_deep.as_bytes());
+    let depth_error = |result: Result<(), json_parser::ParseError>| {
+        matches!(result, Err(e) if e.kind == ParseErrorKind::DepthExceeded && e.position == 1792)
+    };
+    let (_, tolerant_errors) = Parser::new(&too_deep).parse_tolerant();
+    let at_limit = format!("{}{}", "[".repeat(512), "]".repeat(512));
+    let over_limit = format!("[{}]", at_limit);
+    let unlimited = ParserOptions {
+        max_depth: None,
+        ..Default::default()
+    };
+    if depth_error(Parser::new(&too_deep).parse().map(drop))
+        && depth_error(Parser::new(&too_deep).validate())
+        && depth_error(Parser::parse_borrowed(&too_deep).map(drop))
+        && depth_error(Parser::new(&too_deep).parse_spanned().map(drop))
+        && depth_error(parse_partial(&too_deep).1.map_or(Ok(()), Err))
+        && tolerant_errors.iter().any(|e| e.kind == ParseErrorKind::DepthExceeded)
+        && is_valid(&at_limit)
+        && Parser::new(&over_limit).parse().is_err_and(|e| e.kind == ParseErrorKind::DepthExceeded && e.position == 512)
+        && Parser::with_options(&over_limit, unlimited).parse().is_ok()
+    {
+        println!("✓ Nesting deeper than 512 is rejected on every parse path");
+    } else {
+        println!("✗ Deep nesting not rejected: {:?}", Parser::new(&too_deep).parse().map(drop));
+    }
+
+    // With the btree_map feature objects iterate, and serialize, in key order
+    #[cfg(feature = "btree_map")]
+    {
+        let mut value = Parser::new(r#"{"zeta": 1, "alpha": {"y": 2, "x": 3}, "mid": null}"#).parse().unwrap();
+        value.entry("beta").unwrap().or_insert(JsonValue::from(4.0));
+        let keys: Vec<String> = value.clone().into_entries().map(|(key, _)| key).collect();
+        let expected = r#"{"alpha": {"x": 3, "y": 2}, "beta": 4, "mid": null, "zeta": 1}"#;
+        if keys == ["alpha", "beta", "mid", "zeta"] && value.to_string_with(&WriteOptions::default()) == expected {
+            println!("✓ BTreeMap-backed objects iterate and serialize in key order");
+        } else {
+            println!("✗ BTreeMap-backed object order wrong: {:?}", keys);
+        }
+    }
+
+    // len and is_empty count array elements and object members, and are 0 for scalars
+    let sizes: Vec<_> = [r#"[1, [2, 3], 4]"#, r#"{"a": 1, "b": {}}"#, "[]", "{}", r#""text""#, "7"]
+        .iter()
+        .map(|input| {
+            let value = Parser::new(input).parse().unwrap();
+            (value.len(), value.is_empty())
+        })
+        .collect();
+    if sizes == [(3, false), (2, false), (0, true), (0, true), (0, true), (0, true)] {
+        println!("✓ len and is_empty report container sizes");
+    } else {
+        println!("✗ len/is_empty wrong: {:?}", sizes);
+    }
+
+    // pointer_or and the typed _at helpers fall back to a default
+    let config = Parser::new(r#"{"retries": 3, "mode": "fast", "debug": true, "limits": [10, 20]}"#).parse().unwrap();
+    let fallback = JsonValue::from("none");
+    let found = (
+        config.pointer_or("/limits/1", &fallback).as_f64(),
+        config.pointer_or("/limits/2", &fallback),
+        config.i64_at("/retries", 1),
+        config.i64_at("/mode", 1),
+        config.f64_at("/limits/0", 0.5),
+        config.str_at("/mode", "slow"),
+        config.str_at("/missing", "slow"),
+        config.bool_at("/debug", false),
+    );
+    if found == (Some(20.0), &fallback, 3, 1, 10.0, "fast", "slow", true) {
+        println!("✓ pointer_or and typed lookups return defaults when missing");
+    } else {
+        println!("✗ Pointer defaults wrong: {:?}", found);
+    }
+
+    // rename_keys rewrites keys at every depth; on a collision the last original key wins
+    let mut renamed = Parser::new(r#"{"a": 1, "list": [{"b": 2}], "A": 3, "nested": {"c": {"d": 4}}}"#).parse().unwrap();
+    renamed.rename_keys(|key| key.to_lowercase());
+    let expected = Parser::new(r#"{"a": 1, "list": [{"b": 2}], "nested": {"c": {"d": 4}}}"#).parse().unwrap();
+    if renamed == expected {
+        println!("✓ rename_keys renamed nested keys deterministically");
+    } else {
+        println!("✗ rename_keys produced {}", renamed);
+    }
+
+    // members and entries iterate containers and yield nothing for other values
+    let mut doc = Parser::new(r#"{"scores": [1, 2, 3], "meta": {"a": 1, "b": 2}}"#).parse().unwrap();
+    let scalar = JsonValue::from(5.0);
+    let counts = [
+        (doc.members().count(), doc.entries().count()),
+        (scalar.members().count(), scalar.entries().count()),
+        (JsonValue::Null.members().count(), JsonValue::from("s").entries().count()),
+    ];
+    if let Some(scores) = doc.pointer_mut("/scores") {
+        scores.members_mut().for_each(|v| *v = JsonValue::from(v.as_f64().unwrap_or(0.0) * 10.0));
+    }
+    if let Some(meta) = doc.pointer_mut("/meta") {
+        meta.entries_mut().for_each(|(key, v)| *v = JsonValue::from(key.as_str()));
+    }
+    let sum: f64 = doc.pointer("/scores").unwrap().members().filter_map(JsonValue::as_f64).sum();
+    if counts == [(0, 2), (0, 0), (0, 0)] && sum == 60.0 && doc.pointer("/meta/b") == Some(&JsonValue::from("b")) {
+        println!("✓ members and entries iterate containers, and nothing otherwise");
+    } else {
+        println!("✗ members/entries wrong: {:?} {} {}", counts, sum, doc);
+    }
+
+    // serialize picks a preset layout
+    let value = Parser::new(r#"{"z": [1, {"y": true, "x": "s"}], "a": {}}"#).parse().unwrap();
+    let compact = value.serialize(Format::Compact);
+    let canonical = value.serialize(Format::Canonical);
+    let pretty = value.serialize(Format::Pretty { indent: 4 });
+    let compact_ok = !compact.contains(' ') && Parser::new(&compact).parse().ok().as_ref() == Some(&value);
+    if compact_ok && canonical == r#"{"a":{},"z":[1,{"x":"s","y":true}]}"# && pretty == value.to_string_pretty(4) {
+        println!("✓ serialize produced compact, canonical and pretty layouts");
+    } else {
+        println!("✗ serialize layouts wrong: {} / {} / {}", compact, canonical, pretty);
+    }
+
+    // TryFrom converts owned or borrowed values, locating failures inside collections
+    let mixed = Parser::new(r#"[1, 2, true, "x"]"#).parse().unwrap();
+    let table = Parser::new(r#"{"a": ["x", "y"], "b": ["z", 1]}"#).parse().unwrap();
+    let converted = (
+        bool::try_from(JsonValue::from(true)).ok(),
+        u64::try_from(&JsonValue::from(7.0)).ok(),
+        i64::try_from(JsonValue::from(1.5)).map_err(|e| e.to_string()),
+        String::try_from(JsonValue::from("owned")).ok(),
+        Vec::<i64>::try_from(&mixed).map_err(|e| e.to_string()),
+        BTreeMap::<String, Vec<String>>::try_from(table).map_err(|e| e.to_string()),
+    );
+    let expected = (
+        Some(true),
+        Some(7),
+        Err("expected i64, found 1.5".to_string()),
+        Some("owned".to_string()),
+        Err("/2: expected number, found boolean".to_string()),
+        Err("/b/1: expected string, found number".to_string()),
+    );
+    if converted == expected {
+        println!("✓ TryFrom conversions report what they found and where");
+    } else {
+        println!("✗ TryFrom conversions wrong: {:?}", converted);
+    }
+
+    // is_integer classifies whole numbers; as_i64 and as_u64 never truncate
+    let numbers: Vec<_> = ["5", "5.0", "-5.5", "1e300", "-3", "\"5\""]
+        .iter()
+        .map(|input| {
+            let value = Parser::new(input).parse().unwrap();
+            (value.is_integer(), value.as_i64(), value.as_u64())
+        })
+        .collect();
+    let expected = [
+        (true, Some(5), Some(5)),
+        (true, Some(5), Some(5)),
+        (false, None, None),
+        (true, None, None),
+        (true, Some(-3), None),
+        (false, None, None),
+    ];
+    if numbers == expected {
+        println!("✓ is_integer, as_i64 and as_u64 classify numbers without truncating");
+    } else {
+        println!("✗ Number classification wrong: {:?}", numbers);
+    }
+
+    // Every variant answers the is_* predicates, type_name and len consistently
+    let variants = [
+        JsonValue::default(),
+        JsonValue::from(false),
+        JsonValue::from(1.5),
+        JsonValue::RawNumber("10".to_string()),
+        JsonValue::from("abc"),
+        Parser::new("[1, 2]").parse().unwrap(),
+        Parser::new(r#"{"a": 1}"#).parse().unwrap(),
+    ];
+    let described: Vec<_> = variants
+        .iter()
+        .map(|v| {
+            let flags = [v.is_null(), v.is_boolean(), v.is_number(), v.is_string(), v.is_array(), v.is_object()];
+            (v.type_name(), flags.iter().position(|&f| f), flags.iter().filter(|&&f| f).count(), v.len(), v.is_empty())
+        })
+        .collect();
+    let expected = [
+        ("null", Some(0), 1, 0, true),
+        ("boolean", Some(1), 1, 0, true),
+        ("number", Some(2), 1, 0, true),
+        ("number", Some(2), 1, 0, true),
+        ("string", Some(3), 1, 0, true),
+        ("array", Some(4), 1, 2, false),
+        ("object", Some(5), 1, 1, false),
+    ];
+    if described == expected {
+        println!("✓ Default, type_name and the is_* predicates agree for every variant");
+    } else {
+        println!("✗ Variant predicates wrong: {:?}", described);
+    }
+
+    let (value, error) = parse_partial(r#"{"a": 1, "b": [true, "x"#);
+    match (value.to_string().as_str(), error) {
+        (r#"{"a": 1, "b": [true]}"#, Some(e)) if e.kind == ParseErrorKind::UnterminatedString => {
+            println!("✓ Partial parse kept the complete members: {}", e)
+        }
+        (text, error) => println!("✗ Unexpected partial parse: {} {:?}", text, error),
+    }
+    match parse_partial("[1, 2] 3") {
+        (value, Some(e)) if value == JsonValue::Array(vec![1.into(), 2.into()]) && e.kind == ParseErrorKind::TrailingData => {
+            println!("✓ Partial parse returned the value before trailing data")
+        }
+        other => println!("✗ Unexpected partial parse of trailing data: {:?}", other),
+    }
+    match parse_partial("[1, {]") {
+        (value, Some(e)) if value.to_string() == "[1, {}]" && e.kind == ParseErrorKind::InvalidKey => {
+            println!("✓ Partial parse closed the object it stopped in")
+        }
+        other => println!("✗ Unexpected partial parse of a broken member: {:?}", other),
+    }
+    match parse_partial(" [1, 2] ") {
+        (value, None) if value == JsonValue::Array(vec![1.into(), 2.into()]) => println!("✓ Partial parse of valid input reports no error"),
+        other => println!("✗ Unexpected partial parse of valid input: {:?}", other),
+    }
+
+    let indented = Parser::new(r#"{"list": [1, [], {"k": null}], "empty": {}}"#).parse().unwrap();
+    let tabs = indented.to_string_with(&WriteOptions {
+        indent_string: Some("\t".to_string()),
+        sort_keys: true,
+        ..Default::default()
+    });
+    let expected_tabs = "{\n\t\"empty\": {},\n\t\"list\": [\n\t\t1,\n\t\t[],\n\t\t{\n\t\t\t\"k\": null\n\t\t}\n\t]\n}";
+    let spaces = indented.to_string_pretty_with("    ");
+    if tabs == expected_tabs
+        && spaces == indented.to_string_pretty(4)
+        && Parser::new(&tabs).parse().ok() == Some(indented.clone())
+        && Parser::new(&spaces).parse().ok() == Some(indented.clone())
+    {
+        println!("✓ Tab and four-space indents round-trip");
+    } else {
+        println!("✗ Unexpected indented output: {:?} {:?}", tabs, spaces);
+    }
+
+    let links = Parser::new(r#"{"a/b": ["https://example.com/x", "<\/script>"]}"#).parse().unwrap();
+    let escaped = links.to_string_with(&WriteOptions {
+        escape_solidus: true,
+        ..Default::default()
+    });
+    let plain = links.to_string_with(&WriteOptions::default());
+    if escaped == r#"{"a\/b": ["https:\/\/example.com\/x", "<\/script>"]}"#
+        && plain == r#"{"a/b": ["https://example.com/x", "</script>"]}"#
+        && Parser::new(&escaped).parse().ok() == Some(links.clone())
+        && Parser::new(&plain).parse().ok() == Some(links.clone())
+    {
+        println!("✓ Forward slashes escaped on request: {}", escaped);
+    } else {
+        println!("✗ Unexpected solidus escaping: {} {}", escaped, plain);
+    }
+
+    // A fixed-capacity target, as no_std code without an allocator would use.
+    struct Fixed {
+        buffer: [u8; 16],
+        len: usize,
+    }
+    impl std::fmt::Write for Fixed {
+        fn write_str(&mut self, s: &str) -> std::fmt::Result {
+            let end = self.len + s.len();
+            self.buffer.get_mut(self.len..end).ok_or(std::fmt::Error)?.copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+    let small = Parser::new(r#"{"k": [1, 2]}"#).parse().unwrap();
+    let mut fixed = Fixed { buffer: [0; 16], len: 0 };
+    let fits = small.write_to(&mut fixed).is_ok() && &fixed.buffer[..fixed.len] == small.to_string().as_bytes();
+    let mut tiny = Fixed { buffer: [0; 16], len: 10 };
+    let overflow = small.write_to(&mut tiny).is_err();
+    let mut pretty = String::new();
+    small.write_to_with(&mut pretty, &Format::Canonical.into()).unwrap();
+    if fits && overflow && pretty == small.serialize(Format::Canonical) {
+        println!("✓ write_to filled a fixed buffer and reported overflow");
+    } else {
+        println!("✗ Unexpected write_to results: {} {} {}", fits, overflow, pretty);
+    }
+
+    let wide = JsonValue::Array(vec![JsonValue::Array((0..1000).map(JsonValue::from).collect()); 1000]);
+    match (wide.try_clone(wide.node_count()), wide.try_clone(5000)) {
+        (Ok(copy), Err(JsonError::TooManyNodes { max: 5000 })) if copy == wide => {
+            println!("✓ try_clone copied within budget and stopped past it")
+        }
+        (copy, capped) => println!("✗ Unexpected try_clone results: {:?} {:?}", copy.is_ok(), capped),
+    }
+
+    let mut log = Vec::new();
+    let records = [
+        Parser::new(r#"{"event": "start", "note": "rs \u001e lf \n"}"#).parse().unwrap(),
+        JsonValue::from(42),
+        JsonValue::from("stop"),
+    ];
+    write_json_seq(&mut log, &records).unwrap();
+    let middle = log.iter().skip(1).position(|&b| b == 0x1E).unwrap() + 1;
+    log[middle + 1] = b'{';
+    let read: Vec<_> = parse_json_seq(&log).collect();
+    match read.as_slice() {
+        [Ok(first), Err(e), Ok(last)] if first == &records[0] && last == &records[2] && e.byte_offset > middle => {
+            println!("✓ JSON text sequence survived a corrupt middle record: {}", e)
+        }
+        other => println!("✗ Unexpected JSON text sequence: {:?}", other),
+    }
+    match parse_json_seq(b"\x1e12").collect::<Vec<_>>().as_slice() {
+        [Err(e)] if e.kind == ParseErrorKind::UnexpectedEnd => println!("✓ Unterminated top-level number flagged as truncated"),
+        other => println!("✗ Truncated number not flagged: {:?}", other),
+    }
+
+    let repeated = r#"{"a": 1, "b": [0], "a": 2, "b": [1], "a": 3}"#;
+    let with_policy = |policy| {
+        let options = ParserOptions {
+            duplicate_keys: policy,
+            ..Default::default()
+        };
+        Parser::with_options(repeated, options).parse().map(|v| v.to_string())
+    };
+    let first = with_policy(DuplicateKeyPolicy::FirstWins).ok();
+    let last = with_policy(DuplicateKeyPolicy::LastWins).ok();
+    let collect = with_policy(DuplicateKeyPolicy::Collect).ok();
+    let reject = with_policy(DuplicateKeyPolicy::Reject);
+    if first.as_deref() == Some(r#"{"a": 1, "b": [0]}"#)
+        && last.as_deref() == Some(r#"{"a": 3, "b": [1]}"#)
+        && collect.as_deref() == Some(r#"{"a": [1, 2, 3], "b": [[0], [1]]}"#)
+        && matches!(&reject, Err(e) if e.kind == ParseErrorKind::DuplicateKey && e.position == 19)
+    {
+        println!("✓ Duplicate keys kept, replaced, collected or rejected: {}", reject.unwrap_err());
+    } else {
+        println!("✗ Unexpected duplicate key handling: {:?} {:?} {:?} {:?}", first, last, collect, reject);
+    }
+    // validate rejects the same repeats at the same place, escapes decoded
+    let reject = ParserOptions {
+        duplicate_keys: DuplicateKeyPolicy::Reject,
+        ..Default::default()
+    };
+    let inputs = [
+        repeated,
+        r#"{"a": 1, "\u0061": 2}"#,
+        r#"{"a": {"a": 1}, "b": [{"a": 2}]}"#,
+        r#"[{"a": 1}, {"a": 2}]"#,
+    ];
+    let disagreements: Vec<&str> = inputs
+        .into_iter()
+        .filter(|input| {
+            let parsed = Parser::with_options(input, reject.clone()).parse().map_err(|e| (e.kind, e.position));
+            Parser::with_options(input, reject.clone()).validate().map_err(|e| (e.kind, e.position)) != parsed.map(|_| ())
+        })
+        .collect();
+    if disagreements.is_empty() && Parser::with_options(repeated, reject.clone()).validate().is_err() {
+        println!("✓ validate rejects duplicate keys exactly where parse does");
+    } else {
+        println!("✗ validate and parse disagree on duplicate keys in: {:?}", disagreements);
+    }
+
+    let table = Parser::new(r#"[{"id": 1, "note": ",\n\""}, {"id": 2, "tags": ["a"]}, {"name": null, "id": 3.5}]"#)
+        .parse()
+        .unwrap();
+    let nested_json = CsvOptions {
+        nested_as_json: true,
+        sort_columns: true,
+        ..Default::default()
+    };
+    let header_only = CsvOptions {
+        columns: Some(vec!["id".to_string(), "name".to_string()]),
+        ..Default::default()
+    };
+    let empty = JsonValue::Array(Vec::new());
+    match (
+        table.to_csv(),
+        table.to_csv_with(&nested_json),
+        empty.to_csv(),
+        empty.to_csv_with(&header_only),
+    ) {
+        (Err(CsvError::NestedValue { row: 1, column }), Ok(csv), Ok(none), Ok(header))
+            if column == "tags"
+                && csv == "id,name,note,tags\n1,,\",\n\"\"\",\n2,,,\"[\"\"a\"\"]\"\n3.5,,,\n"
+                && none.is_empty()
+                && header == "id,name\n" =>
+        {
+            println!("✓ CSV quoted special characters and unioned the columns")
+        }
+        other => println!("✗ Unexpected CSV output: {:?}", other),
+    }
+
+    let loose = Parser::new(r#"[null, "", " 42 ", "1e3", "0b101", "-Infinity", "inf", [], ["5"], [1, 2], {}, false]"#)
+        .parse()
+        .unwrap();
+    let numbers: Vec<Option<f64>> = loose.members().map(JsonValue::coerce_to_number).collect();
+    let truthy: Vec<bool> = loose.members().map(JsonValue::coerce_to_bool).collect();
+    let nan_text = JsonValue::Number(f64::NAN).coerce_to_string();
+    if numbers
+        == [
+            Some(0.0),
+            Some(0.0),
+            Some(42.0),
+            Some(1000.0),
+            Some(5.0),
+            Some(f64::NEG_INFINITY),
+            None,
+            Some(0.0),
+            Some(5.0),
+            None,
+            None,
+            Some(0.0),
+        ]
+        && truthy == [false, false, true, true, true, true, true, true, true, true, true, false]
+        && nan_text == "NaN"
+        && loose.coerce_to_string() == ",, 42 ,1e3,0b101,-Infinity,inf,,5,1,2,[object Object],false"
+    {
+        println!("✓ JavaScript-style coercions to number, string and bool");
+    } else {
+        println!("✗ Unexpected coercions: {:?} {:?} {}", numbers, truthy, loose.coerce_to_string());
+    }
+
+    let review = Parser::new(
+        r#"{"answer": "no", "version": "3.0", "pair": "a: b", "poem": "roses\n\nviolets\n", "greeting": "héllo wörld ✓",
+            "list": [{"id": 1, "tags": ["x", " padded "]}, [], [true, null]], "empty": {}, "": "-"}"#,
+    )
+    .parse()
+    .unwrap();
+    let expected_yaml = "\
+\"\": \"-\"
+answer: \"no\"
+empty: {}
+greeting: héllo wörld ✓
+list:
+  - id: 1
+    tags:
+      - x
+      - \" padded \"
+  - []
+  - - true
+    - null
+pair: \"a: b\"
+poem: |
+    roses
+
+    violets
+version: \"3.0\"
+";
+    let yaml = review.to_yaml_string();
+    if yaml == expected_yaml {
+        println!("✓ YAML output quotes ambiguous strings:\n{}", yaml.trim_end());
+    } else {
+        println!("✗ Unexpected YAML:\n{}", yaml);
+    }
+
+    let mut reused = Parser::with_options("", ParserOptions::json5());
+    let messages = ["{id: 1}", "[0x10, 'x']", "{id: 2, }", "'last'"];
+    let results: Vec<String> = messages
+        .iter()
+        .map(|message| {
+            reused.reset(message);
+            match reused.parse() {
+                Ok(value) => value.to_string(),
+                Err(e) => format!("error at {}", e.position),
+            }
+        })
+        .collect();
+    if results == [r#"{"id": 1}"#, r#"[16, "x"]"#, "error at 8", r#""last""#] && reused.position() == 6 {
+        println!("✓ One parser reset and reused across messages: {:?}", results);
+    } else {
+        println!("✗ Unexpected results from a reused parser: {:?}", results);
+    }
+
+    let rows = format!("[{}]", vec![r#"{"id": 1, "name": "n", "tags": {"id": 0}}"#; 1000].join(", "));
+    let mut interner = KeyInterner::new();
+    let interned = Parser::new(&rows).parse_shared(&mut interner).unwrap();
+    let again = Parser::new(r#"{"name": "x"}"#).parse_shared(&mut interner).unwrap();
+    let key_of = |value: &SharedJson, wanted: &str| match value {
+        SharedJson::Object(members) => members.keys().find(|key| &***key == wanted).cloned(),
+        _ => None,
+    };
+    let first = interned.get_index(0).and_then(|row| key_of(row, "name")).unwrap();
+    let shared_keys = (0..1000).all(|i| {
+        let row = interned.get_index(i).unwrap();
+        key_of(row, "name").is_some_and(|key| Arc::ptr_eq(&key, &first))
+            && key_of(row.get("tags").unwrap(), "id").zip(key_of(row, "id")).is_some_and(|(a, b)| Arc::ptr_eq(&a, &b))
+    });
+    if shared_keys
+        && interner.len() == 3
+        && key_of(&again, "name").is_some_and(|key| Arc::ptr_eq(&key, &first))
+        && interned == Parser::new(&rows).parse().unwrap().into_shared()
+    {
+        println!("✓ 1000 objects share {} interned keys, across parses too", interner.len());
+    } else {
+        println!("✗ Keys were not interned");
+    }
+
+    let source = "\u{FEFF}{name: 'caf\u{e9}', list: [1, {\"k\": null}], name: 2}";
+    let spanned = Parser::with_options(source, ParserOptions::json5()).parse_spanned().unwrap();
+    let slice = |span: &std::ops::Range<usize>| &source[span.clone()];
+    let names: Vec<_> = match &spanned.kind {
+        SpannedKind::Object(members) => members.iter().filter(|m| m.key == "name").map(|m| slice(&m.key_span)).collect(),
+        _ => Vec::new(),
+    };
+    if spanned.span == (3..source.len())
+        && names == ["name", "name"]
+        && spanned.pointer("/name").map(|v| slice(&v.span)) == Some("2")
+        && spanned.pointer("/list/1/k").map(|v| slice(&v.span)) == Some("null")
+        && spanned.get("list").map(|v| slice(&v.span)) == Some("[1, {\"k\": null}]")
+        && spanned.to_value() == Parser::with_options(source, ParserOptions::json5()).parse().unwrap()
+    {
+        println!("✓ Spans point back into the source, past a BOM and multi-byte text");
+    } else {
+        println!("✗ Wrong spans: {:?}", spanned);
+    }
+    // Rejecting repeats in a wide spanned object looks keys up in a set,
+    // and to_value lets the last repeat win whatever the policy
+    let members: Vec<String> = (0..20_000).map(|i| format!(r#""k{}": {}"#, i, i)).collect();
+    let wide = format!(r#"{{{}, "k19999": "again"}}"#, members.join(", "));
+    let reject = ParserOptions {
+        duplicate_keys: DuplicateKeyPolicy::Reject,
+        ..Default::default()
+    };
+    let started = std::time::Instant::now();
+    let rejected = Parser::with_options(&wide, reject.clone()).parse_spanned().map(drop).map_err(|e| (e.kind, e.position));
+    let spanned_time = started.elapsed();
+    let started = std::time::Instant::now();
+    let expected = Parser::with_options(&wide, reject).parse().map(drop).map_err(|e| (e.kind, e.position));
+    let parse_time = started.elapsed();
+    let first_wins = ParserOptions {
+        duplicate_keys: DuplicateKeyPolicy::FirstWins,
+        ..Default::default()
+    };
+    let repeated = Parser::with_options(r#"{"a": 1, "a": 2}"#, first_wins).parse_spanned().unwrap();
+    if matches!(rejected, Err((ParseErrorKind::DuplicateKey, _)))
+        && rejected == expected
+        && spanned_time < parse_time * 5
+        && repeated.to_value() == Parser::new(r#"{"a": 2}"#).parse().unwrap()
+    {
+        println!("✓ parse_spanned rejected a repeat among {} keys in {:?}", members.len(), spanned_time);
+    } else {
+        println!("✗ Spanned duplicate check wrong or slow: {:?} in {:?} against {:?}", rejected, spanned_time, parse_time);
+    }
+
+    let fixtures = [
+        r#"{"a": {"b": [1, 2]}, "c": {"d": {"e": null}}, "f": "x"}"#,
+        r#"[[1, [2, []]], {"k": {}}, "s"]"#,
+        r#"{"a.b": {"c\\.d": [true]}, "back\\slash": 1, "x": {"y": "a.b"}}"#,
+        r#"{"items": [{"id": 1, "tags": ["a", "b"]}, {"id": 2, "tags": []}]}"#,
+        "42",
+    ];
+    let round_trips = fixtures.iter().all(|text| {
+        let value = Parser::new(text).parse().unwrap();
+        [".", "/", "->"].iter().all(|sep| value.flatten_to_object(sep).and_then(|flat| flat.unflatten_object(sep)).ok() == Some(value.clone()))
+    });
+    let escaped = Parser::new(r#"{"a.b": {"c": 1}}"#).parse().unwrap().flatten();
+    let sparse = Parser::new(r#"{"a.0": 1, "a.2": 2, "b.01": 3, "c.1": 4, "c.0": 5}"#).parse().unwrap();
+    let collisions = [
+        Parser::new(r#"{"a": 1, "a.b": 2}"#).parse().unwrap(),
+        Parser::new(r#"{"a.b": 1, "a": 2}"#).parse().unwrap(),
+        Parser::new(r#"{"a.0": {}, "a.0.x": 1}"#).parse().unwrap(),
+    ];
+    if round_trips
+        && escaped == [("a\\.b.c".to_string(), JsonValue::from(1))]
+        && sparse.unflatten_object(".").ok()
+            == Some(Parser::new(r#"{"a": {"0": 1, "2": 2}, "b": {"01": 3}, "c": [5, 4]}"#).parse().unwrap())
+        && collisions.iter().all(|flat| flat.unflatten_object(".").is_err())
+        && JsonValue::from(1).unflatten_object(".").is_err()
+        && JsonValue::unflatten_with_separator(&escaped, "").is_err()
+    {
+        println!("✓ flatten_to_object and unflatten_object round-trip, escaping separators in keys");
+    } else {
+        println!("✗ Flat objects did not round-trip: {:?}", sparse.unflatten_object("."));
+    }
+    // Empty keys keep their own segment rather than vanishing
+    let empty_keys = [r#"{"": {"a": 1}}"#, r#"{"": 1}"#, r#"{"a": {"": [{"": null}]}, "": ""}"#, r#"[{"": {}}]"#];
+    let empty_round_trips = empty_keys.iter().all(|text| {
+        let value = Parser::new(text).parse().unwrap();
+        [".", "/"].iter().all(|sep| value.flatten_to_object(sep).and_then(|flat| flat.unflatten_object(sep)).ok() == Some(value.clone()))
+    });
+    let nested_empty = Parser::new(r#"{"": {"a": 1}}"#).parse().unwrap().flatten();
+    if empty_round_trips
+        && nested_empty == [("\\e.a".to_string(), JsonValue::from(1))]
+        && Parser::new(r#"{"a": 1}"#).parse().unwrap().flatten() != nested_empty
+        && JsonValue::unflatten_with_separator(&nested_empty, "e").is_err()
+    {
+        println!("✓ Empty keys flatten to \\e and unflatten back");
+    } else {
+        println!("✗ Empty keys lost in flatten: {:?}", nested_empty);
+    }
+    // Awkward separators either round-trip keys full of their own pieces or
+    // are refused both ways, never flattened into paths that read back wrong
+    let awkward = Parser::new(r#"{"a:": {"b": 1}, "-": {">": [">-", "->"]}, "x->y": {"": {"-->": "e"}}, "|=": {"=|": 2}}"#).parse().unwrap();
+    let awkward_round_trips = ["->", "|=", "ab"]
+        .iter()
+        .all(|sep| awkward.flatten_to_object(sep).and_then(|flat| flat.unflatten_object(sep)).ok() == Some(awkward.clone()));
+    let refused = ["", "e.", "::", "\\", "a/a", "||"]
+        .iter()
+        .all(|sep| awkward.flatten_with_separator(sep).is_err() && JsonValue::unflatten_with_separator(&awkward.flatten(), sep).is_err());
+    if awkward_round_trips && refused {
+        println!("✓ Awkward separators round-trip or are refused by flatten and unflatten alike");
+    } else {
+        println!("✗ Awkward separators: round trips {}, refused {}", awkward_round_trips, refused);
+    }
+
+    let merged = |strategy| {
+        let mut base = Parser::new(r#"{"a": [1, 2], "b": {"c": [{"x": 1}, 5], "d": 1}}"#).parse().unwrap();
+        base.merge_with(Parser::new(r#"{"a": [3], "b": {"c": [{"y": 2}], "d": null}}"#).parse().unwrap(), strategy);
+        base.to_string()
+    };
+    let mut plain = Parser::new("[1, 2]").parse().unwrap();
+    plain.merge(Parser::new("[3]").parse().unwrap());
+    let results = [
+        merged(ArrayMergeStrategy::Replace),
+        merged(ArrayMergeStrategy::Concat),
+        merged(ArrayMergeStrategy::IndexWise),
+    ];
+    if results
+        == [
+            r#"{"a": [3], "b": {"c": [{"y": 2}], "d": null}}"#,
+            r#"{"a": [1, 2, 3], "b": {"c": [{"x": 1}, 5, {"y": 2}], "d": null}}"#,
+            r#"{"a": [3, 2], "b": {"c": [{"x": 1, "y": 2}, 5], "d": null}}"#,
+        ]
+        && plain.to_string() == "[3]"
+    {
+        println!("✓ merge_with replaced, concatenated and merged arrays index-wise");
+    } else {
+        println!("✗ Unexpected merges: {:?}", results);
+    }
+
+    let layered = |arrays, nulls| {
+        let mut base = Parser::new(r#"{"list": [{"a": 1}, {"b": [1], "d": 4}], "keep": {"x": 1}, "n": 0}"#).parse().unwrap();
+        let layer = Parser::new(r#"{"list": [null, {"b": [2], "c": null}, 3], "keep": null, "n": null, "new": null}"#)
+            .parse()
+            .unwrap();
+        base.deep_merge(&layer, MergeOptions { arrays, nulls });
+        base.to_string()
+    };
+    let untouched = {
+        let mut base = Parser::new(r#"{"a": [1, {"b": 2}]}"#).parse().unwrap();
+        let before = base.clone();
+        let options = MergeOptions {
+            arrays: ArrayMergeStrategy::IndexWise,
+            nulls: NullMergePolicy::Ignore,
+        };
+        base.deep_merge(&JsonValue::Null, options);
+        base == before
+    };
+    let deep = [
+        layered(ArrayMergeStrategy::Replace, NullMergePolicy::Overwrite),
+        layered(ArrayMergeStrategy::Concat, NullMergePolicy::Ignore),
+        layered(ArrayMergeStrategy::IndexWise, NullMergePolicy::Ignore),
+        layered(ArrayMergeStrategy::IndexWise, NullMergePolicy::Overwrite),
+    ];
+    if untouched
+        && deep
+            == [
+                r#"{"keep": null, "list": [null, {"b": [2], "c": null}, 3], "n": null, "new": null}"#,
+                r#"{"keep": {"x": 1}, "list": [{"a": 1}, {"b": [1], "d": 4}, null, {"b": [2], "c": null}, 3], "n": 0}"#,
+                r#"{"keep": {"x": 1}, "list": [{"a": 1}, {"b": [2], "d": 4}, 3], "n": 0}"#,
+                r#"{"keep": null, "list": [null, {"b": [2], "c": null, "d": 4}, 3], "n": null, "new": null}"#,
+            ]
+    {
+        println!("✓ deep_merge applied each array strategy and null policy to nested layers");
+    } else {
+        println!("✗ Unexpected deep merges: {:?}", deep);
+    }
+
+    let mut mixed = vec![
+        JsonValue::from("b"),
+        JsonValue::Number(f64::NAN),
+        JsonValue::Object(Default::default()),
+        JsonValue::Number(0.0),
+        JsonValue::Array(vec![1.into()]),
+        JsonValue::Boolean(true),
+        JsonValue::Number(-0.0),
+        JsonValue::Null,
+        JsonValue::Number(-f64::NAN),
+        JsonValue::from("a"),
+        JsonValue::Array(Vec::new()),
+        JsonValue::Number(f64::NEG_INFINITY),
+        JsonValue::Boolean(false),
+    ]
+    .into_iter()
+    .map(TotalOrd)
+    .collect::<Vec<_>>();
+    mixed.sort_unstable();
+    let order: Vec<String> = mixed
+        .iter()
+        .map(|v| match &v.0 {
+            JsonValue::Number(n) => format!("{:?}", n),
+            other => other.to_string(),
+        })
+        .collect();
+    let counts = [1, 2, 1].iter().map(|&n| TotalOrd(n.into())).fold(BTreeMap::new(), |mut counts, key| {
+        *counts.entry(key).or_insert(0) += 1;
+        counts
+    });
+    if order == ["null", "false", "true", "NaN", "-inf", "-0.0", "0.0", "NaN", "\"a\"", "\"b\"", "[]", "[1]", "{}"]
+        && mixed[3].0.as_f64().is_some_and(|n| n.is_sign_negative())
+        && JsonValue::Number(f64::NAN) != JsonValue::Number(f64::NAN)
+        && TotalOrd(JsonValue::Number(f64::NAN)) == TotalOrd(JsonValue::Number(f64::NAN))
+        && JsonValue::Number(-0.0) == JsonValue::Number(0.0)
+        && TotalOrd(JsonValue::Number(-0.0)) < TotalOrd(JsonValue::Number(0.0))
+        && counts.into_iter().map(|(k, n)| (k.0.to_string(), n)).collect::<Vec<_>>() == [("1".to_string(), 2), ("2".to_string(), 1)]
+    {
+        println!("✓ TotalOrd sorted a mixed array: {}", order.join(" < "));
+    } else {
+        println!("✗ Unexpected order: {:?}", order);
+    }
+
+    let source = "{name: 'x\\u0041', n: [0x1F, -.5], ok: TRUE} ";
+    let options = ParserOptions {
+        case_insensitive_literals: true,
+        ..ParserOptions::json5()
+    };
+    let mut lexer = Lexer::with_options(source, options);
+    let mut texts = Vec::new();
+    let mut tokens = Vec::new();
+    while let Some(Ok(token)) = lexer.next() {
+        texts.push(&source[lexer.span()]);
+        tokens.push(token);
+    }
+    let bad: Vec<_> = Lexer::new("[1, \"a\\q\"] nope").collect();
+    if tokens
+        == [
+            Token::LeftBrace,
+            Token::Identifier("name".into()),
+            Token::Colon,
+            Token::String("xA".into()),
+            Token::Comma,
+            Token::Identifier("n".into()),
+            Token::Colon,
+            Token::LeftBracket,
+            Token::Number(31.0),
+            Token::Comma,
+            Token::Number(-0.5),
+            Token::RightBracket,
+            Token::Comma,
+            Token::Identifier("ok".into()),
+            Token::Colon,
+            Token::Boolean(true),
+            Token::RightBrace,
+        ]
+        && texts.concat() == "{name:'x\\u0041',n:[0x1F,-.5],ok:TRUE}"
+        && bad.len() == 4
+        && bad[3].as_ref().is_err_and(|e| e.byte_offset == 8)
+    {
+        println!("✓ Lexer produced JSON5 tokens and stopped at a bad escape");
+    } else {
+        println!("✗ Unexpected tokens: {:?} / {:?}", tokens, bad);
+    }
+
+    let fixture = Parser::new(r#"{"a": [1, {"b": null}], "x~y/z": {"deep": {"skip": [true]}}, "s": "t"}"#)
+        .parse()
+        .unwrap();
+    let mut visited = Vec::new();
+    fixture.walk(|pointer, value| {
+        visited.push(format!("{} {}", pointer, value.type_name()));
+        if pointer == "/x~0y~1z/deep" { WalkControl::SkipChildren } else { WalkControl::Continue }
+    });
+    visited.sort();
+    let mut stop_count = 0;
+    fixture.walk(|_, _| {
+        stop_count += 1;
+        if stop_count == 3 { WalkControl::Stop } else { WalkControl::Continue }
+    });
+    let mut doubled = fixture.clone();
+    doubled.walk_mut(|_, value| {
+        match value {
+            JsonValue::Number(n) => *n *= 2.0,
+            JsonValue::Null => *value = JsonValue::Array(vec![7.into()]),
+            _ => {}
+        }
+        WalkControl::Continue
+    });
+    if visited
+        == [
+            " object",
+            "/a array",
+            "/a/0 number",
+            "/a/1 object",
+            "/a/1/b null",
+            "/s string",
+            "/x~0y~1z object",
+            "/x~0y~1z/deep object",
+        ]
+        && stop_count == 3
+        && doubled.pointer("/a/0") == Some(&2.into())
+        && doubled.pointer("/a/1/b/0") == Some(&14.into())
+    {
+        println!("✓ walk visited {} pointers, pruned and stopped; walk_mut edited in place", visited.len());
+    } else {
+        println!("✗ Unexpected walk: {:?}", visited);
+    }
+
+    let messy = Parser::new(r#"{"a": {"p": 1.005, "q": [2.4567, " x ", "true"]}, "flag": "false", "e": [], "n": 1}"#)
+        .parse()
+        .unwrap();
+    let mut calls = 0;
+    let cleaned = messy.map_values(|leaf| {
+        calls += 1;
+        match leaf {
+            JsonValue::Number(n) if *n == 1.0 => *leaf = JsonValue::Array(vec![1.into(), 2.into()]),
+            JsonValue::Number(n) => *n = (*n * 100.0).round() / 100.0,
+            JsonValue::String(s) if s == "true" || s == "false" => *leaf = JsonValue::Boolean(s == "true"),
+            JsonValue::String(s) => *s = s.trim().to_string(),
+            _ => {}
+        }
+    });
+    if cleaned.to_string() == r#"{"a": {"p": 1, "q": [2.46, "x", true]}, "e": [], "flag": false, "n": [1, 2]}"#
+        && calls == 6
+    {
+        println!("✓ map_values rounded, trimmed and converted leaves without revisiting new containers");
+    } else {
+        println!("✗ Unexpected map_values result after {} calls: {}", calls, cleaned);
+    }
+
+    let script = ObjectBuilder::new().insert("line\u{2028}key", "a\u{2029}b\"/").build();
+    let js_safe = WriteOptions {
+        escape: EscapePolicy::JsSafe,
+        ..Default::default()
+    };
+    let written = script.to_string_with(&js_safe);
+    if written == r#"{"line\u2028key": "a\u2029b\"/"}"#
+        && script.to_string() == "{\"line\u{2028}key\": \"a\u{2029}b\\\"/\"}"
+        && Parser::new(&written).parse().ok() == Some(script.clone())
+    {
+        println!("✓ JsSafe escaped U+2028 and U+2029; Display left them as they were");
+    } else {
+        println!("✗ Unexpected escaping: {}", written);
+    }
+
+    let mut scrubbed = Parser::new(
+        r#"{"a": null, "b": {"c": null, "d": [1, null, {"e": null, "f": 2}]}, "_x": 1,
+            "g": {"_y": 2, "h": {"_z": 3, "i": {"_w": 4, "j": 5}}}}"#,
+    )
+    .parse()
+    .unwrap();
+    let mut nulls_gone = scrubbed.clone();
+    nulls_gone.retain_recursive(|_, value| !value.is_null());
+    scrubbed.retain_recursive(|key, _| !key.starts_with('_'));
+    let mut list = Parser::new(r#"[5, "x", 3, null, 1, "y"]"#).parse().unwrap();
+    let mut seen = Vec::new();
+    let kept = list.retain_elements(|i, item| {
+        seen.push(i);
+        item.is_number()
+    });
+    let mut scalar = JsonValue::from(1);
+    if nulls_gone.to_string() == r#"{"_x": 1, "b": {"d": [1, null, {"f": 2}]}, "g": {"_y": 2, "h": {"_z": 3, "i": {"_w": 4, "j": 5}}}}"#
+        && scrubbed.to_string() == r#"{"a": null, "b": {"c": null, "d": [1, null, {"e": null, "f": 2}]}, "g": {"h": {"i": {"j": 5}}}}"#
+        && kept
+        && list.to_string() == "[5, 3, 1]"
+        && seen == [0, 1, 2, 3, 4, 5]
+        && !scalar.retain_entries(|_, _| false)
+        && !scalar.retain_elements(|_, _| false)
+        && !scalar.retain_recursive(|_, _| false)
+        && !list.retain_entries(|_, _| false)
+        && scalar == JsonValue::from(1)
+    {
+        println!("✓ retain_* stripped nulls and deep underscore keys, kept element order, ignored other types");
+    } else {
+        println!("✗ Unexpected retain results: {} / {} / {}", nulls_gone, scrubbed, list);
+    }
+}
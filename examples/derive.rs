@@ -0,0 +1,76 @@
+// Demonstrates `#[derive(FromJson, ToJson)]`.
+// Run with `cargo run --features derive --example derive`.
+use json_parser::{FromJson, JsonValue, Parser, ToJson};
+
+#[derive(Debug, PartialEq, FromJson, ToJson)]
+enum Role {
+    Admin,
+    #[json(rename = "read-only")]
+    ReadOnly,
+}
+
+#[derive(Debug, PartialEq, FromJson, ToJson)]
+struct UserId(u64);
+
+#[derive(Debug, PartialEq, FromJson, ToJson)]
+#[json(deny_unknown_fields)]
+struct User {
+    id: UserId,
+    #[json(rename = "displayName")]
+    display_name: String,
+    role: Role,
+    email: Option<String>,
+    #[json(default)]
+    tags: Vec<String>,
+    #[json(skip)]
+    cached_score: f64,
+}
+
+fn main() {
+    let doc = Parser::new(r#"{"id": 7, "displayName": "Ann", "role": "read-only", "email": null}"#)
+        .parse()
+        .unwrap();
+    let user = User::from_json(&doc);
+    let expected = User {
+        id: UserId(7),
+        display_name: "Ann".to_string(),
+        role: Role::ReadOnly,
+        email: None,
+        tags: Vec::new(),
+        cached_score: 0.0,
+    };
+    match user {
+        Ok(user) if user == expected => println!("✓ Derived FromJson read {:?}", user),
+        other => println!("✗ Unexpected derive result: {:?}", other),
+    }
+
+    let back = expected.to_json();
+    let roundtrip = User::from_json(&back);
+    if back.get_path("displayName") == Some(&"Ann".into())
+        && back.get_path("cached_score").is_none()
+        && roundtrip.as_ref().ok() == Some(&expected)
+    {
+        println!("✓ Derived ToJson wrote {}", back);
+    } else {
+        println!("✗ Unexpected ToJson output {}: {:?}", back, roundtrip);
+    }
+
+    let missing = Parser::new(r#"{"id": 7, "role": "Admin"}"#).parse().unwrap();
+    match User::from_json(&missing) {
+        Err(e) => println!("✓ Missing field reported: {}", e),
+        Ok(user) => println!("✗ Should have failed, got: {:?}", user),
+    }
+
+    let unknown = Parser::new(r#"{"id": 7, "displayName": "Ann", "role": "Admin", "extra": 1}"#)
+        .parse()
+        .unwrap();
+    match User::from_json(&unknown) {
+        Err(e) => println!("✓ Unknown field rejected: {}", e),
+        Ok(user) => println!("✗ Should have failed, got: {:?}", user),
+    }
+
+    match Role::from_json(&JsonValue::from("Guest")) {
+        Err(e) => println!("✓ Unknown variant rejected: {}", e),
+        Ok(role) => println!("✗ Should have failed, got: {:?}", role),
+    }
+}
@@ -0,0 +1,157 @@
+// Checks MessagePack encoding and decoding against known encodings, and
+// round-trips values through both.
+// Run with `cargo run --features msgpack --example msgpack`.
+use json_parser::{JsonValue, MsgpackError, Parser, from_msgpack, to_msgpack};
+
+fn hex(text: &str) -> Vec<u8> {
+    (0..text.len()).step_by(2).map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap()).collect()
+}
+
+fn main() {
+    // (MessagePack, the value as JSON, whether `to_msgpack` produces exactly
+    // these bytes). The integer fixtures are in the smallest format for
+    // their value, as msgpack-c and msgpack-python write them; the float 32
+    // and the last three come from encoders that choose differently.
+    let long_string = "x".repeat(300);
+    let fixtures = [
+        ("c0".to_string(), "null".to_string(), true),
+        ("c3".to_string(), "true".to_string(), true),
+        ("7f".to_string(), "127".to_string(), true),
+        ("cc80".to_string(), "128".to_string(), true),
+        ("cd0100".to_string(), "256".to_string(), true),
+        ("ce00010000".to_string(), "65536".to_string(), true),
+        ("cf0000000100000000".to_string(), "4294967296".to_string(), true),
+        ("ff".to_string(), "-1".to_string(), true),
+        ("e0".to_string(), "-32".to_string(), true),
+        ("d0df".to_string(), "-33".to_string(), true),
+        ("d1ff7f".to_string(), "-129".to_string(), true),
+        ("d2ffff7fff".to_string(), "-32769".to_string(), true),
+        ("d3ffffffff7fffffff".to_string(), "-2147483649".to_string(), true),
+        ("d38000000000000000".to_string(), "-9223372036854775808".to_string(), true),
+        ("cf001fffffffffffff".to_string(), "9007199254740991".to_string(), true),
+        ("cf0020000000000000".to_string(), "9007199254740992".to_string(), true),
+        ("cf0020000000000001".to_string(), "9007199254740993".to_string(), false),
+        ("d3ffdfffffffffffff".to_string(), "-9007199254740993".to_string(), false),
+        ("cfffffffffffffffff".to_string(), "18446744073709551615".to_string(), false),
+        ("cb3ff199999999999a".to_string(), "1.1".to_string(), true),
+        ("cb8000000000000000".to_string(), "-0".to_string(), true),
+        ("ca3fc00000".to_string(), "1.5".to_string(), false),
+        ("a0".to_string(), r#""""#.to_string(), true),
+        ("a3c3a97e".to_string(), "\"\u{e9}~\"".to_string(), true),
+        (format!("da012c{}", "78".repeat(300)), format!("\"{}\"", long_string), true),
+        ("93010203".to_string(), "[1, 2, 3]".to_string(), true),
+        (format!("dc0010{}", "c0".repeat(16)), format!("[{}]", vec!["null"; 16].join(", ")), true),
+        ("82a7636f6d70616374c3a6736368656d6100".to_string(), r#"{"compact": true, "schema": 0}"#.to_string(), true),
+        ("d90161".to_string(), r#""a""#.to_string(), false),
+        ("dd0000000101".to_string(), "[1]".to_string(), false),
+        ("df00000001a16101".to_string(), r#"{"a": 1}"#.to_string(), false),
+    ];
+    let mut failed = 0;
+    for (bytes, json, canonical) in &fixtures {
+        let expected = Parser::new(json).parse().unwrap();
+        let decoded = from_msgpack(&hex(bytes));
+        let matches = match &decoded {
+            Ok(value) => value == &expected || value.to_string() == *json,
+            Err(_) => false,
+        };
+        let encoded_ok = !canonical || to_msgpack(&expected) == hex(bytes);
+        if !matches || !encoded_ok {
+            failed += 1;
+            println!("✗ Fixture {} ({}): decoded {:?}, encoded {:02x?}", bytes, json, decoded, to_msgpack(&expected));
+        }
+    }
+    if failed == 0 {
+        println!("✓ {} MessagePack fixtures decoded, and encoded where canonical", fixtures.len());
+    }
+
+    // Integers decode exactly: as numbers up to 2^53 and as raw digits past
+    // it, unless the f64 happens to be exact, as for powers of two.
+    let boundary = [
+        (9_007_199_254_740_992_u64, true),
+        (9_007_199_254_740_993, false),
+        (9_007_199_254_740_994, true),
+        (1 << 63, true),
+        (u64::MAX, false),
+    ];
+    let exact = boundary.iter().all(|&(n, is_number)| {
+        let mut bytes = vec![0xcf];
+        bytes.extend_from_slice(&n.to_be_bytes());
+        match from_msgpack(&bytes) {
+            Ok(JsonValue::Number(f)) => is_number && f as u64 == n,
+            Ok(JsonValue::RawNumber(digits)) => !is_number && digits == n.to_string(),
+            _ => false,
+        }
+    });
+    let raw = JsonValue::RawNumber("9007199254740993".to_string());
+    if exact && from_msgpack(&to_msgpack(&raw)).ok() == Some(raw) {
+        println!("✓ Integers around 2^53 decode without losing precision");
+    } else {
+        println!("✗ Integer precision lost near 2^53");
+    }
+
+    let rejected = [
+        ("c1", "never used"),
+        ("c40100", "binary data"),
+        ("d6ff00000000", "extension type"),
+        ("810102", "map key"),
+        ("9201", "end"),
+        ("0000", "after"),
+        ("a2c328", "UTF-8"),
+    ];
+    let mut messages = Vec::new();
+    for (bytes, expected) in rejected {
+        match from_msgpack(&hex(bytes)) {
+            Err(e) if e.to_string().contains(expected) => messages.push(e.to_string()),
+            other => println!("✗ {} should have been rejected ({}): {:?}", bytes, expected, other),
+        }
+    }
+    if messages.len() == rejected.len() {
+        println!("✓ Unsupported and malformed MessagePack rejected:\n    {}", messages.join("\n    "));
+    }
+    match from_msgpack(&[0x91].repeat(10_000)) {
+        Err(MsgpackError::TooDeep { offset: 512 }) => println!("✓ Deep nesting rejected without overflowing the stack"),
+        other => println!("✗ Expected deep nesting to be rejected: {:?}", other),
+    }
+
+    let value = Parser::new(
+        r#"{"name": "caché", "sizes": [0, -0.0, 1.5, 1e300, -9007199254740992, 4294967296, 1e19, -9.3e18],
+            "flags": {"a": true, "b": null}, "empty": [{}, [], ""]}"#,
+    )
+    .parse()
+    .unwrap();
+    let bytes = to_msgpack(&value);
+    // `cmp` rather than `==`, which takes -0.0 for 0.0.
+    match from_msgpack(&bytes) {
+        Ok(back) if back.total_cmp(&value).is_eq() && value.to_string().len() > bytes.len() => {
+            println!("✓ Round-tripped through {} bytes of MessagePack ({} as JSON)", bytes.len(), value.to_string().len())
+        }
+        other => println!("✗ MessagePack round trip changed the value: {:?}", other),
+    }
+
+    // Negative raw numbers, as parsed with `numbers_as_strings`, encode by
+    // their value and decode back to the same number.
+    let raw_negatives = [
+        ("-0", "cb8000000000000000"),
+        ("-1", "ff"),
+        ("-1000", "d1fc18"),
+        ("-9223372036854775808", "d38000000000000000"),
+        ("-18446744073709551616", "cbc3f0000000000000"),
+    ];
+    let mut failed = 0;
+    for (raw, msgpack) in raw_negatives {
+        let encoded = to_msgpack(&JsonValue::RawNumber(raw.to_string()));
+        let decoded = from_msgpack(&encoded);
+        let same = match &decoded {
+            Ok(JsonValue::Number(n)) => n.to_bits() == raw.parse::<f64>().unwrap().to_bits(),
+            Ok(JsonValue::RawNumber(s)) => s == raw,
+            _ => false,
+        };
+        if encoded != hex(msgpack) || !same {
+            failed += 1;
+            println!("✗ Raw number {}: encoded {:02x?}, decoded {:?}", raw, encoded, decoded);
+        }
+    }
+    if failed == 0 {
+        println!("✓ {} negative raw numbers round-tripped, -0 keeping its sign", raw_negatives.len());
+    }
+}
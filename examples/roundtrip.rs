@@ -0,0 +1,74 @@
+// Property check for the parser/serializer pair: every generated value must
+// survive `parse(value.to_string())` unchanged, and with the `cbor` and
+// `msgpack` features also `from_cbor(to_cbor(value))` and its MessagePack
+// equivalent.
+// Run with `cargo run --features arbitrary --example roundtrip [CASES]`.
+use std::{
+    env,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use arbitrary::{Arbitrary, Unstructured};
+use json_parser::{JsonValue, Parser};
+
+// xorshift64*, seeded from the clock; good enough to feed `Unstructured`.
+fn random_bytes(state: &mut u64, len: usize) -> Vec<u8> {
+    (0..len)
+        .map(|_| {
+            *state ^= *state >> 12;
+            *state ^= *state << 25;
+            *state ^= *state >> 27;
+            (state.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 56) as u8
+        })
+        .collect()
+}
+
+fn main() {
+    let cases: usize = env::args().nth(1).and_then(|n| n.parse().ok()).unwrap_or(10_000);
+    let seed = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos() as u64 | 1;
+    let mut state = seed;
+    let mut failures = 0;
+
+    for _ in 0..cases {
+        let bytes = random_bytes(&mut state, 512);
+        let Ok(value) = JsonValue::arbitrary(&mut Unstructured::new(&bytes)) else {
+            continue;
+        };
+        #[cfg(feature = "cbor")]
+        if json_parser::from_cbor(&json_parser::to_cbor(&value)).ok().as_ref() != Some(&value) {
+            failures += 1;
+            if failures <= 5 {
+                println!("✗ CBOR round trip changed value\n    value: {:?}", value);
+            }
+        }
+        #[cfg(feature = "msgpack")]
+        if json_parser::from_msgpack(&json_parser::to_msgpack(&value)).ok().as_ref() != Some(&value) {
+            failures += 1;
+            if failures <= 5 {
+                println!("✗ MessagePack round trip changed value\n    value: {:?}", value);
+            }
+        }
+        let text = value.to_string();
+        match Parser::new(&text).parse() {
+            Ok(parsed) if parsed == value => {}
+            Ok(parsed) => {
+                failures += 1;
+                if failures <= 5 {
+                    println!("✗ Round trip changed value\n    text:   {}\n    parsed: {:?}\n    value:  {:?}", text, parsed, value);
+                }
+            }
+            Err(e) => {
+                failures += 1;
+                if failures <= 5 {
+                    println!("✗ Serialized output did not parse: {}\n    text: {}", e, text);
+                }
+            }
+        }
+    }
+
+    if failures == 0 {
+        println!("✓ {} random values round-tripped (seed {})", cases, seed);
+    } else {
+        println!("✗ {} of {} values failed to round-trip (seed {})", failures, cases, seed);
+    }
+}
@@ -0,0 +1,121 @@
+// Demonstrates deserializing serde types from a parsed `JsonValue`.
+// Run with `cargo run --features serde --example serde`.
+use std::collections::BTreeMap;
+
+use json_parser::{JsonError, JsonValue, Parser, from_value};
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Level {
+    Debug,
+    Info,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+enum Output {
+    Stdout,
+    File(String),
+    Rotating { path: String, keep: u8 },
+    Remote(String, u16),
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Port(u16);
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Config {
+    name: String,
+    port: Port,
+    level: Level,
+    outputs: Vec<Output>,
+    timeout: Option<f64>,
+    retry: Option<(u8, f32)>,
+    labels: BTreeMap<String, i64>,
+}
+
+fn main() {
+    let doc = Parser::new(
+        r#"{
+            "name": "api",
+            "port": 8080,
+            "level": "info",
+            "outputs": [
+                "Stdout",
+                {"File": "/var/log/api.log"},
+                {"Rotating": {"path": "/tmp/api", "keep": 5}},
+                {"Remote": ["logs.example.com", 514]}
+            ],
+            "timeout": null,
+            "retry": [3, 0.5],
+            "labels": {"tier": 1, "zone": -2}
+        }"#,
+    )
+    .parse()
+    .unwrap();
+    let expected = Config {
+        name: "api".to_string(),
+        port: Port(8080),
+        level: Level::Info,
+        outputs: vec![
+            Output::Stdout,
+            Output::File("/var/log/api.log".to_string()),
+            Output::Rotating {
+                path: "/tmp/api".to_string(),
+                keep: 5,
+            },
+            Output::Remote("logs.example.com".to_string(), 514),
+        ],
+        timeout: None,
+        retry: Some((3, 0.5)),
+        labels: BTreeMap::from([("tier".to_string(), 1), ("zone".to_string(), -2)]),
+    };
+    match from_value::<Config>(doc) {
+        Ok(config) if config == expected => println!("✓ Deserialized nested enums, options and collections"),
+        other => println!("✗ Unexpected from_value result: {:?}", other),
+    }
+    match from_value::<Level>(JsonValue::from("debug")) {
+        Ok(Level::Debug) => println!("✓ Unit variants read from strings"),
+        other => println!("✗ Unexpected unit variant: {:?}", other),
+    }
+
+    let narrowing = Parser::new(r#"{"outputs": [{"Rotating": {"path": "/tmp/api", "keep": 1000}}]}"#)
+        .parse()
+        .unwrap();
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Outputs {
+        outputs: Vec<Output>,
+    }
+    match from_value::<Outputs>(narrowing) {
+        Err(JsonError::AtPath { path, error }) if path == "/outputs/0/Rotating/keep" => {
+            println!("✓ Narrowing error located at {}: {}", path, error)
+        }
+        other => println!("✗ Expected a located narrowing error, got {:?}", other),
+    }
+
+    let fraction = Parser::new(r#"{"port": 80.5}"#).parse().unwrap();
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Listen {
+        port: u16,
+    }
+    match from_value::<Listen>(fraction) {
+        Err(e) if e.to_string() == "/port: expected u16, found 80.5" => println!("✓ Fractions rejected for integers: {}", e),
+        other => println!("✗ Expected a fraction error, got {:?}", other),
+    }
+
+    let missing = Parser::new(r#"{"outputs": [{"Rotating": {"path": "/tmp/api"}}]}"#).parse().unwrap();
+    match from_value::<Outputs>(missing) {
+        Err(e) if e.to_string() == "/outputs/0/Rotating: missing field `keep`" => {
+            println!("✓ Missing field located: {}", e)
+        }
+        other => println!("✗ Expected a missing field error, got {:?}", other),
+    }
+
+    let borrowed = Parser::new(r#"["a", "b"]"#).parse().unwrap();
+    match <Vec<&str>>::deserialize(&borrowed) {
+        Ok(items) if items == ["a", "b"] => println!("✓ Borrowed strings straight from the value"),
+        other => println!("✗ Borrowing failed: {:?}", other),
+    }
+}
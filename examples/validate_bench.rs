@@ -0,0 +1,39 @@
+// Compares `Parser::validate` against a full `Parser::parse` on a large
+// generated document. Run with `cargo run --release --example validate_bench`.
+use std::time::Instant;
+
+use json_parser::Parser;
+
+fn fixture(records: usize) -> String {
+    let mut out = String::from("[");
+    for i in 0..records {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push_str(&format!(
+            "{{\"id\": {}, \"name\": \"user \\\"{}\\\"\", \"score\": {}.5e-3, \"active\": {}, \"tags\": [\"a\", \"b\", null]}}",
+            i,
+            i,
+            i,
+            i % 2 == 0
+        ));
+    }
+    out.push(']');
+    out
+}
+
+fn main() {
+    let input = fixture(200_000);
+    println!("fixture: {} bytes", input.len());
+
+    let start = Instant::now();
+    Parser::new(&input).validate().expect("fixture should validate");
+    let validate_time = start.elapsed();
+
+    let start = Instant::now();
+    Parser::new(&input).parse().expect("fixture should parse");
+    let parse_time = start.elapsed();
+
+    println!("validate: {:?}", validate_time);
+    println!("parse:    {:?}", parse_time);
+}
@@ -0,0 +1,239 @@
+//! `#[derive(FromJson, ToJson)]` for `json_parser`. Enable the `derive`
+//! feature of `json_parser` rather than depending on this crate directly.
+//!
+//! Supported shapes:
+//!
+//! - structs with named fields, mapped to objects with one key per field
+//! - newtype structs, converted as their single field
+//! - unit structs, converted as `null`
+//! - enums whose variants are all unit variants, converted as strings
+//!
+//! Field attributes: `#[json(rename = "key")]`, `#[json(default)]` (use
+//! `Default::default()` when the key is missing) and `#[json(skip)]` (never
+//! read or written; filled with `Default::default()`). Variants accept
+//! `#[json(rename = "...")]` too. On a struct, `#[json(deny_unknown_fields)]`
+//! makes `from_json` reject keys that no field maps to; by default they are
+//! ignored.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    Attribute, Data, DeriveInput, Fields, GenericParam, Generics, Ident, LitStr, Result, parse_macro_input,
+    parse_quote,
+};
+
+#[proc_macro_derive(FromJson, attributes(json))]
+pub fn derive_from_json(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_from_json(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(ToJson, attributes(json))]
+pub fn derive_to_json(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_to_json(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct JsonAttrs {
+    rename: Option<String>,
+    default: bool,
+    skip: bool,
+    deny_unknown_fields: bool,
+}
+
+fn json_attrs(attrs: &[Attribute]) -> Result<JsonAttrs> {
+    let mut out = JsonAttrs::default();
+    for attr in attrs.iter().filter(|a| a.path().is_ident("json")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                let name: LitStr = meta.value()?.parse()?;
+                out.rename = Some(name.value());
+            } else if meta.path.is_ident("default") {
+                out.default = true;
+            } else if meta.path.is_ident("skip") {
+                out.skip = true;
+            } else if meta.path.is_ident("deny_unknown_fields") {
+                out.deny_unknown_fields = true;
+            } else {
+                return Err(meta.error("unknown json attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(out)
+}
+
+// A named field that is read from / written to an object key.
+struct Field<'a> {
+    ident: &'a Ident,
+    key: String,
+    attrs: JsonAttrs,
+}
+
+fn named_fields(fields: &syn::FieldsNamed) -> Result<Vec<Field<'_>>> {
+    fields
+        .named
+        .iter()
+        .map(|f| {
+            let ident = f.ident.as_ref().expect("named field");
+            let attrs = json_attrs(&f.attrs)?;
+            let key = attrs
+                .rename
+                .clone()
+                .unwrap_or_else(|| ident.to_string().trim_start_matches("r#").to_string());
+            Ok(Field { ident, key, attrs })
+        })
+        .collect()
+}
+
+// Unit variants as (ident, string) pairs; anything else is an error.
+fn unit_variants(data: &syn::DataEnum) -> Result<Vec<(&Ident, String)>> {
+    data.variants
+        .iter()
+        .map(|v| {
+            if !matches!(v.fields, Fields::Unit) {
+                return Err(syn::Error::new_spanned(v, "only unit variants can be derived"));
+            }
+            let attrs = json_attrs(&v.attrs)?;
+            Ok((&v.ident, attrs.rename.unwrap_or_else(|| v.ident.to_string())))
+        })
+        .collect()
+}
+
+fn add_bound(generics: &Generics, bound: TokenStream2) -> Generics {
+    let mut generics = generics.clone();
+    for param in &mut generics.params {
+        if let GenericParam::Type(ty) = param {
+            ty.bounds.push(parse_quote!(#bound));
+        }
+    }
+    generics
+}
+
+fn expand_from_json(input: &DeriveInput) -> Result<TokenStream2> {
+    let name = &input.ident;
+    let generics = add_bound(&input.generics, quote!(::json_parser::FromJson));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let container = json_attrs(&input.attrs)?;
+
+    let body = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => {
+                let fields = named_fields(fields)?;
+                let inits = fields.iter().map(|f| {
+                    let ident = f.ident;
+                    let key = &f.key;
+                    if f.attrs.skip {
+                        quote!(#ident: ::core::default::Default::default())
+                    } else if f.attrs.default {
+                        quote!(#ident: match object.get(#key) {
+                            ::core::option::Option::Some(v) => ::json_parser::__private::field(#key, v)?,
+                            ::core::option::Option::None => ::core::default::Default::default(),
+                        })
+                    } else {
+                        quote!(#ident: ::json_parser::__private::required_field(object, #key)?)
+                    }
+                });
+                let deny = container.deny_unknown_fields.then(|| {
+                    let keys = fields.iter().filter(|f| !f.attrs.skip).map(|f| &f.key);
+                    quote!(::json_parser::__private::deny_unknown_fields(object, &[#(#keys),*])?;)
+                });
+                quote! {
+                    let object = ::json_parser::__private::expect_object(value)?;
+                    #deny
+                    ::core::result::Result::Ok(#name { #(#inits),* })
+                }
+            }
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                quote!(::core::result::Result::Ok(#name(::json_parser::FromJson::from_json(value)?)))
+            }
+            Fields::Unnamed(_) => {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "FromJson can only be derived for tuple structs with exactly one field",
+                ));
+            }
+            Fields::Unit => quote! {
+                ::json_parser::__private::expect_null(value)?;
+                ::core::result::Result::Ok(#name)
+            },
+        },
+        Data::Enum(data) => {
+            let arms = unit_variants(data)?
+                .into_iter()
+                .map(|(ident, s)| quote!(#s => ::core::result::Result::Ok(#name::#ident)));
+            quote! {
+                let s = <::json_parser::__private::String as ::json_parser::FromJson>::from_json(value)?;
+                match s.as_str() {
+                    #(#arms,)*
+                    other => ::core::result::Result::Err(::json_parser::JsonError::custom(
+                        ::json_parser::__private::format!("unknown variant '{}' for {}", other, ::core::stringify!(#name)),
+                    )),
+                }
+            }
+        }
+        Data::Union(_) => return Err(syn::Error::new_spanned(name, "FromJson cannot be derived for unions")),
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::json_parser::FromJson for #name #ty_generics #where_clause {
+            fn from_json(value: &::json_parser::JsonValue) -> ::core::result::Result<Self, ::json_parser::JsonError> {
+                #body
+            }
+        }
+    })
+}
+
+fn expand_to_json(input: &DeriveInput) -> Result<TokenStream2> {
+    let name = &input.ident;
+    let generics = add_bound(&input.generics, quote!(::json_parser::ToJson));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => {
+                let inserts = named_fields(fields)?.into_iter().filter(|f| !f.attrs.skip).map(|f| {
+                    let ident = f.ident;
+                    let key = &f.key;
+                    quote!(.insert_json(#key, &self.#ident))
+                });
+                quote!(::json_parser::ObjectBuilder::new() #(#inserts)* .build())
+            }
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                quote!(::json_parser::ToJson::to_json(&self.0))
+            }
+            Fields::Unnamed(_) => {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "ToJson can only be derived for tuple structs with exactly one field",
+                ));
+            }
+            Fields::Unit => quote!(::json_parser::JsonValue::Null),
+        },
+        Data::Enum(data) => {
+            let arms = unit_variants(data)?
+                .into_iter()
+                .map(|(ident, s)| quote!(#name::#ident => #s));
+            quote! {
+                ::json_parser::JsonValue::String(::json_parser::__private::String::from(match self {
+                    #(#arms,)*
+                }))
+            }
+        }
+        Data::Union(_) => return Err(syn::Error::new_spanned(name, "ToJson cannot be derived for unions")),
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::json_parser::ToJson for #name #ty_generics #where_clause {
+            fn to_json(&self) -> ::json_parser::JsonValue {
+                #body
+            }
+        }
+    })
+}
@@ -0,0 +1,50 @@
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{JsonValue, Map};
+
+const MAX_DEPTH: usize = 4;
+const MAX_LEN: usize = 8;
+
+// Generates random value trees for property testing. Nesting is capped at
+// MAX_DEPTH and containers at MAX_LEN items so generated documents stay small.
+// Numbers are always finite since JSON has no spelling for NaN or infinity.
+impl<'a> Arbitrary<'a> for JsonValue {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        arbitrary_value(u, 0)
+    }
+}
+
+fn arbitrary_value(u: &mut Unstructured, depth: usize) -> Result<JsonValue> {
+    let variants = if depth < MAX_DEPTH { 6 } else { 4 };
+    Ok(match u.choose_index(variants)? {
+        0 => JsonValue::Null,
+        1 => JsonValue::Boolean(u.arbitrary()?),
+        2 => {
+            let n: f64 = u.arbitrary()?;
+            JsonValue::Number(if n.is_finite() { n } else { 0.0 })
+        }
+        3 => JsonValue::String(arbitrary_string(u)?),
+        4 => {
+            let len = u.int_in_range(0..=MAX_LEN)?;
+            let mut items = Vec::with_capacity(len);
+            for _ in 0..len {
+                items.push(arbitrary_value(u, depth + 1)?);
+            }
+            JsonValue::Array(items)
+        }
+        _ => {
+            let len = u.int_in_range(0..=MAX_LEN)?;
+            let mut map = Map::new();
+            for _ in 0..len {
+                map.insert(arbitrary_string(u)?, arbitrary_value(u, depth + 1)?);
+            }
+            JsonValue::Object(map)
+        }
+    })
+}
+
+fn arbitrary_string(u: &mut Unstructured) -> Result<String> {
+    u.arbitrary()
+}
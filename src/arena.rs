@@ -0,0 +1,228 @@
+use bumpalo::{Bump, collections::Vec as BumpVec};
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{
+    JsonValue, Map, ParseError, Parser, ParserOptions,
+    options::DuplicateKeyPolicy,
+    lexer::ScannedString,
+    parser::Build,
+};
+
+/// A parsed value whose strings, arrays and objects all live in one bump
+/// arena, from `Parser::parse_in_arena`. Nothing is freed node by node: the
+/// whole document goes at once when the arena is dropped or reset, so
+/// parsing many documents into an arena that is reset between them saves
+/// most of the allocator traffic of building a `JsonValue`.
+///
+/// Values are `Copy`. Objects are slices of members in source order, with
+/// repeated keys already resolved by `ParserOptions::duplicate_keys`, so
+/// looking up a key is a linear scan.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArenaValue<'b> {
+    Null,
+    Boolean(bool),
+    Number(f64),
+    RawNumber(&'b str),
+    String(&'b str),
+    Array(&'b [ArenaValue<'b>]),
+    Object(&'b [(&'b str, ArenaValue<'b>)]),
+}
+
+impl<'b> ArenaValue<'b> {
+    /// Looks up `key` if this is an object.
+    pub fn get(&self, key: &str) -> Option<&'b ArenaValue<'b>> {
+        match self {
+            ArenaValue::Object(members) => members.iter().find(|(k, _)| *k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Looks up `index` if this is an array.
+    pub fn get_index(&self, index: usize) -> Option<&'b ArenaValue<'b>> {
+        match self {
+            ArenaValue::Array(items) => items.get(index),
+            _ => None,
+        }
+    }
+
+    /// Copies the value out of the arena into an owned `JsonValue`.
+    pub fn to_owned(&self) -> JsonValue {
+        match self {
+            ArenaValue::Null => JsonValue::Null,
+            ArenaValue::Boolean(b) => JsonValue::Boolean(*b),
+            ArenaValue::Number(n) => JsonValue::Number(*n),
+            ArenaValue::RawNumber(s) => JsonValue::RawNumber(s.to_string()),
+            ArenaValue::String(s) => JsonValue::String(s.to_string()),
+            ArenaValue::Array(items) => JsonValue::Array(items.iter().map(ArenaValue::to_owned).collect()),
+            ArenaValue::Object(members) => {
+                JsonValue::Object(members.iter().map(|(k, v)| (k.to_string(), v.to_owned())).collect())
+            }
+        }
+    }
+}
+
+struct Arena<'a, 'b> {
+    source: &'a str,
+    bump: &'b Bump,
+}
+
+impl<'b> Arena<'_, 'b> {
+    // Copies a string into the arena, from the input unless it had escapes
+    // that had to be decoded.
+    fn text(&self, s: ScannedString) -> &'b str {
+        match &s.decoded {
+            Some(decoded) => self.bump.alloc_str(decoded),
+            None => self.bump.alloc_str(&self.source[s.range]),
+        }
+    }
+}
+
+// Objects with more members than this get an index of their keys while they
+// are built, so finding a repeated key stays cheap; smaller ones are searched
+// linearly, and never allocate outside the arena.
+const LINEAR_MEMBERS: usize = 16;
+
+// An object being built: its members, and where each key is among them once
+// there are too many to search.
+struct ArenaObject<'b> {
+    members: BumpVec<'b, (&'b str, ArenaValue<'b>)>,
+    index: Option<Map<&'b str, usize>>,
+}
+
+impl<'b> ArenaObject<'b> {
+    fn position(&self, key: &str) -> Option<usize> {
+        match &self.index {
+            Some(index) => index.get(key).copied(),
+            None => self.members.iter().position(|(k, _)| *k == key),
+        }
+    }
+
+    fn push(&mut self, key: &'b str, value: ArenaValue<'b>) {
+        self.members.push((key, value));
+        let len = self.members.len();
+        match &mut self.index {
+            Some(index) => {
+                index.insert(key, len - 1);
+            }
+            None if len > LINEAR_MEMBERS => {
+                self.index = Some(self.members.iter().enumerate().map(|(i, (k, _))| (*k, i)).collect());
+            }
+            None => {}
+        }
+    }
+}
+
+impl<'b> Build for Arena<'_, 'b> {
+    type Value = ArenaValue<'b>;
+    type Array = BumpVec<'b, ArenaValue<'b>>;
+    type Object = ArenaObject<'b>;
+
+    const BORROWS_STRINGS: bool = true;
+
+    fn null(&mut self) -> Self::Value {
+        ArenaValue::Null
+    }
+
+    fn boolean(&mut self, b: bool) -> Self::Value {
+        ArenaValue::Boolean(b)
+    }
+
+    fn number(&mut self, n: f64) -> Self::Value {
+        ArenaValue::Number(n)
+    }
+
+    fn raw_number(&mut self, text: ScannedString) -> Self::Value {
+        ArenaValue::RawNumber(self.text(text))
+    }
+
+    fn string(&mut self, s: ScannedString) -> Self::Value {
+        ArenaValue::String(self.text(s))
+    }
+
+    fn new_array(&mut self) -> Self::Array {
+        BumpVec::new_in(self.bump)
+    }
+
+    fn push(&mut self, array: &mut Self::Array, value: Self::Value) {
+        array.push(value);
+    }
+
+    fn array(&mut self, items: Self::Array) -> Self::Value {
+        ArenaValue::Array(items.into_bump_slice())
+    }
+
+    fn new_object(&mut self) -> Self::Object {
+        ArenaObject {
+            members: BumpVec::new_in(self.bump),
+            index: None,
+        }
+    }
+
+    // Objects are not maps here, so this follows `insert_member` by hand.
+    fn insert(
+        &mut self,
+        object: &mut Self::Object,
+        key: ScannedString,
+        value: Self::Value,
+        policy: DuplicateKeyPolicy,
+        collected: &mut Vec<String>,
+    ) -> Result<(), String> {
+        let key = self.text(key);
+        let Some(i) = object.position(key) else {
+            object.push(key, value);
+            return Ok(());
+        };
+        let existing = &mut object.members[i].1;
+        match policy {
+            DuplicateKeyPolicy::FirstWins => {}
+            DuplicateKeyPolicy::LastWins => *existing = value,
+            DuplicateKeyPolicy::Reject => return Err(key.to_string()),
+            DuplicateKeyPolicy::Collect => {
+                let first = !collected.iter().any(|k| k == key);
+                if first {
+                    collected.push(key.to_string());
+                }
+                let current = *existing;
+                let earlier = match &current {
+                    ArenaValue::Array(items) if !first => items,
+                    single => core::slice::from_ref(single),
+                };
+                let mut items = BumpVec::with_capacity_in(earlier.len() + 1, self.bump);
+                items.extend_from_slice(earlier);
+                items.push(value);
+                *existing = ArenaValue::Array(items.into_bump_slice());
+            }
+        }
+        Ok(())
+    }
+
+    fn object(&mut self, object: Self::Object) -> Self::Value {
+        ArenaValue::Object(object.members.into_bump_slice())
+    }
+}
+
+impl Parser {
+    /// Parses `input` into an `ArenaValue` allocated in `bump`. Call
+    /// `bump.reset()` between documents to reuse its memory; the borrow
+    /// checker makes sure no value from the previous document is still in
+    /// use.
+    ///
+    /// ```
+    /// # use json_parser::{ArenaValue, Bump, Parser};
+    /// let mut bump = Bump::new();
+    /// for input in [r#"{"id": 1, "tags": ["a"]}"#, r#"{"id": 2, "tags": ["b\n"]}"#] {
+    ///     let doc = Parser::parse_in_arena(input, &bump).unwrap();
+    ///     assert!(matches!(doc.get("tags").and_then(|t| t.get_index(0)), Some(ArenaValue::String(_))));
+    ///     bump.reset();
+    /// }
+    /// ```
+    pub fn parse_in_arena<'b>(input: &str, bump: &'b Bump) -> Result<ArenaValue<'b>, ParseError> {
+        Parser::parse_in_arena_with(input, ParserOptions::default(), bump)
+    }
+
+    /// Like `parse_in_arena`, parsing under `options`.
+    pub fn parse_in_arena_with<'b>(input: &str, options: ParserOptions, bump: &'b Bump) -> Result<ArenaValue<'b>, ParseError> {
+        Parser::with_options(input, options).parse_with(&mut Arena { source: input, bump })
+    }
+}
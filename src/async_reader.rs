@@ -0,0 +1,60 @@
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::{JsonValue, ParseError, ParseErrorKind, Parser, ParserOptions};
+
+/// Parses a single JSON document from an async reader without blocking the
+/// executor on reads.
+///
+/// The parser can't wait for input in the middle of a value, so this does
+/// not parse as the bytes arrive: it reads the whole stream into memory,
+/// awaiting each chunk, and then parses it through the same incremental
+/// reader path as `from_reader`, so encoding errors and positions are
+/// reported identically. Memory use is therefore that of the whole
+/// document, and no syntax error is reported before the stream ends. Use
+/// [`parse_from_async_reader_with_options`] with `max_input_length` set to
+/// bound that memory for untrusted input.
+///
+/// Dropping the future part way through loses the bytes read so far. With a
+/// reader passed by `&mut`, the reader is left after them, mid-document, so
+/// the document can't be parsed from it again.
+pub async fn parse_from_async_reader<R: AsyncRead + Unpin>(reader: R) -> Result<JsonValue, ParseError> {
+    parse_from_async_reader_with_options(reader, ParserOptions::default()).await
+}
+
+/// Like [`parse_from_async_reader`], parsing with `options`.
+///
+/// `max_input_length` is enforced while reading: the stream is read no
+/// further than a byte past the limit, so an oversized document is never
+/// buffered whole, and the `InputTooLong` error is the one `from_reader`
+/// would report for the same bytes.
+pub async fn parse_from_async_reader_with_options<R: AsyncRead + Unpin>(
+    mut reader: R,
+    options: ParserOptions,
+) -> Result<JsonValue, ParseError> {
+    // One byte past the limit is enough for the parser to see it was passed.
+    let limit = options.max_input_length.map(|max| max.saturating_add(1));
+    let mut bytes = Vec::new();
+    let mut chunk = [0u8; 8 * 1024];
+    loop {
+        let want = match limit {
+            Some(limit) => chunk.len().min(limit - bytes.len()),
+            None => chunk.len(),
+        };
+        if want == 0 {
+            break;
+        }
+        match reader.read(&mut chunk[..want]).await {
+            Ok(0) => break,
+            Ok(n) => bytes.extend_from_slice(&chunk[..n]),
+            Err(e) => {
+                return Err(ParseError {
+                    kind: ParseErrorKind::Io(e.kind()),
+                    message: format!("failed to read input: {}", e),
+                    position: String::from_utf8_lossy(&bytes).chars().count(),
+                    byte_offset: bytes.len(),
+                });
+            }
+        }
+    }
+    Parser::with_reader(bytes.as_slice(), options).parse()
+}
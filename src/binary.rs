@@ -0,0 +1,55 @@
+// What the CBOR and MessagePack decoders share: reading the input a few
+// bytes at a time, and how deeply containers may nest in it.
+
+// Containers nested deeper than this are rejected when decoding, as the
+// parser rejects them by default.
+pub(crate) const MAX_DEPTH: usize = crate::options::DEFAULT_MAX_DEPTH;
+
+// The input ended partway through an item. Each codec's error converts from
+// it, so a short read can be passed on with `?`.
+pub(crate) struct UnexpectedEnd;
+
+pub(crate) struct Cursor<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Cursor { bytes, position: 0 }
+    }
+
+    // Byte offset of the next byte to be read.
+    pub(crate) fn position(&self) -> usize {
+        self.position
+    }
+
+    pub(crate) fn at_end(&self) -> bool {
+        self.position == self.bytes.len()
+    }
+
+    // Only CBOR, looking for the break after indefinite-length items, reads
+    // a byte without taking it.
+    #[cfg(feature = "cbor")]
+    pub(crate) fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.position).copied()
+    }
+
+    pub(crate) fn take(&mut self, len: usize) -> Result<&'a [u8], UnexpectedEnd> {
+        let bytes = self.bytes;
+        let end = self.position.checked_add(len).filter(|&end| end <= bytes.len());
+        let slice = &bytes[self.position..end.ok_or(UnexpectedEnd)?];
+        self.position += len;
+        Ok(slice)
+    }
+
+    pub(crate) fn take_array<const N: usize>(&mut self) -> Result<[u8; N], UnexpectedEnd> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    // Room to reserve for `len` items: every item takes at least a byte, so
+    // a length beyond the input left is trusted no further than that.
+    pub(crate) fn capacity(&self, len: u64) -> usize {
+        len.min((self.bytes.len() - self.position) as u64) as usize
+    }
+}
@@ -0,0 +1,144 @@
+use alloc::borrow::Cow;
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{
+    JsonValue, Map, ParseError, Parser,
+    options::DuplicateKeyPolicy,
+    lexer::ScannedString,
+    parser::{Build, insert_member},
+};
+
+/// A parsed value whose strings and keys borrow from the input where
+/// possible. A string is only copied into an owned `String` when it contained
+/// escape sequences that had to be decoded.
+#[derive(Debug, PartialEq, Clone)]
+pub enum JsonValueRef<'a> {
+    Null,
+    Boolean(bool),
+    Number(f64),
+    RawNumber(Cow<'a, str>),
+    String(Cow<'a, str>),
+    Array(Vec<JsonValueRef<'a>>),
+    Object(Map<Cow<'a, str>, JsonValueRef<'a>>),
+}
+
+impl JsonValueRef<'_> {
+    /// Copies the value into an owned `JsonValue`.
+    pub fn to_owned(&self) -> JsonValue {
+        match self {
+            JsonValueRef::Null => JsonValue::Null,
+            JsonValueRef::Boolean(b) => JsonValue::Boolean(*b),
+            JsonValueRef::Number(n) => JsonValue::Number(*n),
+            JsonValueRef::RawNumber(s) => JsonValue::RawNumber(s.to_string()),
+            JsonValueRef::String(s) => JsonValue::String(s.to_string()),
+            JsonValueRef::Array(a) => JsonValue::Array(a.iter().map(JsonValueRef::to_owned).collect()),
+            JsonValueRef::Object(o) => {
+                JsonValue::Object(o.iter().map(|(k, v)| (k.to_string(), v.to_owned())).collect())
+            }
+        }
+    }
+
+    /// Converts into an owned `JsonValue`, reusing strings that were already
+    /// owned.
+    pub fn into_owned(self) -> JsonValue {
+        match self {
+            JsonValueRef::Null => JsonValue::Null,
+            JsonValueRef::Boolean(b) => JsonValue::Boolean(b),
+            JsonValueRef::Number(n) => JsonValue::Number(n),
+            JsonValueRef::RawNumber(s) => JsonValue::RawNumber(s.into_owned()),
+            JsonValueRef::String(s) => JsonValue::String(s.into_owned()),
+            JsonValueRef::Array(a) => JsonValue::Array(a.into_iter().map(JsonValueRef::into_owned).collect()),
+            JsonValueRef::Object(o) => {
+                JsonValue::Object(o.into_iter().map(|(k, v)| (k.into_owned(), v.into_owned())).collect())
+            }
+        }
+    }
+}
+
+struct Borrowed<'a> {
+    source: &'a str,
+}
+
+impl<'a> Borrowed<'a> {
+    fn cow(&self, s: ScannedString) -> Cow<'a, str> {
+        match s.decoded {
+            Some(decoded) => Cow::Owned(decoded),
+            None => Cow::Borrowed(&self.source[s.range]),
+        }
+    }
+}
+
+impl<'a> Build for Borrowed<'a> {
+    type Value = JsonValueRef<'a>;
+    type Array = Vec<Self::Value>;
+    type Object = Map<Cow<'a, str>, JsonValueRef<'a>>;
+
+    const BORROWS_STRINGS: bool = true;
+
+    fn null(&mut self) -> Self::Value {
+        JsonValueRef::Null
+    }
+
+    fn boolean(&mut self, b: bool) -> Self::Value {
+        JsonValueRef::Boolean(b)
+    }
+
+    fn number(&mut self, n: f64) -> Self::Value {
+        JsonValueRef::Number(n)
+    }
+
+    fn raw_number(&mut self, text: ScannedString) -> Self::Value {
+        JsonValueRef::RawNumber(self.cow(text))
+    }
+
+    fn string(&mut self, s: ScannedString) -> Self::Value {
+        JsonValueRef::String(self.cow(s))
+    }
+
+    fn new_array(&mut self) -> Self::Array {
+        Vec::new()
+    }
+
+    fn push(&mut self, array: &mut Self::Array, value: Self::Value) {
+        array.push(value);
+    }
+
+    fn array(&mut self, items: Self::Array) -> Self::Value {
+        JsonValueRef::Array(items)
+    }
+
+    fn new_object(&mut self) -> Self::Object {
+        Map::new()
+    }
+
+    fn insert(
+        &mut self,
+        object: &mut Self::Object,
+        key: ScannedString,
+        value: Self::Value,
+        policy: DuplicateKeyPolicy,
+        collected: &mut Vec<String>,
+    ) -> Result<(), String> {
+        insert_member(object, self.cow(key), value, policy, collected, |existing, value, first| {
+            if first {
+                *existing = JsonValueRef::Array(vec![core::mem::replace(existing, JsonValueRef::Null)]);
+            }
+            if let JsonValueRef::Array(items) = existing {
+                items.push(value);
+            }
+        })
+    }
+
+    fn object(&mut self, object: Self::Object) -> Self::Value {
+        JsonValueRef::Object(object)
+    }
+}
+
+impl Parser {
+    /// Parses `input` into a `JsonValueRef` that borrows escape-free strings
+    /// and keys straight from `input` instead of allocating them.
+    pub fn parse_borrowed(input: &str) -> Result<JsonValueRef<'_>, ParseError> {
+        Parser::new(input).parse_with(&mut Borrowed { source: input })
+    }
+}
@@ -0,0 +1,110 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{JsonValue, Map};
+
+/// Builds an object value one member at a time.
+///
+/// ```
+/// # use json_parser::{ArrayBuilder, ObjectBuilder};
+/// let user = ObjectBuilder::new()
+///     .insert("name", "bob")
+///     .insert("tags", ArrayBuilder::new().push(1).push(2).build())
+///     .insert_some("email", None::<&str>)
+///     .build();
+/// assert_eq!(user.get_path("tags[1]"), Some(&2.into()));
+/// assert!(user.get_path("email").is_none());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ObjectBuilder {
+    map: Map<String, JsonValue>,
+}
+
+impl ObjectBuilder {
+    pub fn new() -> Self {
+        ObjectBuilder::default()
+    }
+
+    /// Adds a member, replacing any earlier value for the same key.
+    pub fn insert(mut self, key: impl Into<String>, value: impl Into<JsonValue>) -> Self {
+        self.map.insert(key.into(), value.into());
+        self
+    }
+
+    /// Same as `insert`, for callers who think of building an object as
+    /// setting its keys.
+    pub fn set(self, key: impl Into<String>, value: impl Into<JsonValue>) -> Self {
+        self.insert(key, value)
+    }
+
+    /// Adds a member only when `condition` is true.
+    pub fn insert_if(self, condition: bool, key: impl Into<String>, value: impl Into<JsonValue>) -> Self {
+        if condition { self.insert(key, value) } else { self }
+    }
+
+    /// Adds a member for `Some`, and leaves the key out entirely for `None`.
+    pub fn insert_some<T: Into<JsonValue>>(self, key: impl Into<String>, value: Option<T>) -> Self {
+        match value {
+            Some(value) => self.insert(key, value),
+            None => self,
+        }
+    }
+
+    pub fn build(self) -> JsonValue {
+        JsonValue::Object(self.map)
+    }
+}
+
+/// Builds an array value one element at a time.
+#[derive(Debug, Clone, Default)]
+pub struct ArrayBuilder {
+    items: Vec<JsonValue>,
+}
+
+impl ArrayBuilder {
+    pub fn new() -> Self {
+        ArrayBuilder::default()
+    }
+
+    pub fn push(mut self, value: impl Into<JsonValue>) -> Self {
+        self.items.push(value.into());
+        self
+    }
+
+    pub fn push_if(self, condition: bool, value: impl Into<JsonValue>) -> Self {
+        if condition { self.push(value) } else { self }
+    }
+
+    /// Appends the value for `Some` and nothing for `None`.
+    pub fn push_some<T: Into<JsonValue>>(self, value: Option<T>) -> Self {
+        match value {
+            Some(value) => self.push(value),
+            None => self,
+        }
+    }
+
+    pub fn build(self) -> JsonValue {
+        JsonValue::Array(self.items)
+    }
+}
+
+impl JsonValue {
+    /// Starts building an array; shorthand for `ArrayBuilder::new()`.
+    ///
+    /// ```
+    /// # use json_parser::JsonValue;
+    /// let mut points = JsonValue::array();
+    /// for i in 0..3 {
+    ///     points = points.push(JsonValue::object().set("x", i).set("y", i * 2).build());
+    /// }
+    /// let points = points.build();
+    /// assert_eq!(points.get_path("[2].y"), Some(&4.into()));
+    /// ```
+    pub fn array() -> ArrayBuilder {
+        ArrayBuilder::new()
+    }
+
+    /// Starts building an object; shorthand for `ObjectBuilder::new()`.
+    pub fn object() -> ObjectBuilder {
+        ObjectBuilder::new()
+    }
+}
@@ -0,0 +1,114 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use crate::{JsonValue, ParseError, Parser, ParserOptions};
+
+/// A parser that remembers the values of recently parsed inputs, for services
+/// that parse the same small documents over and over.
+///
+/// Results are shared as `Arc<JsonValue>`, so a hit costs a hash lookup rather
+/// than a parse or a deep clone. At most `capacity` documents are kept; when
+/// full, the least recently used one is evicted. Errors are never cached. The
+/// cache sits behind a `Mutex`, so one `CachingParser` can be shared between
+/// threads; parsing itself happens outside the lock.
+///
+/// ```
+/// # use json_parser::CachingParser;
+/// # use std::sync::Arc;
+/// let cache = CachingParser::new(16);
+/// let a = cache.parse(r#"{"debug": true}"#).unwrap();
+/// let b = cache.parse(r#"{"debug": true}"#).unwrap();
+/// assert!(Arc::ptr_eq(&a, &b));
+/// ```
+pub struct CachingParser {
+    options: ParserOptions,
+    capacity: usize,
+    cache: Mutex<Lru>,
+}
+
+struct Lru {
+    entries: HashMap<String, (Arc<JsonValue>, u64)>,
+    clock: u64,
+}
+
+impl CachingParser {
+    pub fn new(capacity: usize) -> Self {
+        CachingParser::with_options(capacity, ParserOptions::default())
+    }
+
+    pub fn with_options(capacity: usize, options: ParserOptions) -> Self {
+        CachingParser {
+            options,
+            capacity,
+            cache: Mutex::new(Lru {
+                entries: HashMap::new(),
+                clock: 0,
+            }),
+        }
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Arc<JsonValue>, ParseError> {
+        if let Some(value) = self.lock().get(input) {
+            return Ok(value);
+        }
+
+        let value = Arc::new(Parser::with_options(input, self.options.clone()).parse()?);
+        if self.capacity > 0 {
+            self.lock().insert(input, Arc::clone(&value), self.capacity);
+        }
+        Ok(value)
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of documents currently cached.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        self.lock().entries.clear();
+    }
+
+    // A panic while holding the lock can't leave the map half-updated, so a
+    // poisoned cache is still safe to use.
+    fn lock(&self) -> std::sync::MutexGuard<'_, Lru> {
+        self.cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Lru {
+    fn get(&mut self, input: &str) -> Option<Arc<JsonValue>> {
+        self.clock += 1;
+        let clock = self.clock;
+        self.entries.get_mut(input).map(|(value, last_used)| {
+            *last_used = clock;
+            Arc::clone(value)
+        })
+    }
+
+    // Eviction scans for the oldest entry, which is cheap next to a parse at
+    // the small capacities this is meant for.
+    fn insert(&mut self, input: &str, value: Arc<JsonValue>, capacity: usize) {
+        if !self.entries.contains_key(input) && self.entries.len() >= capacity {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(key) = oldest {
+                self.entries.remove(&key);
+            }
+        }
+        self.clock += 1;
+        self.entries.insert(input.to_string(), (value, self.clock));
+    }
+}
@@ -0,0 +1,307 @@
+use core::{error::Error, fmt, str};
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{
+    JsonValue, Map,
+    binary::{Cursor, MAX_DEPTH, UnexpectedEnd},
+    value::integer_value,
+};
+
+/// Why `from_cbor` failed. Offsets are byte offsets into the input of the
+/// item at fault.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CborError {
+    /// The input ended partway through an item.
+    UnexpectedEnd,
+    /// Bytes left over after the first complete item.
+    TrailingData { offset: usize },
+    /// Bytes that are not well-formed CBOR.
+    Malformed { offset: usize, message: &'static str },
+    /// Well-formed CBOR with no JSON equivalent, such as a byte string, a
+    /// tag or a map key that is not a text string.
+    Unsupported { offset: usize, what: &'static str },
+    /// Arrays and maps nested more than 512 deep.
+    TooDeep { offset: usize },
+}
+
+impl fmt::Display for CborError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CborError::UnexpectedEnd => write!(f, "unexpected end of CBOR input"),
+            CborError::TrailingData { offset } => write!(f, "unexpected data after the CBOR item at offset {}", offset),
+            CborError::Malformed { offset, message } => write!(f, "malformed CBOR at offset {}: {}", offset, message),
+            CborError::Unsupported { offset, what } => {
+                write!(f, "{} at offset {} has no JSON equivalent", what, offset)
+            }
+            CborError::TooDeep { offset } => write!(f, "CBOR nested too deeply at offset {}", offset),
+        }
+    }
+}
+
+impl Error for CborError {}
+
+impl From<UnexpectedEnd> for CborError {
+    fn from(_: UnexpectedEnd) -> Self {
+        CborError::UnexpectedEnd
+    }
+}
+
+/// Encodes the value as CBOR (RFC 8949). Integral numbers that fit in 64
+/// bits become CBOR integers, and other numbers floats, in single precision
+/// when that loses nothing. Map keys are written in the deterministic order
+/// of RFC 8949 section 4.2.1, so equal values always encode to the same
+/// bytes. A `RawNumber` is encoded by its numeric value.
+///
+/// ```
+/// # use json_parser::{Parser, from_cbor, to_cbor};
+/// let value = Parser::new(r#"{"a": [1, -2, 0.5, "x", null, true]}"#).parse().unwrap();
+/// let bytes = to_cbor(&value);
+/// assert_eq!(bytes[..4], [0xa1, 0x61, b'a', 0x86]);
+/// assert_eq!(from_cbor(&bytes).unwrap(), value);
+/// ```
+pub fn to_cbor(value: &JsonValue) -> Vec<u8> {
+    let mut out = Vec::new();
+    encode(&mut out, value);
+    out
+}
+
+fn encode(out: &mut Vec<u8>, value: &JsonValue) {
+    match value {
+        JsonValue::Null => out.push(0xf6),
+        JsonValue::Boolean(b) => out.push(if *b { 0xf5 } else { 0xf4 }),
+        JsonValue::Number(n) => encode_number(out, *n),
+        JsonValue::RawNumber(s) => match (s.parse::<u64>(), s.parse::<i64>()) {
+            (Ok(n), _) => write_head(out, 0, n),
+            // "-0" parses as the integer 0, and is left to `encode_number`
+            // to keep its sign.
+            (_, Ok(n)) if n < 0 => write_head(out, 1, !(n as u64)),
+            _ => encode_number(out, s.parse().unwrap_or_default()),
+        },
+        JsonValue::String(s) => {
+            write_head(out, 3, s.len() as u64);
+            out.extend_from_slice(s.as_bytes());
+        }
+        JsonValue::Array(items) => {
+            write_head(out, 4, items.len() as u64);
+            for item in items {
+                encode(out, item);
+            }
+        }
+        JsonValue::Object(map) => {
+            write_head(out, 5, map.len() as u64);
+            // Ordering by length first, then bytewise, is the same as
+            // ordering by the keys' encoded bytes.
+            let mut members: Vec<_> = map.iter().collect();
+            members.sort_unstable_by(|a, b| a.0.len().cmp(&b.0.len()).then_with(|| a.0.cmp(b.0)));
+            for (key, value) in members {
+                write_head(out, 3, key.len() as u64);
+                out.extend_from_slice(key.as_bytes());
+                encode(out, value);
+            }
+        }
+    }
+}
+
+fn encode_number(out: &mut Vec<u8>, n: f64) {
+    // -0.0 stays a float so that its sign survives.
+    let integral = n % 1.0 == 0.0 && !(n == 0.0 && n.is_sign_negative());
+    if integral && (0.0..18_446_744_073_709_551_616.0).contains(&n) {
+        write_head(out, 0, n as u64);
+    } else if integral && (-18_446_744_073_709_551_616.0..0.0).contains(&n) {
+        // A negative integer is stored as -1 minus the argument. -n is
+        // exact, while -1 - n need not be, so the 1 is taken off after
+        // converting; only -2^64 itself does not fit.
+        let magnitude = -n;
+        write_head(out, 1, if magnitude == 18_446_744_073_709_551_616.0 { u64::MAX } else { magnitude as u64 - 1 });
+    } else if (n as f32) as f64 == n || n.is_nan() {
+        out.push(0xfa);
+        out.extend_from_slice(&(n as f32).to_be_bytes());
+    } else {
+        out.push(0xfb);
+        out.extend_from_slice(&n.to_be_bytes());
+    }
+}
+
+// Writes the initial byte of an item of `major` type and its argument,
+// using the shortest encoding of the argument.
+fn write_head(out: &mut Vec<u8>, major: u8, argument: u64) {
+    let major = major << 5;
+    match argument {
+        0..=23 => out.push(major | argument as u8),
+        24..=0xff => out.extend_from_slice(&[major | 24, argument as u8]),
+        0x100..=0xffff => {
+            out.push(major | 25);
+            out.extend_from_slice(&(argument as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(major | 26);
+            out.extend_from_slice(&(argument as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(major | 27);
+            out.extend_from_slice(&argument.to_be_bytes());
+        }
+    }
+}
+
+/// Decodes a single CBOR item covering all of `bytes`.
+///
+/// Integers become numbers, except that one too large to be an exact f64
+/// becomes a `RawNumber` with its decimal digits, so no precision is lost.
+/// Half, single and double precision floats are all read, as are
+/// indefinite-length text strings, arrays and maps. `undefined` decodes as
+/// `Null`, and a repeated map key keeps its last value. Byte strings, tags,
+/// other simple values and map keys that are not text are errors.
+///
+/// ```
+/// # use json_parser::{JsonValue, from_cbor};
+/// // [1, {"b": 2.5}] with an indefinite-length array
+/// let value = from_cbor(&[0x9f, 0x01, 0xa1, 0x61, b'b', 0xf9, 0x41, 0x00, 0xff]).unwrap();
+/// assert_eq!(value.to_string(), r#"[1, {"b": 2.5}]"#);
+/// assert!(from_cbor(&[0x42, 0x01, 0x02]).is_err()); // a byte string
+/// ```
+pub fn from_cbor(bytes: &[u8]) -> Result<JsonValue, CborError> {
+    let mut decoder = Decoder { input: Cursor::new(bytes) };
+    let value = decoder.decode(0)?;
+    if !decoder.input.at_end() {
+        return Err(CborError::TrailingData { offset: decoder.input.position() });
+    }
+    Ok(value)
+}
+
+struct Decoder<'a> {
+    input: Cursor<'a>,
+}
+
+// The initial byte of an item: its major type and, if it is not of
+// indefinite length, its argument.
+struct Head {
+    offset: usize,
+    major: u8,
+    argument: Option<u64>,
+    info: u8,
+}
+
+impl<'a> Decoder<'a> {
+    fn read_head(&mut self) -> Result<Head, CborError> {
+        let offset = self.input.position();
+        let [initial] = self.input.take_array()?;
+        let (major, info) = (initial >> 5, initial & 0x1f);
+        let argument = match info {
+            0..=23 => Some(info as u64),
+            24 => Some(u8::from_be_bytes(self.input.take_array()?) as u64),
+            25 => Some(u16::from_be_bytes(self.input.take_array()?) as u64),
+            26 => Some(u32::from_be_bytes(self.input.take_array()?) as u64),
+            27 => Some(u64::from_be_bytes(self.input.take_array()?)),
+            31 if matches!(major, 2..=5 | 7) => None,
+            _ => return Err(CborError::Malformed { offset, message: "reserved additional information" }),
+        };
+        Ok(Head { offset, major, argument, info })
+    }
+
+    // Whether the next byte is the "break" that ends an indefinite-length
+    // item, consuming it if so.
+    fn at_break(&mut self) -> Result<bool, CborError> {
+        match self.input.peek() {
+            Some(0xff) => {
+                self.input.take(1)?;
+                Ok(true)
+            }
+            Some(_) => Ok(false),
+            None => Err(CborError::UnexpectedEnd),
+        }
+    }
+
+    fn decode(&mut self, depth: usize) -> Result<JsonValue, CborError> {
+        let head = self.read_head()?;
+        let offset = head.offset;
+        match (head.major, head.argument) {
+            (0, Some(n)) => Ok(integer_value(n as i128)),
+            (1, Some(n)) => Ok(integer_value(-1 - n as i128)),
+            (2, _) => Err(CborError::Unsupported { offset, what: "a byte string" }),
+            (3, Some(len)) => Ok(JsonValue::String(self.text(len, offset)?.to_string())),
+            (3, None) => {
+                let mut text = String::new();
+                while !self.at_break()? {
+                    match self.read_head()? {
+                        Head { major: 3, argument: Some(len), offset, .. } => text.push_str(self.text(len, offset)?),
+                        Head { offset, .. } => {
+                            return Err(CborError::Malformed { offset, message: "expected a text string chunk" });
+                        }
+                    }
+                }
+                Ok(JsonValue::String(text))
+            }
+            (4 | 5, _) if depth >= MAX_DEPTH => Err(CborError::TooDeep { offset }),
+            (4, len) => {
+                let mut items = Vec::with_capacity(len.map_or(0, |len| self.input.capacity(len)));
+                while self.more(len, items.len())? {
+                    items.push(self.decode(depth + 1)?);
+                }
+                Ok(JsonValue::Array(items))
+            }
+            (5, len) => {
+                let mut map = Map::new();
+                let mut count = 0;
+                while self.more(len, count)? {
+                    let key_offset = self.input.position();
+                    let JsonValue::String(key) = self.decode(depth + 1)? else {
+                        return Err(CborError::Unsupported {
+                            offset: key_offset,
+                            what: "a map key that is not a text string",
+                        });
+                    };
+                    map.insert(key, self.decode(depth + 1)?);
+                    count += 1;
+                }
+                Ok(JsonValue::Object(map))
+            }
+            (6, _) => Err(CborError::Unsupported { offset, what: "a tag" }),
+            (7, argument) => self.simple(head.info, argument, offset),
+            _ => unreachable!("every major type is covered"),
+        }
+    }
+
+    // Whether a container of `len` items, or of indefinite length for
+    // `None`, has more after the `read` so far.
+    fn more(&mut self, len: Option<u64>, read: usize) -> Result<bool, CborError> {
+        match len {
+            Some(len) => Ok((read as u64) < len),
+            None => Ok(!self.at_break()?),
+        }
+    }
+
+    fn text(&mut self, len: u64, offset: usize) -> Result<&'a str, CborError> {
+        let len = usize::try_from(len).map_err(|_| CborError::UnexpectedEnd)?;
+        str::from_utf8(self.input.take(len)?).map_err(|_| CborError::Malformed { offset, message: "invalid UTF-8 in a text string" })
+    }
+
+    fn simple(&mut self, info: u8, argument: Option<u64>, offset: usize) -> Result<JsonValue, CborError> {
+        Ok(match (info, argument) {
+            (20, _) => JsonValue::Boolean(false),
+            (21, _) => JsonValue::Boolean(true),
+            (22 | 23, _) => JsonValue::Null,
+            (25, Some(bits)) => JsonValue::Number(f16_to_f64(bits as u16)),
+            (26, Some(bits)) => JsonValue::Number(f32::from_bits(bits as u32) as f64),
+            (27, Some(bits)) => JsonValue::Number(f64::from_bits(bits)),
+            (31, _) => return Err(CborError::Malformed { offset, message: "unexpected break" }),
+            _ => return Err(CborError::Unsupported { offset, what: "a simple value" }),
+        })
+    }
+}
+
+// Half precision has a 5 bit exponent biased by 15 and a 10 bit fraction.
+fn f16_to_f64(bits: u16) -> f64 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = ((bits >> 10) & 0x1f) as i64;
+    let fraction = (bits & 0x3ff) as f64;
+    // 2^e, built directly from its bits since `powi` needs std.
+    let power_of_two = |e: i64| f64::from_bits(((e + 1023) as u64) << 52);
+    sign * match exponent {
+        0 => fraction * power_of_two(-24),
+        31 if fraction == 0.0 => f64::INFINITY,
+        31 => f64::NAN,
+        _ => (1024.0 + fraction) * power_of_two(exponent - 25),
+    }
+}
@@ -0,0 +1,101 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{JsonValue, NonFinitePolicy, WriteOptions};
+
+// Lenient conversions following JavaScript's rules, for callers such as
+// template engines that want a best-effort reading of any value. The
+// accessors like `as_f64` stay strict: they only ever return a value that
+// already has the requested type.
+impl JsonValue {
+    /// Converts the value to a number the way JavaScript's `Number()`
+    /// does, returning `None` where it would give `NaN`. `null` and blank
+    /// strings are 0, booleans are 1 and 0, and strings are parsed after
+    /// trimming whitespace, including `Infinity` and `0x`, `0o` and `0b`
+    /// integers. An array converts as its `coerce_to_string` text would,
+    /// so `[]` is 0 and `["7"]` is 7; objects are never numbers.
+    ///
+    /// ```
+    /// # use json_parser::{JsonValue, Parser};
+    /// assert_eq!(JsonValue::from(" 2.5 ").coerce_to_number(), Some(2.5));
+    /// assert_eq!(JsonValue::from(true).coerce_to_number(), Some(1.0));
+    /// assert_eq!(JsonValue::from("12px").coerce_to_number(), None);
+    /// assert_eq!(Parser::new("[\"0x10\"]").parse().unwrap().coerce_to_number(), Some(16.0));
+    /// ```
+    pub fn coerce_to_number(&self) -> Option<f64> {
+        match self {
+            JsonValue::Null => Some(0.0),
+            JsonValue::Boolean(b) => Some(if *b { 1.0 } else { 0.0 }),
+            JsonValue::Number(n) => Some(*n).filter(|n| !n.is_nan()),
+            JsonValue::RawNumber(s) | JsonValue::String(s) => parse_js_number(s),
+            JsonValue::Array(_) => parse_js_number(&self.coerce_to_string()),
+            JsonValue::Object(_) => None,
+        }
+    }
+
+    /// Converts the value to text the way JavaScript's `String()` does,
+    /// except that `null` becomes the empty string. Numbers are written as
+    /// JavaScript writes them, `NaN` and `Infinity` included, arrays join
+    /// their elements' text with commas, and every object is
+    /// `[object Object]`.
+    ///
+    /// ```
+    /// # use json_parser::Parser;
+    /// let value = Parser::new(r#"[1e21, "a", null, [true]]"#).parse().unwrap();
+    /// assert_eq!(value.coerce_to_string(), "1e+21,a,,true");
+    /// ```
+    pub fn coerce_to_string(&self) -> String {
+        match self {
+            JsonValue::Null => String::new(),
+            JsonValue::Boolean(b) => b.to_string(),
+            JsonValue::Number(_) => self.to_string_with(&WriteOptions {
+                non_finite: NonFinitePolicy::Literal,
+                ..Default::default()
+            }),
+            JsonValue::RawNumber(s) | JsonValue::String(s) => s.clone(),
+            JsonValue::Array(items) => items.iter().map(JsonValue::coerce_to_string).collect::<Vec<_>>().join(","),
+            JsonValue::Object(_) => "[object Object]".to_string(),
+        }
+    }
+
+    /// Whether the value is truthy in JavaScript: everything except `null`,
+    /// `false`, zero, `NaN` and the empty string. Empty arrays and objects
+    /// are truthy, and so is the string `"0"`.
+    pub fn coerce_to_bool(&self) -> bool {
+        match self {
+            JsonValue::Null => false,
+            JsonValue::Boolean(b) => *b,
+            JsonValue::Number(n) => *n != 0.0 && !n.is_nan(),
+            JsonValue::RawNumber(s) => s.parse::<f64>().is_ok_and(|n| n != 0.0),
+            JsonValue::String(s) => !s.is_empty(),
+            JsonValue::Array(_) | JsonValue::Object(_) => true,
+        }
+    }
+}
+
+// JavaScript's string to number conversion. Rust's float parsing accepts a
+// superset of the decimal forms, like `inf` and `nan`, so anything with
+// other characters is turned away first.
+fn parse_js_number(s: &str) -> Option<f64> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Some(0.0);
+    }
+    let (negative, unsigned) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    if unsigned == "Infinity" {
+        return Some(if negative { f64::NEG_INFINITY } else { f64::INFINITY });
+    }
+    let radix = match s.get(..2) {
+        Some("0x" | "0X") => 16,
+        Some("0o" | "0O") => 8,
+        Some("0b" | "0B") => 2,
+        _ if s.bytes().all(|b| b.is_ascii_digit() || matches!(b, b'.' | b'e' | b'E' | b'+' | b'-')) => {
+            return s.parse().ok();
+        }
+        _ => return None,
+    };
+    // Integers in other bases may be too long for u64 and take no sign.
+    s[2..].chars().try_fold(0.0, |n: f64, c| Some(n * radix as f64 + c.to_digit(radix)? as f64)).filter(|_| s.len() > 2)
+}
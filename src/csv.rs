@@ -0,0 +1,153 @@
+use alloc::{borrow::Cow, collections::BTreeSet};
+use core::{error::Error, fmt};
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{Format, JsonValue, Map};
+
+/// Settings for `JsonValue::to_csv_with`.
+#[derive(Debug, Clone, Default)]
+pub struct CsvOptions {
+    /// Write exactly these columns, in this order, instead of every key
+    /// found in the rows. Keys not listed are left out. With columns given,
+    /// an empty array still produces the header row.
+    pub columns: Option<Vec<String>>,
+
+    /// Order the columns found in the rows by key rather than by where they
+    /// first appear.
+    pub sort_columns: bool,
+
+    /// Write an array or object in a cell as compact JSON instead of failing
+    /// with `CsvError::NestedValue`.
+    pub nested_as_json: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CsvError {
+    /// The value is not an array.
+    NotAnArray { found: &'static str },
+    /// Row number `row` (0-based) is not an object.
+    NotAnObject { row: usize, found: &'static str },
+    /// A cell holds an array or object, without `nested_as_json`.
+    NestedValue { row: usize, column: String },
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CsvError::NotAnArray { found } => write!(f, "expected an array of objects, found {}", found),
+            CsvError::NotAnObject { row, found } => write!(f, "row {}: expected an object, found {}", row, found),
+            CsvError::NestedValue { row, column } => {
+                write!(f, "row {}: column '{}' holds a nested array or object", row, column)
+            }
+        }
+    }
+}
+
+impl Error for CsvError {}
+
+impl JsonValue {
+    /// Converts an array of flat objects into CSV, one row per object under
+    /// a header row of column names; see `to_csv_with`.
+    ///
+    /// ```
+    /// # use json_parser::Parser;
+    /// let rows = Parser::new(r#"[{"name": "a, b", "n": 1}, {"name": "say \"hi\"", "extra": true}]"#)
+    ///     .parse()
+    ///     .unwrap();
+    /// assert_eq!(rows.to_csv().unwrap(), "n,name,extra\n1,\"a, b\",\n,\"say \"\"hi\"\"\",true\n");
+    /// ```
+    pub fn to_csv(&self) -> Result<String, CsvError> {
+        self.to_csv_with(&CsvOptions::default())
+    }
+
+    /// Converts an array of objects into CSV as `options` says.
+    ///
+    /// Unless `columns` is set, the columns are every key of every row, in
+    /// the order they first appear; keys new in the same row are taken in
+    /// key order, since an object's members have no order of their own.
+    /// Missing members and `null` become empty cells, and strings are
+    /// written without JSON quotes. A cell containing a comma, a quote or a
+    /// line break is quoted, with its quotes doubled. Rows end in `\n`.
+    ///
+    /// An empty array gives empty output, or just the header if `columns`
+    /// is set.
+    pub fn to_csv_with(&self, options: &CsvOptions) -> Result<String, CsvError> {
+        let JsonValue::Array(items) = self else {
+            return Err(CsvError::NotAnArray { found: self.type_name() });
+        };
+        let mut rows = Vec::with_capacity(items.len());
+        for (row, item) in items.iter().enumerate() {
+            match item {
+                JsonValue::Object(object) => rows.push(object),
+                other => return Err(CsvError::NotAnObject { row, found: other.type_name() }),
+            }
+        }
+
+        let columns: Vec<&str> = match &options.columns {
+            Some(columns) => columns.iter().map(String::as_str).collect(),
+            None => {
+                let mut columns = Vec::new();
+                let mut seen = BTreeSet::new();
+                for object in &rows {
+                    let mut new: Vec<&str> = object.keys().map(String::as_str).filter(|key| !seen.contains(key)).collect();
+                    new.sort_unstable();
+                    seen.extend(new.iter().copied());
+                    columns.extend(new);
+                }
+                if options.sort_columns {
+                    columns.sort_unstable();
+                }
+                columns
+            }
+        };
+        if columns.is_empty() {
+            return Ok(String::new());
+        }
+
+        let mut out = String::new();
+        write_row(&mut out, columns.iter().map(|&column| Cow::Borrowed(column)));
+        for (row, object) in rows.into_iter().enumerate() {
+            let cells = columns
+                .iter()
+                .map(|&column| cell(object, row, column, options))
+                .collect::<Result<Vec<_>, _>>()?;
+            write_row(&mut out, cells.into_iter());
+        }
+        Ok(out)
+    }
+}
+
+fn cell<'a>(object: &'a Map<String, JsonValue>, row: usize, column: &str, options: &CsvOptions) -> Result<Cow<'a, str>, CsvError> {
+    Ok(match object.get(column) {
+        None | Some(JsonValue::Null) => Cow::Borrowed(""),
+        Some(JsonValue::String(s)) => Cow::Borrowed(s),
+        Some(JsonValue::RawNumber(s)) => Cow::Borrowed(s),
+        Some(value @ (JsonValue::Array(_) | JsonValue::Object(_))) => {
+            if !options.nested_as_json {
+                return Err(CsvError::NestedValue {
+                    row,
+                    column: column.to_string(),
+                });
+            }
+            Cow::Owned(value.serialize(Format::Compact))
+        }
+        Some(value) => Cow::Owned(value.to_string()),
+    })
+}
+
+fn write_row<'a>(out: &mut String, fields: impl Iterator<Item = Cow<'a, str>>) {
+    for (i, field) in fields.enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        if field.contains([',', '"', '\n', '\r']) {
+            out.push('"');
+            out.push_str(&field.replace('"', "\"\""));
+            out.push('"');
+        } else {
+            out.push_str(&field);
+        }
+    }
+    out.push('\n');
+}
@@ -0,0 +1,344 @@
+use core::fmt;
+
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor,
+    value::BorrowedStrDeserializer,
+};
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{JsonError, JsonValue, Map};
+
+/// Converts a parsed value into any type implementing serde's
+/// `Deserialize`.
+///
+/// Objects map to structs and maps, arrays to sequences and tuples, and
+/// `null` to `None` and `()`. Enums are externally tagged: a unit variant is
+/// its name as a string, any other variant an object with the name as its
+/// only key. Numbers converted to an integer type must be whole and in
+/// range. Errors inside nested values carry the JSON Pointer to that value,
+/// as `JsonError::AtPath`.
+///
+/// To deserialize borrowed data, such as `&str` fields, call
+/// `T::deserialize(&value)` instead, since `&JsonValue` is a `Deserializer`.
+///
+/// ```
+/// # use json_parser::{Parser, from_value};
+/// #[derive(serde::Deserialize)]
+/// struct Limits {
+///     retries: u8,
+/// }
+///
+/// let value = Parser::new(r#"{"retries": 3}"#).parse().unwrap();
+/// assert_eq!(from_value::<Limits>(value).unwrap().retries, 3);
+///
+/// let value = Parser::new(r#"{"retries": 300}"#).parse().unwrap();
+/// let error = from_value::<Limits>(value).err().unwrap();
+/// assert_eq!(error.to_string(), "/retries: expected u8, found 300");
+/// ```
+pub fn from_value<T: DeserializeOwned>(value: JsonValue) -> Result<T, JsonError> {
+    T::deserialize(&value)
+}
+
+impl de::Error for JsonError {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        JsonError::Custom(message.to_string())
+    }
+}
+
+fn unexpected(expected: &'static str, value: &JsonValue) -> JsonError {
+    JsonError::UnexpectedType {
+        expected,
+        found: value.type_name(),
+    }
+}
+
+// Converts a number to the integer type `T`, which must hold it exactly.
+// Raw numbers are read from their text, so large integers keep every digit.
+fn integer<T: TryFrom<i128>>(value: &JsonValue, expected: &'static str) -> Result<T, JsonError> {
+    let n = match value {
+        JsonValue::Number(n) => *n,
+        JsonValue::RawNumber(s) => match s.parse::<i128>() {
+            Ok(n) => return T::try_from(n).map_err(|_| JsonError::InvalidNumber { expected, found: n as f64 }),
+            Err(_) => s.parse().unwrap_or(f64::NAN),
+        },
+        other => return Err(unexpected("number", other)),
+    };
+    let whole = n % 1.0 == 0.0 && n >= i128::MIN as f64 && n <= i128::MAX as f64;
+    whole
+        .then(|| T::try_from(n as i128).ok())
+        .flatten()
+        .ok_or(JsonError::InvalidNumber { expected, found: n })
+}
+
+// Hands a number to a visitor as an integer when it is one, so that types
+// accepting only integers, like a map key of type `u32`, can take it.
+fn visit_number<'de, V: Visitor<'de>>(n: f64, visitor: V) -> Result<V::Value, JsonError> {
+    if n % 1.0 == 0.0 && n >= 0.0 && n < u64::MAX as f64 {
+        visitor.visit_u64(n as u64)
+    } else if n % 1.0 == 0.0 && n >= i64::MIN as f64 && n < 0.0 {
+        visitor.visit_i64(n as i64)
+    } else {
+        visitor.visit_f64(n)
+    }
+}
+
+macro_rules! deserialize_integer {
+    ($($method:ident => $visit:ident($ty:ty),)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JsonError> {
+                visitor.$visit(integer::<$ty>(self, stringify!($ty))?)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for &'de JsonValue {
+    type Error = JsonError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JsonError> {
+        match self {
+            JsonValue::Null => visitor.visit_unit(),
+            JsonValue::Boolean(b) => visitor.visit_bool(*b),
+            JsonValue::Number(n) => visit_number(*n, visitor),
+            JsonValue::RawNumber(s) => match (s.parse::<u64>(), s.parse::<i64>()) {
+                (Ok(n), _) => visitor.visit_u64(n),
+                (_, Ok(n)) => visitor.visit_i64(n),
+                _ => visitor.visit_f64(s.parse().unwrap_or(f64::NAN)),
+            },
+            JsonValue::String(s) => visitor.visit_borrowed_str(s),
+            JsonValue::Array(_) => self.deserialize_seq(visitor),
+            JsonValue::Object(_) => self.deserialize_map(visitor),
+        }
+    }
+
+    deserialize_integer! {
+        deserialize_i8 => visit_i8(i8),
+        deserialize_i16 => visit_i16(i16),
+        deserialize_i32 => visit_i32(i32),
+        deserialize_i64 => visit_i64(i64),
+        deserialize_i128 => visit_i128(i128),
+        deserialize_u8 => visit_u8(u8),
+        deserialize_u16 => visit_u16(u16),
+        deserialize_u32 => visit_u32(u32),
+        deserialize_u64 => visit_u64(u64),
+        deserialize_u128 => visit_u128(u128),
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JsonError> {
+        let n = self.as_f64().ok_or_else(|| unexpected("number", self))?;
+        if n.is_finite() && (n as f32).is_infinite() {
+            return Err(JsonError::InvalidNumber { expected: "f32", found: n });
+        }
+        visitor.visit_f32(n as f32)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JsonError> {
+        visitor.visit_f64(self.as_f64().ok_or_else(|| unexpected("number", self))?)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JsonError> {
+        match self {
+            JsonValue::Boolean(b) => visitor.visit_bool(*b),
+            other => Err(unexpected("boolean", other)),
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JsonError> {
+        match self {
+            JsonValue::String(s) => visitor.visit_borrowed_str(s),
+            other => Err(unexpected("string", other)),
+        }
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JsonError> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JsonError> {
+        match self {
+            JsonValue::Null => visitor.visit_none(),
+            value => visitor.visit_some(value),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JsonError> {
+        match self {
+            JsonValue::Null => visitor.visit_unit(),
+            other => Err(unexpected("null", other)),
+        }
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, JsonError> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, JsonError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JsonError> {
+        let JsonValue::Array(items) = self else {
+            return Err(unexpected("array", self));
+        };
+        let mut seq = SeqDeserializer {
+            items: items.iter().enumerate(),
+        };
+        let value = visitor.visit_seq(&mut seq)?;
+        match seq.items.len() {
+            0 => Ok(value),
+            _ => Err(de::Error::invalid_length(items.len(), &"fewer elements in array")),
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, JsonError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, JsonError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JsonError> {
+        match self {
+            JsonValue::Object(object) => visitor.visit_map(MapDeserializer {
+                members: object.iter(),
+                value: None,
+            }),
+            other => Err(unexpected("object", other)),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, JsonError> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, JsonError> {
+        match self {
+            JsonValue::String(variant) => visitor.visit_enum(BorrowedStrDeserializer::new(variant)),
+            JsonValue::Object(object) if object.len() == 1 => {
+                let (variant, value) = object.iter().next().expect("the object has one member");
+                visitor.visit_enum(EnumDeserializer { variant, value })
+            }
+            other => Err(unexpected("string or single-member object", other)),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JsonError> {
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        char bytes byte_buf identifier
+    }
+}
+
+struct SeqDeserializer<'de> {
+    items: core::iter::Enumerate<core::slice::Iter<'de, JsonValue>>,
+}
+
+impl<'de> SeqAccess<'de> for SeqDeserializer<'de> {
+    type Error = JsonError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, JsonError> {
+        match self.items.next() {
+            Some((index, item)) => seed.deserialize(item).map(Some).map_err(|e| e.nested(&index.to_string())),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.items.len())
+    }
+}
+
+struct MapDeserializer<'de> {
+    members: <&'de Map<String, JsonValue> as IntoIterator>::IntoIter,
+    // The member whose key was just handed out, waiting for its value.
+    value: Option<(&'de str, &'de JsonValue)>,
+}
+
+impl<'de> MapAccess<'de> for MapDeserializer<'de> {
+    type Error = JsonError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, JsonError> {
+        let Some((key, value)) = self.members.next() else {
+            return Ok(None);
+        };
+        self.value = Some((key, value));
+        seed.deserialize(BorrowedStrDeserializer::<JsonError>::new(key))
+            .map(Some)
+            .map_err(|e| e.nested(key))
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, JsonError> {
+        let (key, value) = self.value.take().expect("next_value_seed is called after next_key_seed");
+        seed.deserialize(value).map_err(|e| e.nested(key))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.members.len())
+    }
+}
+
+// A non-unit enum variant: an object whose only key names the variant.
+struct EnumDeserializer<'de> {
+    variant: &'de str,
+    value: &'de JsonValue,
+}
+
+impl<'de> EnumAccess<'de> for EnumDeserializer<'de> {
+    type Error = JsonError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), JsonError> {
+        let variant = seed.deserialize(BorrowedStrDeserializer::<JsonError>::new(self.variant))?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for EnumDeserializer<'de> {
+    type Error = JsonError;
+
+    fn unit_variant(self) -> Result<(), JsonError> {
+        match self.value {
+            JsonValue::Null => Ok(()),
+            other => Err(unexpected("null", other).nested(self.variant)),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, JsonError> {
+        seed.deserialize(self.value).map_err(|e| e.nested(self.variant))
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, JsonError> {
+        de::Deserializer::deserialize_seq(self.value, visitor).map_err(|e| e.nested(self.variant))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, JsonError> {
+        de::Deserializer::deserialize_map(self.value, visitor).map_err(|e| e.nested(self.variant))
+    }
+}
@@ -0,0 +1,54 @@
+// Helpers called from code generated by `#[derive(FromJson)]`. Not part of
+// the public API.
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{FieldError, FromJson, JsonError, JsonValue, Map};
+
+// Named by the generated code, which cannot rely on `std` being there.
+pub use alloc::{format, string::String};
+
+pub fn expect_object(value: &JsonValue) -> Result<&Map<String, JsonValue>, JsonError> {
+    match value {
+        JsonValue::Object(object) => Ok(object),
+        other => Err(JsonError::UnexpectedType {
+            expected: "object",
+            found: other.type_name(),
+        }),
+    }
+}
+
+pub fn expect_null(value: &JsonValue) -> Result<(), JsonError> {
+    match value {
+        JsonValue::Null => Ok(()),
+        other => Err(JsonError::UnexpectedType {
+            expected: "null",
+            found: other.type_name(),
+        }),
+    }
+}
+
+pub fn field<T: FromJson>(key: &str, value: &JsonValue) -> Result<T, JsonError> {
+    T::from_json(value).map_err(|e| {
+        JsonError::Fields(vec![FieldError::Invalid {
+            field: key.to_string(),
+            message: e.to_string(),
+        }])
+    })
+}
+
+// A missing key is treated like `null`, so `Option` fields may be left out;
+// for any other type it is reported as a missing field.
+pub fn required_field<T: FromJson>(object: &Map<String, JsonValue>, key: &str) -> Result<T, JsonError> {
+    match object.get(key) {
+        Some(value) => field(key, value),
+        None => T::from_json(&JsonValue::Null)
+            .map_err(|_| JsonError::Fields(vec![FieldError::Missing(key.to_string())])),
+    }
+}
+
+pub fn deny_unknown_fields(object: &Map<String, JsonValue>, known: &[&str]) -> Result<(), JsonError> {
+    match object.keys().find(|key| !known.contains(&key.as_str())) {
+        Some(key) => Err(JsonError::custom(format!("unknown field '{}'", key))),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,109 @@
+use core::fmt;
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{JsonValue, ParseError, ParseErrorKind, Parser};
+
+/// A Unicode encoding JSON text may arrive in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Utf32Le,
+    Utf32Be,
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Encoding::Utf8 => "UTF-8",
+            Encoding::Utf16Le => "UTF-16LE",
+            Encoding::Utf16Be => "UTF-16BE",
+            Encoding::Utf32Le => "UTF-32LE",
+            Encoding::Utf32Be => "UTF-32BE",
+        })
+    }
+}
+
+/// Guesses the encoding of JSON text from its first bytes: a byte order mark
+/// if there is one, and otherwise the pattern of zero bytes described in
+/// RFC 4627, which works because a JSON text starts with an ASCII character.
+pub fn detect_encoding(bytes: &[u8]) -> Encoding {
+    match bytes {
+        [0xEF, 0xBB, 0xBF, ..] => Encoding::Utf8,
+        [0x00, 0x00, 0xFE, 0xFF, ..] => Encoding::Utf32Be,
+        [0xFF, 0xFE, 0x00, 0x00, ..] => Encoding::Utf32Le,
+        [0xFE, 0xFF, ..] => Encoding::Utf16Be,
+        [0xFF, 0xFE, ..] => Encoding::Utf16Le,
+        [0, 0, 0, _, ..] => Encoding::Utf32Be,
+        [_, 0, 0, 0, ..] => Encoding::Utf32Le,
+        [0, _, ..] => Encoding::Utf16Be,
+        [_, 0, ..] => Encoding::Utf16Le,
+        _ => Encoding::Utf8,
+    }
+}
+
+/// Parses a JSON document from raw bytes in any of the encodings in
+/// `Encoding`, detected with `detect_encoding`. A leading byte order mark is
+/// skipped. Error positions are char indices into the decoded text, so a BOM
+/// counts as one char. For UTF-8 input byte offsets are into `bytes`; for the
+/// other encodings they are into the text re-encoded as UTF-8.
+///
+/// Input that does not decode in the detected encoding, such as an odd number
+/// of bytes for UTF-16 or an unpaired surrogate, is reported as an error
+/// naming the encoding.
+pub fn parse_bytes(bytes: &[u8]) -> Result<JsonValue, ParseError> {
+    let encoding = detect_encoding(bytes);
+    let text = match encoding {
+        Encoding::Utf8 => {
+            let text = str::from_utf8(bytes).map_err(|e| {
+                let valid = str::from_utf8(&bytes[..e.valid_up_to()]).unwrap_or_default();
+                invalid(encoding, valid, "invalid byte sequence")
+            })?;
+            return Parser::new(text).parse();
+        }
+        Encoding::Utf16Le => decode_utf16(bytes, encoding, u16::from_le_bytes)?,
+        Encoding::Utf16Be => decode_utf16(bytes, encoding, u16::from_be_bytes)?,
+        Encoding::Utf32Le => decode_utf32(bytes, encoding, u32::from_le_bytes)?,
+        Encoding::Utf32Be => decode_utf32(bytes, encoding, u32::from_be_bytes)?,
+    };
+    Parser::new(&text).parse()
+}
+
+fn decode_utf16(bytes: &[u8], encoding: Encoding, unit: fn([u8; 2]) -> u16) -> Result<String, ParseError> {
+    let units = bytes.chunks_exact(2).map(|pair| unit([pair[0], pair[1]]));
+    let mut text = String::with_capacity(bytes.len() / 2);
+    for c in char::decode_utf16(units) {
+        let c = c.map_err(|e| invalid(encoding, &text, &format!("unpaired surrogate 0x{:04X}", e.unpaired_surrogate())))?;
+        text.push(c);
+    }
+    if !bytes.len().is_multiple_of(2) {
+        return Err(invalid(encoding, &text, "input ends partway through a code unit"));
+    }
+    Ok(text)
+}
+
+fn decode_utf32(bytes: &[u8], encoding: Encoding, unit: fn([u8; 4]) -> u32) -> Result<String, ParseError> {
+    let mut text = String::with_capacity(bytes.len() / 4);
+    for chunk in bytes.chunks_exact(4) {
+        let value = unit([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        let c = char::from_u32(value)
+            .ok_or_else(|| invalid(encoding, &text, &format!("0x{:X} is not a Unicode scalar value", value)))?;
+        text.push(c);
+    }
+    if !bytes.len().is_multiple_of(4) {
+        return Err(invalid(encoding, &text, "input ends partway through a code unit"));
+    }
+    Ok(text)
+}
+
+// An error at the end of the text decoded so far.
+pub(crate) fn invalid(encoding: Encoding, decoded: &str, detail: &str) -> ParseError {
+    ParseError {
+        kind: ParseErrorKind::InvalidEncoding(encoding),
+        message: format!("invalid {} input: {}", encoding, detail),
+        position: decoded.chars().count(),
+        byte_offset: decoded.len(),
+    }
+}
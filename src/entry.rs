@@ -0,0 +1,63 @@
+#[cfg(any(not(feature = "std"), feature = "btree_map"))]
+use alloc::collections::btree_map as map;
+#[cfg(all(feature = "std", not(feature = "btree_map")))]
+use std::collections::hash_map as map;
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{JsonValue, Map};
+
+/// A view into a single member of an object value, from `JsonValue::entry`.
+pub enum Entry<'a> {
+    Occupied(map::OccupiedEntry<'a, String, JsonValue>),
+    Vacant(map::VacantEntry<'a, String, JsonValue>),
+}
+
+impl<'a> Entry<'a> {
+    pub fn key(&self) -> &str {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    pub fn or_insert(self, default: JsonValue) -> &'a mut JsonValue {
+        self.or_insert_with(|| default)
+    }
+
+    pub fn or_insert_with<F: FnOnce() -> JsonValue>(self, default: F) -> &'a mut JsonValue {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Runs `f` on the existing value, if there is one.
+    pub fn and_modify<F: FnOnce(&mut JsonValue)>(self, f: F) -> Self {
+        match self {
+            Entry::Occupied(mut entry) => {
+                f(entry.get_mut());
+                Entry::Occupied(entry)
+            }
+            vacant => vacant,
+        }
+    }
+}
+
+impl JsonValue {
+    /// Returns the entry for `key` for in-place manipulation. A `Null` value
+    /// is first turned into an empty object, so entries can be chained
+    /// through freshly inserted members; any other non-object gives `None`.
+    pub fn entry(&mut self, key: impl Into<String>) -> Option<Entry<'_>> {
+        if let JsonValue::Null = self {
+            *self = JsonValue::Object(Map::new());
+        }
+        match self {
+            JsonValue::Object(map) => Some(match map.entry(key.into()) {
+                map::Entry::Occupied(entry) => Entry::Occupied(entry),
+                map::Entry::Vacant(entry) => Entry::Vacant(entry),
+            }),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use core::{error::Error, fmt};
+#[cfg(feature = "std")]
+use std::io;
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{Encoding, FieldError, pointer::push_pointer_segment};
+
+#[derive(Debug)]
+pub struct ParseError {
+    /// What went wrong, for callers that need to react to the cause.
+    pub kind: ParseErrorKind,
+    /// The same, described for people.
+    pub message: String,
+    /// Where the error is, as an index of a char in the input.
+    pub position: usize,
+    /// Where the error is, as a byte offset into the input, so that
+    /// `&input[error.byte_offset..]` is the text from that point on.
+    pub byte_offset: usize,
+}
+
+/// The cause of a `ParseError`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// The input ended in the middle of a value.
+    UnexpectedEnd,
+    /// A character that cannot appear at this point.
+    UnexpectedChar(char),
+    /// Something other than whitespace after a complete document.
+    TrailingData,
+    /// A trailing comma before `]` or `}`.
+    TrailingComma,
+    /// An object key that is not a string (or, with `allow_unquoted_keys`,
+    /// an identifier).
+    InvalidKey,
+    UnterminatedString,
+    UnterminatedArray,
+    UnterminatedObject,
+    /// A backslash escape that JSON does not define, or a `\u` without four
+    /// hex digits.
+    InvalidEscape,
+    /// A `\u` escape for half of a surrogate pair without the other half.
+    LoneSurrogate,
+    /// A raw control character inside a string.
+    ControlCharacter,
+    InvalidNumber,
+    /// A number too large for an f64, with `OverflowPolicy::Error`.
+    NumberOutOfRange,
+    /// Input that is not valid in the encoding it was read as.
+    InvalidEncoding(Encoding),
+    /// Reading the input failed.
+    #[cfg(feature = "std")]
+    Io(io::ErrorKind),
+    /// More than `max_input_length` bytes of input.
+    InputTooLong,
+    /// A string or key over `max_string_length`.
+    StringTooLong,
+    /// An array with more than `max_array_length` elements.
+    TooManyElements,
+    /// An object with more than `max_object_members` members.
+    TooManyMembers,
+    /// A document with more than `max_nodes` values.
+    TooManyNodes,
+    /// Arrays and objects nested more than `max_depth` deep.
+    DepthExceeded,
+    /// A repeated object key, with `DuplicateKeyPolicy::Reject`.
+    DuplicateKey,
+}
+
+impl ParseError {
+    /// Converts the error position into a 1-based `(line, column)` pair for
+    /// the input it was produced from.
+    pub fn line_column(&self, input: &str) -> (usize, usize) {
+        let mut line = 1;
+        let mut column = 1;
+        for c in input.chars().take(self.position) {
+            if c == '\n' {
+                line += 1;
+                column = 1;
+            } else {
+                column += 1;
+            }
+        }
+        (line, column)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Parse error at position {}: {}", self.position, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+/// A value that cannot be serialized as JSON.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum SerializeError {
+    /// A NaN or infinite number, with `NonFinitePolicy::Error`.
+    NonFinite(f64),
+}
+
+impl fmt::Display for SerializeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SerializeError::NonFinite(n) => write!(f, "cannot serialize {} as a JSON number", n),
+        }
+    }
+}
+
+impl Error for SerializeError {}
+
+#[derive(Debug)]
+pub enum JsonError {
+    Parse(ParseError),
+    UnexpectedType {
+        expected: &'static str,
+        found: &'static str,
+    },
+    /// A number that does not fit the requested numeric type, such as a
+    /// fraction or an out-of-range value converted to an integer.
+    InvalidNumber {
+        expected: &'static str,
+        found: f64,
+    },
+    Fields(Vec<FieldError>),
+    /// An error in a nested value, at the JSON Pointer `path`.
+    AtPath { path: String, error: Box<JsonError> },
+    /// A value with more than `max` values in its tree, from `try_clone`.
+    TooManyNodes { max: usize },
+    Custom(String),
+}
+
+impl JsonError {
+    /// Builds an error with a free-form message, for use in hand-written
+    /// `FromJson` implementations (missing fields, invalid values, ...).
+    pub fn custom(message: impl Into<String>) -> Self {
+        JsonError::Custom(message.into())
+    }
+
+    // Records that the error happened inside the element or member
+    // `segment`, which goes in front of whatever path the error already has
+    // from deeper down.
+    pub(crate) fn nested(self, segment: &str) -> JsonError {
+        let mut path = String::new();
+        push_pointer_segment(&mut path, segment);
+        match self {
+            JsonError::AtPath { path: rest, error } => {
+                path.push_str(&rest);
+                JsonError::AtPath { path, error }
+            }
+            error => JsonError::AtPath {
+                path,
+                error: Box::new(error),
+            },
+        }
+    }
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JsonError::Parse(e) => write!(f, "{}", e),
+            JsonError::UnexpectedType { expected, found } => {
+                write!(f, "expected {}, found {}", expected, found)
+            }
+            JsonError::InvalidNumber { expected, found } => {
+                write!(f, "expected {}, found {}", expected, found)
+            }
+            JsonError::Fields(errors) => {
+                for (i, e) in errors.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "; ")?;
+                    }
+                    write!(f, "{}", e)?;
+                }
+                Ok(())
+            }
+            JsonError::AtPath { path, error } => write!(f, "{}: {}", path, error),
+            JsonError::TooManyNodes { max } => write!(f, "value has more than the maximum of {} values", max),
+            JsonError::Custom(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl Error for JsonError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            JsonError::Parse(e) => Some(e),
+            JsonError::AtPath { error, .. } => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for JsonError {
+    fn from(e: ParseError) -> Self {
+        JsonError::Parse(e)
+    }
+}
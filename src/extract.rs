@@ -0,0 +1,143 @@
+use core::{error::Error, fmt};
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{FromJson, JsonError, JsonValue, Map};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldError {
+    Missing(String),
+    Invalid { field: String, message: String },
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FieldError::Missing(field) => write!(f, "missing field '{}'", field),
+            FieldError::Invalid { field, message } => write!(f, "invalid field '{}': {}", field, message),
+        }
+    }
+}
+
+impl Error for FieldError {}
+
+/// Pulls typed fields out of an object, remembering every missing or invalid
+/// field so they can all be reported together by `finish`.
+///
+/// ```
+/// # use json_parser::{FieldExtractor, Parser};
+/// let value = Parser::new(r#"{"name": "Alice", "age": 30}"#).parse().unwrap();
+/// let mut fields = FieldExtractor::new(&value).unwrap();
+/// let name = fields.require_str("name");
+/// let age = fields.optional_f64("age");
+/// fields.finish().unwrap();
+/// assert_eq!(name.unwrap(), "Alice");
+/// assert_eq!(age.unwrap(), Some(30.0));
+/// ```
+pub struct FieldExtractor<'a> {
+    object: &'a Map<String, JsonValue>,
+    errors: Vec<FieldError>,
+}
+
+impl<'a> FieldExtractor<'a> {
+    pub fn new(value: &'a JsonValue) -> Result<Self, JsonError> {
+        match value {
+            JsonValue::Object(object) => Ok(FieldExtractor {
+                object,
+                errors: Vec::new(),
+            }),
+            other => Err(JsonError::UnexpectedType {
+                expected: "object",
+                found: other.type_name(),
+            }),
+        }
+    }
+
+    /// Extracts a required field with a custom conversion.
+    pub fn require_with<T, F>(&mut self, key: &str, convert: F) -> Result<T, JsonError>
+    where
+        F: FnOnce(&'a JsonValue) -> Result<T, JsonError>,
+    {
+        match self.object.get(key) {
+            Some(value) => self.check(key, convert(value)),
+            None => {
+                self.errors.push(FieldError::Missing(key.to_string()));
+                Err(JsonError::custom(format!("missing field '{}'", key)))
+            }
+        }
+    }
+
+    /// Extracts an optional field with a custom conversion. A missing key or a
+    /// `null` value both yield `None`.
+    pub fn optional_with<T, F>(&mut self, key: &str, convert: F) -> Result<Option<T>, JsonError>
+    where
+        F: FnOnce(&'a JsonValue) -> Result<T, JsonError>,
+    {
+        match self.object.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(value) => self.check(key, convert(value)).map(Some),
+        }
+    }
+
+    pub fn require<T: FromJson>(&mut self, key: &str) -> Result<T, JsonError> {
+        self.require_with(key, T::from_json)
+    }
+
+    pub fn optional<T: FromJson>(&mut self, key: &str) -> Result<Option<T>, JsonError> {
+        self.optional_with(key, T::from_json)
+    }
+
+    pub fn require_str(&mut self, key: &str) -> Result<&'a str, JsonError> {
+        self.require_with(key, as_str)
+    }
+
+    pub fn optional_str(&mut self, key: &str) -> Result<Option<&'a str>, JsonError> {
+        self.optional_with(key, as_str)
+    }
+
+    pub fn require_f64(&mut self, key: &str) -> Result<f64, JsonError> {
+        self.require(key)
+    }
+
+    pub fn optional_f64(&mut self, key: &str) -> Result<Option<f64>, JsonError> {
+        self.optional(key)
+    }
+
+    pub fn require_bool(&mut self, key: &str) -> Result<bool, JsonError> {
+        self.require(key)
+    }
+
+    pub fn optional_bool(&mut self, key: &str) -> Result<Option<bool>, JsonError> {
+        self.optional(key)
+    }
+
+    /// Reports every missing or invalid field seen so far, or `Ok` if all of
+    /// the extractions succeeded.
+    pub fn finish(self) -> Result<(), JsonError> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(JsonError::Fields(self.errors))
+        }
+    }
+
+    fn check<T>(&mut self, key: &str, result: Result<T, JsonError>) -> Result<T, JsonError> {
+        if let Err(e) = &result {
+            self.errors.push(FieldError::Invalid {
+                field: key.to_string(),
+                message: e.to_string(),
+            });
+        }
+        result
+    }
+}
+
+fn as_str(value: &JsonValue) -> Result<&str, JsonError> {
+    match value {
+        JsonValue::String(s) => Ok(s),
+        other => Err(JsonError::UnexpectedType {
+            expected: "string",
+            found: other.type_name(),
+        }),
+    }
+}
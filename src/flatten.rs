@@ -0,0 +1,243 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{JsonError, JsonValue, Map, pointer::array_index};
+
+impl JsonValue {
+    /// Flattens the tree into `(path, leaf)` pairs using `.` between path
+    /// segments, so `{"a":{"b":1},"c":[2,3]}` becomes `a.b=1`, `c.0=2`,
+    /// `c.1=3`. See `flatten_with_separator`.
+    pub fn flatten(&self) -> Vec<(String, JsonValue)> {
+        let mut pairs = Vec::new();
+        self.flatten_into(&mut String::new(), ".", &mut pairs);
+        pairs
+    }
+
+    /// Flattens the tree into `(path, leaf)` pairs, joining object keys and
+    /// array indices with `separator`. Empty arrays and objects are kept as
+    /// leaves so they are not lost, and a scalar root has the path `""`.
+    ///
+    /// A key containing `separator` or `\` has them escaped with a `\`, so
+    /// with `.` the key `a.b` becomes the segment `a\.b` and `unflatten`
+    /// still reads it back as one key. The empty key becomes the segment
+    /// `\e`, so that `{"": {"a": 1}}` flattens to `\e.a` rather than to the
+    /// same `a` as `{"a": 1}`.
+    ///
+    /// Fails on the separators `unflatten_with_separator` rejects, whose
+    /// paths could not be read back.
+    pub fn flatten_with_separator(&self, separator: &str) -> Result<Vec<(String, JsonValue)>, JsonError> {
+        check_separator(separator)?;
+        let mut pairs = Vec::new();
+        let mut path = String::new();
+        self.flatten_into(&mut path, separator, &mut pairs);
+        Ok(pairs)
+    }
+
+    /// Flattens the tree into a single-level object whose keys are the
+    /// paths from `flatten_with_separator`, failing on the same separators.
+    ///
+    /// ```
+    /// # use json_parser::Parser;
+    /// let nested = Parser::new(r#"{"a": {"b": [1, 2]}, "c.d": true}"#).parse().unwrap();
+    /// let flat = nested.flatten_to_object(".").unwrap();
+    /// assert_eq!(flat, Parser::new(r#"{"a.b.0": 1, "a.b.1": 2, "c\\.d": true}"#).parse().unwrap());
+    /// assert_eq!(flat.unflatten_object(".").unwrap(), nested);
+    /// ```
+    pub fn flatten_to_object(&self, separator: &str) -> Result<JsonValue, JsonError> {
+        Ok(JsonValue::Object(self.flatten_with_separator(separator)?.into_iter().collect()))
+    }
+
+    /// Rebuilds a nested value from `(path, value)` pairs such as those from
+    /// `flatten`. See `unflatten_with_separator`.
+    pub fn unflatten(pairs: &[(String, JsonValue)]) -> Result<JsonValue, JsonError> {
+        JsonValue::unflatten_with_separator(pairs, ".")
+    }
+
+    /// Rebuilds a nested value from `(path, value)` pairs whose paths are
+    /// split on `separator`, except where it is escaped with a `\`. A
+    /// container whose segments are exactly the indices `0` to `n - 1`
+    /// becomes an array; any other becomes an object, so `a.0` and `a.2`
+    /// without `a.1` give `{"a": {"0": ..., "2": ...}}`. An object that only
+    /// had keys like those, such as `{"0": 1}`, therefore comes back as an
+    /// array.
+    ///
+    /// Fails if the same path is given twice, if a path is used both as a
+    /// leaf and as a container, as `a` and `a.b` are, or if `separator` is
+    /// empty, contains a `\`, starts with `e` or overlaps itself, which
+    /// would make paths ambiguous. A separator overlaps itself when it ends
+    /// the way it starts, as `::` does: with it, the key `a:` followed by
+    /// `b` gives `a:::b`, which reads just as well as `a` followed by `:b`.
+    pub fn unflatten_with_separator(pairs: &[(String, JsonValue)], separator: &str) -> Result<JsonValue, JsonError> {
+        check_separator(separator)?;
+        let mut root = Node::Vacant;
+        for (path, value) in pairs {
+            root.insert(&split_path(path, separator), value, path)?;
+        }
+        Ok(root.into_value())
+    }
+
+    /// Rebuilds a nested value from a single-level object such as one from
+    /// `flatten_to_object`, treating its keys as paths as
+    /// `unflatten_with_separator` does. Fails if this is not an object.
+    pub fn unflatten_object(&self, separator: &str) -> Result<JsonValue, JsonError> {
+        let JsonValue::Object(map) = self else {
+            return Err(JsonError::UnexpectedType {
+                expected: "object",
+                found: self.type_name(),
+            });
+        };
+        let pairs: Vec<(String, JsonValue)> = map.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+        JsonValue::unflatten_with_separator(&pairs, separator)
+    }
+
+    fn flatten_into(&self, path: &mut String, separator: &str, pairs: &mut Vec<(String, JsonValue)>) {
+        match self {
+            JsonValue::Array(a) if !a.is_empty() => {
+                for (i, item) in a.iter().enumerate() {
+                    let len = push_segment(path, separator, &i.to_string());
+                    item.flatten_into(path, separator, pairs);
+                    path.truncate(len);
+                }
+            }
+            JsonValue::Object(o) if !o.is_empty() => {
+                for (key, value) in o {
+                    let len = push_segment(path, separator, key);
+                    value.flatten_into(path, separator, pairs);
+                    path.truncate(len);
+                }
+            }
+            leaf => pairs.push((path.clone(), leaf.clone())),
+        }
+    }
+}
+
+enum Node {
+    Vacant,
+    Leaf(JsonValue),
+    Branch(Map<String, Node>),
+}
+
+impl Node {
+    fn insert(&mut self, segments: &[String], value: &JsonValue, path: &str) -> Result<(), JsonError> {
+        let Some((segment, rest)) = segments.split_first() else {
+            return match self {
+                Node::Vacant => {
+                    *self = Node::Leaf(value.clone());
+                    Ok(())
+                }
+                _ => Err(conflict(path)),
+            };
+        };
+
+        if let Node::Vacant = self {
+            *self = Node::Branch(Map::new());
+        }
+        match self {
+            Node::Branch(map) => map
+                .entry(segment.clone())
+                .or_insert(Node::Vacant)
+                .insert(rest, value, path),
+            _ => Err(conflict(path)),
+        }
+    }
+
+    fn into_value(self) -> JsonValue {
+        match self {
+            Node::Vacant => JsonValue::Null,
+            Node::Leaf(value) => value,
+            Node::Branch(map) => {
+                // Distinct keys that are all indices below their count are
+                // exactly 0 to n - 1.
+                let len = map.len();
+                if map.keys().all(|key| array_index(key).is_some_and(|i| i < len)) {
+                    let mut items: Vec<(usize, Node)> =
+                        map.into_iter().map(|(key, node)| (array_index(&key).unwrap_or(0), node)).collect();
+                    items.sort_unstable_by_key(|(i, _)| *i);
+                    JsonValue::Array(items.into_iter().map(|(_, node)| node.into_value()).collect())
+                } else {
+                    JsonValue::Object(map.into_iter().map(|(k, v)| (k, v.into_value())).collect())
+                }
+            }
+        }
+    }
+}
+
+// Rejects the separators whose paths can't be split back into the segments
+// they were joined from: see `unflatten_with_separator`.
+fn check_separator(separator: &str) -> Result<(), JsonError> {
+    let overlaps = (1..separator.len())
+        .any(|len| separator.is_char_boundary(len) && separator.ends_with(&separator[..len]));
+    if separator.is_empty() || separator.contains('\\') || separator.starts_with('e') || overlaps {
+        return Err(JsonError::custom(
+            "the path separator must be non-empty, free of '\\', not start with 'e' and not overlap itself",
+        ));
+    }
+    Ok(())
+}
+
+fn conflict(path: &str) -> JsonError {
+    JsonError::custom(format!("path '{}' conflicts with another path", path))
+}
+
+// Appends a segment, escaping `\` and `separator` within it and writing the
+// empty segment as `\e`, and returns the previous length so it can be popped
+// again.
+fn push_segment(path: &mut String, separator: &str, segment: &str) -> usize {
+    let len = path.len();
+    if len > 0 {
+        path.push_str(separator);
+    }
+    if segment.is_empty() {
+        path.push_str("\\e");
+    }
+    let mut rest = segment;
+    while let Some(c) = rest.chars().next() {
+        if rest.starts_with(separator) {
+            path.push('\\');
+            path.push_str(separator);
+            rest = &rest[separator.len()..];
+        } else {
+            if c == '\\' {
+                path.push('\\');
+            }
+            path.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    len
+}
+
+// Splits a path on unescaped separators, undoing `push_segment`'s escapes,
+// with `\e` adding nothing to its segment. A `\` before anything else is kept
+// as it is. The empty path has no segments.
+fn split_path(path: &str, separator: &str) -> Vec<String> {
+    if path.is_empty() {
+        return Vec::new();
+    }
+    let mut segments = Vec::new();
+    let mut segment = String::new();
+    let mut rest = path;
+    while let Some(c) = rest.chars().next() {
+        if let Some(after) = rest.strip_prefix(separator) {
+            segments.push(core::mem::take(&mut segment));
+            rest = after;
+        } else if let Some(escaped) = rest.strip_prefix('\\') {
+            if let Some(after) = escaped.strip_prefix(separator) {
+                segment.push_str(separator);
+                rest = after;
+            } else if let Some(after) = escaped.strip_prefix('\\') {
+                segment.push('\\');
+                rest = after;
+            } else if let Some(after) = escaped.strip_prefix('e') {
+                rest = after;
+            } else {
+                segment.push('\\');
+                rest = escaped;
+            }
+        } else {
+            segment.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    segments.push(segment);
+    segments
+}
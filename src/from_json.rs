@@ -0,0 +1,300 @@
+use alloc::collections::BTreeMap;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{JsonError, JsonValue, Parser, value::MAX_SAFE_INTEGER};
+
+/// Conversion from a parsed `JsonValue` into a Rust type.
+///
+/// Implement this for your own structs by pulling fields out of the object
+/// and delegating to the implementations for their types. Conversion errors
+/// name the type that was expected and what was found instead.
+///
+/// ```
+/// use json_parser::{FromJson, JsonError, JsonValue, Parser};
+///
+/// struct Config {
+///     name: String,
+///     port: i64,
+///     verbose: Option<bool>,
+/// }
+///
+/// impl FromJson for Config {
+///     fn from_json(value: &JsonValue) -> Result<Self, JsonError> {
+///         let field = |key: &str| value.get_path(key).unwrap_or(&JsonValue::Null);
+///         Ok(Config {
+///             name: String::from_json(field("name"))?,
+///             port: i64::from_json(field("port"))?,
+///             verbose: Option::from_json(field("verbose"))?,
+///         })
+///     }
+/// }
+///
+/// let doc = Parser::new(r#"{"name": "api", "port": 8080}"#).parse().unwrap();
+/// let config = Config::from_json(&doc).unwrap();
+/// assert_eq!((config.name.as_str(), config.port, config.verbose), ("api", 8080, None));
+///
+/// let doc = Parser::new(r#"{"name": "api", "port": "8080"}"#).parse().unwrap();
+/// let error = Config::from_json(&doc).err().unwrap();
+/// assert_eq!(error.to_string(), "expected number, found string");
+/// ```
+pub trait FromJson: Sized {
+    fn from_json(value: &JsonValue) -> Result<Self, JsonError>;
+}
+
+/// Parses `input` and converts the result into `T` in one step.
+pub fn parse_into<T: FromJson>(input: &str) -> Result<T, JsonError> {
+    let value = Parser::new(input).parse()?;
+    T::from_json(&value)
+}
+
+fn unexpected(expected: &'static str, value: &JsonValue) -> JsonError {
+    JsonError::UnexpectedType {
+        expected,
+        found: value.type_name(),
+    }
+}
+
+impl FromJson for JsonValue {
+    fn from_json(value: &JsonValue) -> Result<Self, JsonError> {
+        Ok(value.clone())
+    }
+}
+
+impl FromJson for bool {
+    fn from_json(value: &JsonValue) -> Result<Self, JsonError> {
+        match value {
+            JsonValue::Boolean(b) => Ok(*b),
+            other => Err(unexpected("boolean", other)),
+        }
+    }
+}
+
+impl FromJson for f64 {
+    fn from_json(value: &JsonValue) -> Result<Self, JsonError> {
+        value.as_f64().ok_or_else(|| unexpected("number", value))
+    }
+}
+
+impl FromJson for f32 {
+    fn from_json(value: &JsonValue) -> Result<Self, JsonError> {
+        f64::from_json(value).map(|n| n as f32)
+    }
+}
+
+// Integers are only accepted when the number is whole and fits the target
+// type; nothing is truncated or saturated. A `RawNumber` of plain digits is
+// read exactly, but an f64 only up to `MAX_SAFE_INTEGER`, as the integer
+// beyond that might be any of several that round to it.
+macro_rules! impl_from_json_int {
+    ($($t:ty),*) => {
+        $(
+            impl FromJson for $t {
+                fn from_json(value: &JsonValue) -> Result<Self, JsonError> {
+                    if let JsonValue::RawNumber(s) = value
+                        && let Ok(n) = s.parse::<$t>()
+                    {
+                        return Ok(n);
+                    }
+                    let n = f64::from_json(value)?;
+                    if n % 1.0 == 0.0
+                        && n.abs() <= MAX_SAFE_INTEGER
+                        && n >= <$t>::MIN as f64
+                        && n < <$t>::MAX as f64 + 1.0
+                    {
+                        Ok(n as $t)
+                    } else {
+                        Err(JsonError::InvalidNumber {
+                            expected: stringify!($t),
+                            found: n,
+                        })
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_json_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl FromJson for String {
+    fn from_json(value: &JsonValue) -> Result<Self, JsonError> {
+        match value {
+            JsonValue::String(s) => Ok(s.clone()),
+            other => Err(unexpected("string", other)),
+        }
+    }
+}
+
+impl<T: FromJson> FromJson for Option<T> {
+    fn from_json(value: &JsonValue) -> Result<Self, JsonError> {
+        match value {
+            JsonValue::Null => Ok(None),
+            other => T::from_json(other).map(Some),
+        }
+    }
+}
+
+impl<T: FromJson> FromJson for Vec<T> {
+    fn from_json(value: &JsonValue) -> Result<Self, JsonError> {
+        match value {
+            JsonValue::Array(items) => items.iter().map(T::from_json).collect(),
+            other => Err(unexpected("array", other)),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: FromJson> FromJson for HashMap<String, T> {
+    fn from_json(value: &JsonValue) -> Result<Self, JsonError> {
+        match value {
+            JsonValue::Object(map) => map
+                .iter()
+                .map(|(k, v)| Ok((k.clone(), T::from_json(v)?)))
+                .collect(),
+            other => Err(unexpected("object", other)),
+        }
+    }
+}
+
+impl<T: FromJson> FromJson for BTreeMap<String, T> {
+    fn from_json(value: &JsonValue) -> Result<Self, JsonError> {
+        match value {
+            JsonValue::Object(map) => map
+                .iter()
+                .map(|(k, v)| Ok((k.clone(), T::from_json(v)?)))
+                .collect(),
+            other => Err(unexpected("object", other)),
+        }
+    }
+}
+
+// `TryFrom` conversions, from an owned value or a reference, for the
+// primitives. They behave exactly like `FromJson`.
+macro_rules! impl_try_from {
+    ($($t:ty),*) => {
+        $(
+            impl TryFrom<&JsonValue> for $t {
+                type Error = JsonError;
+
+                fn try_from(value: &JsonValue) -> Result<Self, JsonError> {
+                    <$t>::from_json(value)
+                }
+            }
+
+            impl TryFrom<JsonValue> for $t {
+                type Error = JsonError;
+
+                fn try_from(value: JsonValue) -> Result<Self, JsonError> {
+                    <$t>::from_json(&value)
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from!(bool, f64, i64, u64);
+
+impl TryFrom<&JsonValue> for String {
+    type Error = JsonError;
+
+    fn try_from(value: &JsonValue) -> Result<Self, JsonError> {
+        String::from_json(value)
+    }
+}
+
+impl TryFrom<JsonValue> for String {
+    type Error = JsonError;
+
+    fn try_from(value: JsonValue) -> Result<Self, JsonError> {
+        match value {
+            JsonValue::String(s) => Ok(s),
+            other => Err(unexpected("string", &other)),
+        }
+    }
+}
+
+/// Converts every element, failing at the first that does not convert with
+/// an error located at its index.
+///
+/// ```
+/// # use json_parser::Parser;
+/// let value = Parser::new(r#"[1, 2, "three"]"#).parse().unwrap();
+/// let error = Vec::<i64>::try_from(value).unwrap_err();
+/// assert_eq!(error.to_string(), "/2: expected number, found string");
+/// ```
+impl<T: TryFrom<JsonValue, Error = JsonError>> TryFrom<JsonValue> for Vec<T> {
+    type Error = JsonError;
+
+    fn try_from(value: JsonValue) -> Result<Self, JsonError> {
+        match value {
+            JsonValue::Array(items) => items
+                .into_iter()
+                .enumerate()
+                .map(|(i, item)| T::try_from(item).map_err(|e| e.nested(&i.to_string())))
+                .collect(),
+            other => Err(unexpected("array", &other)),
+        }
+    }
+}
+
+impl<'a, T: TryFrom<&'a JsonValue, Error = JsonError>> TryFrom<&'a JsonValue> for Vec<T> {
+    type Error = JsonError;
+
+    fn try_from(value: &'a JsonValue) -> Result<Self, JsonError> {
+        match value {
+            JsonValue::Array(items) => items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| T::try_from(item).map_err(|e| e.nested(&i.to_string())))
+                .collect(),
+            other => Err(unexpected("array", other)),
+        }
+    }
+}
+
+// The map conversions, for each map type, locating errors at the key of the
+// member that failed.
+macro_rules! impl_try_from_map {
+    ($($(#[$attr:meta])* $map:ident,)*) => {
+        $(
+            $(#[$attr])*
+            impl<T: TryFrom<JsonValue, Error = JsonError>> TryFrom<JsonValue> for $map<String, T> {
+                type Error = JsonError;
+
+                fn try_from(value: JsonValue) -> Result<Self, JsonError> {
+                    match value {
+                        JsonValue::Object(map) => map
+                            .into_iter()
+                            .map(|(k, v)| T::try_from(v).map_err(|e| e.nested(&k)).map(|v| (k, v)))
+                            .collect(),
+                        other => Err(unexpected("object", &other)),
+                    }
+                }
+            }
+
+            $(#[$attr])*
+            impl<'a, T: TryFrom<&'a JsonValue, Error = JsonError>> TryFrom<&'a JsonValue> for $map<String, T> {
+                type Error = JsonError;
+
+                fn try_from(value: &'a JsonValue) -> Result<Self, JsonError> {
+                    match value {
+                        JsonValue::Object(map) => map
+                            .iter()
+                            .map(|(k, v)| Ok((k.clone(), T::try_from(v).map_err(|e| e.nested(k))?)))
+                            .collect(),
+                        other => Err(unexpected("object", other)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_map! {
+    #[cfg(feature = "std")]
+    HashMap,
+    BTreeMap,
+}
@@ -0,0 +1,68 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{JsonValue, ParseError, Parser, ParserOptions, parse_bytes};
+
+/// Fuzzing entry point: feeds arbitrary bytes through each way of parsing
+/// them and panics if any of them misbehaves. Returning at all, whether the
+/// input was valid JSON or not, means the input found nothing.
+///
+/// Besides not panicking, the parse paths must agree with one another:
+/// validating succeeds exactly when parsing does, error positions lie inside
+/// the input, and a parsed value prints as JSON that parses back to itself.
+///
+/// ```
+/// json_parser::fuzz_parse(r#"{"a": [1, 2.5e3, "é"]}"#.as_bytes());
+/// json_parser::fuzz_parse(b"\xff\xfe[\0");
+/// json_parser::fuzz_parse(b"[1, {\"a\": tru");
+/// ```
+pub fn fuzz_parse(data: &[u8]) {
+    if let Ok(value) = parse_bytes(data) {
+        check_round_trip(&value);
+    }
+
+    let Ok(text) = str::from_utf8(data) else {
+        return;
+    };
+    let parsed = Parser::new(text).parse();
+    match &parsed {
+        Ok(value) => check_round_trip(value),
+        Err(e) => check_position(e, text),
+    }
+    assert_eq!(
+        Parser::new(text).validate().is_ok(),
+        parsed.is_ok(),
+        "validate and parse disagree on {:?}",
+        text
+    );
+
+    let (_, errors) = Parser::new(text).parse_tolerant();
+    for e in &errors {
+        check_position(e, text);
+    }
+    if let Err(e) = Parser::with_options(text, ParserOptions::json5()).parse() {
+        check_position(&e, text);
+    }
+
+    #[cfg(feature = "std")]
+    {
+        let from_reader = Parser::with_reader(data, ParserOptions::default()).parse();
+        match (&parsed, &from_reader) {
+            (Ok(a), Ok(b)) => assert_eq!(a, b, "reader parsed {:?} differently", text),
+            (Err(a), Err(b)) => assert_eq!(a.position, b.position, "reader placed the error in {:?} differently", text),
+            _ => panic!("reader and string parsing disagree on {:?}", text),
+        }
+    }
+}
+
+fn check_position(e: &ParseError, text: &str) {
+    assert!(e.byte_offset <= text.len() && text.is_char_boundary(e.byte_offset), "{:?} is outside {:?}", e, text);
+    assert_eq!(text[..e.byte_offset].chars().count(), e.position, "{:?} has mismatched offsets in {:?}", e, text);
+}
+
+fn check_round_trip(value: &JsonValue) {
+    let text = value.to_string();
+    match Parser::new(&text).parse() {
+        Ok(reparsed) => assert_eq!(&reparsed, value, "{} did not parse back to the same value", text),
+        Err(e) => panic!("{} does not parse back: {}", text, e),
+    }
+}
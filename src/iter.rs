@@ -0,0 +1,155 @@
+use alloc::vec;
+use core::slice;
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{JsonValue, Map};
+
+/// What consuming a `JsonValue` by iteration yields: the elements of an
+/// array or the members of an object.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Child {
+    /// An array element.
+    Element(JsonValue),
+    /// An object member, as its key and value.
+    Member(String, JsonValue),
+}
+
+impl Child {
+    /// The member's key, or `None` for an element.
+    pub fn key(&self) -> Option<&str> {
+        match self {
+            Child::Element(_) => None,
+            Child::Member(key, _) => Some(key),
+        }
+    }
+
+    /// The element or the member's value, dropping any key.
+    pub fn into_value(self) -> JsonValue {
+        match self {
+            Child::Element(value) | Child::Member(_, value) => value,
+        }
+    }
+}
+
+/// The iterator from `JsonValue::into_iter`.
+#[derive(Debug)]
+pub struct IntoChildren(Children);
+
+#[derive(Debug)]
+enum Children {
+    Elements(vec::IntoIter<JsonValue>),
+    Members(<Map<String, JsonValue> as IntoIterator>::IntoIter),
+}
+
+impl Iterator for IntoChildren {
+    type Item = Child;
+
+    fn next(&mut self) -> Option<Child> {
+        match &mut self.0 {
+            Children::Elements(items) => items.next().map(Child::Element),
+            Children::Members(members) => members.next().map(|(key, value)| Child::Member(key, value)),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.0 {
+            Children::Elements(items) => items.size_hint(),
+            Children::Members(members) => members.size_hint(),
+        }
+    }
+}
+
+/// Consumes an array into `Child::Element`s, in order, or an object into
+/// `Child::Member`s, in the map's iteration order. Any other value yields
+/// nothing. `into_elements` and `into_entries` skip the enum when the kind
+/// of container is known.
+///
+/// ```
+/// # use json_parser::{Child, Parser};
+/// let value = Parser::new(r#"{"a": 1, "b": 2}"#).parse().unwrap();
+/// let mut total = 0.0;
+/// for child in value {
+///     if let Child::Member(_, n) = child {
+///         total += n.as_f64().unwrap();
+///     }
+/// }
+/// assert_eq!(total, 3.0);
+/// ```
+impl IntoIterator for JsonValue {
+    type Item = Child;
+    type IntoIter = IntoChildren;
+
+    fn into_iter(self) -> IntoChildren {
+        IntoChildren(match self {
+            JsonValue::Object(map) => Children::Members(map.into_iter()),
+            JsonValue::Array(items) => Children::Elements(items.into_iter()),
+            _ => Children::Elements(Vec::new().into_iter()),
+        })
+    }
+}
+
+impl JsonValue {
+    /// Iterates over the elements of an array. Every other value, objects
+    /// included, yields nothing rather than panicking.
+    ///
+    /// ```
+    /// # use json_parser::Parser;
+    /// let doc = Parser::new(r#"{"scores": [9.5, "n/a", 7]}"#).parse().unwrap();
+    /// let total: f64 = doc.get_path("scores").unwrap().members().filter_map(|v| v.as_f64()).sum();
+    /// assert_eq!(total, 16.5);
+    /// ```
+    pub fn members(&self) -> slice::Iter<'_, JsonValue> {
+        match self {
+            JsonValue::Array(items) => items.iter(),
+            _ => [].iter(),
+        }
+    }
+
+    /// Like `members`, with mutable access to the elements.
+    pub fn members_mut(&mut self) -> slice::IterMut<'_, JsonValue> {
+        match self {
+            JsonValue::Array(items) => items.iter_mut(),
+            _ => [].iter_mut(),
+        }
+    }
+
+    /// Iterates over the `(key, value)` members of an object, in the map's
+    /// iteration order. Every other value yields nothing.
+    pub fn entries(&self) -> impl Iterator<Item = (&String, &JsonValue)> {
+        match self {
+            JsonValue::Object(map) => Some(map.iter()),
+            _ => None,
+        }
+        .into_iter()
+        .flatten()
+    }
+
+    /// Like `entries`, with mutable access to the values.
+    pub fn entries_mut(&mut self) -> impl Iterator<Item = (&String, &mut JsonValue)> {
+        match self {
+            JsonValue::Object(map) => Some(map.iter_mut()),
+            _ => None,
+        }
+        .into_iter()
+        .flatten()
+    }
+
+    /// Consumes an array into its elements. Every other value, objects
+    /// included, yields nothing.
+    pub fn into_elements(self) -> vec::IntoIter<JsonValue> {
+        match self {
+            JsonValue::Array(items) => items.into_iter(),
+            _ => Vec::new().into_iter(),
+        }
+    }
+
+    /// Consumes an object into its `(key, value)` members, in the map's
+    /// iteration order. Every other value yields nothing.
+    pub fn into_entries(self) -> <Map<String, JsonValue> as IntoIterator>::IntoIter {
+        match self {
+            JsonValue::Object(map) => map.into_iter(),
+            _ => Map::new().into_iter(),
+        }
+    }
+}
@@ -0,0 +1,1069 @@
+#[cfg(not(feature = "std"))]
+use core::marker::PhantomData;
+use core::ops::Range;
+#[cfg(feature = "std")]
+use std::io::{self, Read};
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+#[cfg(feature = "std")]
+use crate::Encoding;
+use crate::{
+    ParseError, ParseErrorKind, ParserOptions,
+    options::{OverflowPolicy, SurrogatePolicy},
+};
+
+// Without std there are no readers, only string input. These stand in for
+// the std names so that `Lexer` and `Parser` have the same shape either way.
+#[cfg(not(feature = "std"))]
+pub(crate) mod io {
+    pub struct Empty;
+    pub trait Read {}
+    impl Read for Empty {}
+}
+#[cfg(not(feature = "std"))]
+use io::Read;
+
+/// Size of the chunks `from_reader` reads its input in.
+#[cfg(feature = "std")]
+const READ_BUFFER_SIZE: usize = 8 * 1024;
+
+/// One token of JSON text, as produced by `Lexer`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    LeftBrace,
+    RightBrace,
+    LeftBracket,
+    RightBracket,
+    Colon,
+    Comma,
+    Null,
+    Boolean(bool),
+    Number(f64),
+    /// A number kept as its exact text, with
+    /// `ParserOptions::numbers_as_strings`.
+    RawNumber(String),
+    /// A string with its escapes decoded.
+    String(String),
+    /// A bare word that is not a literal, such as a JSON5 unquoted key. Only
+    /// produced with `ParserOptions::allow_unquoted_keys`.
+    Identifier(String),
+}
+
+/// Splits JSON text into tokens without building a tree, for tooling such
+/// as highlighters and formatters. `Parser` reads its input through a
+/// `Lexer` too, under the same options, so strings and numbers are checked
+/// and decoded exactly as they would be when parsed. Only the tokens
+/// themselves are checked, not the order they come in.
+///
+/// Iteration stops after the first error.
+///
+/// ```
+/// # use json_parser::{Lexer, Token};
+/// let mut lexer = Lexer::new(r#"{"a": [1, true]}"#);
+/// let tokens: Vec<Token> = lexer.by_ref().collect::<Result<_, _>>().unwrap();
+/// assert_eq!(tokens, [
+///     Token::LeftBrace,
+///     Token::String("a".into()),
+///     Token::Colon,
+///     Token::LeftBracket,
+///     Token::Number(1.0),
+///     Token::Comma,
+///     Token::Boolean(true),
+///     Token::RightBracket,
+///     Token::RightBrace,
+/// ]);
+/// assert_eq!(lexer.span(), 15..16);
+/// ```
+pub struct Lexer<R = io::Empty> {
+    // The part of the input currently held in memory. For a string that is
+    // all of it; for a reader, chars before the token being scanned are
+    // dropped on each refill, so `input[0]` is char number `offset`.
+    input: Vec<char>,
+    offset: usize,
+    // Earliest char the current token may still need to look back at.
+    token_start: usize,
+    #[cfg(feature = "std")]
+    reader: Option<ReadState<R>>,
+    #[cfg(not(feature = "std"))]
+    reader: PhantomData<R>,
+    position: usize,
+    byte_position: usize,
+    // Char and byte position of the last char read as part of a token, not
+    // counting whitespace between tokens. Errors at the end of the input
+    // point here. It is tracked because a reader may have dropped that char.
+    last_char: (usize, usize),
+    // Char and byte position where the last token started.
+    start: (usize, usize),
+    pub(crate) options: ParserOptions,
+    // Set by `Parser::parse_tolerant`: errors inside a string are recorded
+    // in `errors` and scanning goes on.
+    pub(crate) tolerant: bool,
+    pub(crate) errors: Vec<ParseError>,
+    // Set by `Parser::parse_partial` once it has hit an error, to make the
+    // input look exhausted so that every open container closes.
+    pub(crate) stopped: bool,
+    // Every char consumed as part of a token, for `minify`.
+    pub(crate) echo: Option<String>,
+    // Set once iteration has ended.
+    done: bool,
+}
+
+#[cfg(feature = "std")]
+struct ReadState<R> {
+    reader: R,
+    buffer: Box<[u8]>,
+    // Bytes of a UTF-8 sequence that was split between two reads.
+    partial: Vec<u8>,
+    bytes_read: usize,
+    eof: bool,
+    error: Option<ParseError>,
+}
+
+// A token as the parser sees it. Strings and raw numbers are only decoded
+// as far as `Decode` asks, so they carry a `ScannedString`.
+pub(crate) enum Lexeme {
+    LeftBrace,
+    RightBrace,
+    LeftBracket,
+    RightBracket,
+    Colon,
+    Comma,
+    Null,
+    Boolean(bool),
+    Number(f64),
+    RawNumber(ScannedString),
+    String(ScannedString),
+    Identifier(ScannedString),
+    /// A char that cannot start any token the parser expects here, left
+    /// unconsumed for the parser to report and recover from.
+    Unexpected(char),
+}
+
+pub(crate) struct ScannedString {
+    /// Byte range of the text in the input: a string's contents without the
+    /// quotes, or a number's digits.
+    pub range: Range<usize>,
+    /// The unescaped contents. Only `None` when the builder borrows strings
+    /// and the contents contained no escapes, or when nothing is built.
+    pub decoded: Option<String>,
+}
+
+// Which tokens the parser can accept next. Punctuation outside the set, and
+// anything else that cannot start an accepted token, comes back as
+// `Lexeme::Unexpected` without being consumed.
+#[derive(Clone, Copy)]
+pub(crate) enum Expect {
+    /// A value, or the closing bracket given.
+    Value(Option<char>),
+    /// An object key, or the closing brace given.
+    Key(Option<char>),
+    /// One of these punctuation chars.
+    Punctuation(&'static [char]),
+    /// Any token at all, for iterating over a `Lexer`. A bare word is a key
+    /// unless it spells a literal.
+    Any,
+}
+
+// How much of a string or number the parser needs.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum Decode {
+    /// Nothing is being built, so tokens are only checked.
+    Skip,
+    /// Strings are decoded only when they contain escapes; otherwise their
+    /// byte range is enough.
+    Lazy,
+    /// Strings are always decoded.
+    Eager,
+}
+
+impl Lexer {
+    pub fn new(input: &str) -> Self {
+        Lexer::with_options(input, ParserOptions::default())
+    }
+
+    pub fn with_options(input: &str, options: ParserOptions) -> Self {
+        Lexer::build_lexer(input.chars().collect(), options)
+    }
+
+    // Starts over on a new `input`, keeping the options and reusing the
+    // buffer the previous input was decoded into.
+    pub(crate) fn reset(&mut self, input: &str) {
+        let mut buffer = core::mem::take(&mut self.input);
+        buffer.clear();
+        buffer.extend(input.chars());
+        let options = core::mem::take(&mut self.options);
+        *self = Lexer::build_lexer(buffer, options);
+    }
+}
+
+impl<R: Read> Lexer<R> {
+    #[cfg(feature = "std")]
+    pub(crate) fn with_reader(reader: R, options: ParserOptions) -> Self {
+        let mut lexer = Lexer::build_lexer(Vec::new(), options);
+        lexer.reader = Some(ReadState {
+            reader,
+            buffer: vec![0; READ_BUFFER_SIZE].into_boxed_slice(),
+            partial: Vec::new(),
+            bytes_read: 0,
+            eof: false,
+            error: None,
+        });
+        lexer
+    }
+
+    fn build_lexer(input: Vec<char>, options: ParserOptions) -> Self {
+        Lexer {
+            input,
+            offset: 0,
+            token_start: 0,
+            reader: Default::default(),
+            position: 0,
+            byte_position: 0,
+            last_char: (0, 0),
+            start: (0, 0),
+            options,
+            tolerant: false,
+            errors: Vec::new(),
+            stopped: false,
+            echo: None,
+            done: false,
+        }
+    }
+
+    /// Byte range in the input of the token returned last, so that
+    /// `&input[lexer.span()]` is its text.
+    pub fn span(&self) -> Range<usize> {
+        self.start.1..self.byte_position
+    }
+
+    pub(crate) fn position(&self) -> usize {
+        self.position
+    }
+
+    pub(crate) fn byte_position(&self) -> usize {
+        self.byte_position
+    }
+
+    // Char and byte position where the last token started.
+    pub(crate) fn token_start(&self) -> (usize, usize) {
+        self.start
+    }
+
+    fn peek_char(&mut self) -> Option<char> {
+        self.char_at(self.position)
+    }
+
+    fn char_at(&mut self, position: usize) -> Option<char> {
+        if self.stopped {
+            return None;
+        }
+        while position - self.offset >= self.input.len() {
+            if !self.refill() {
+                return None;
+            }
+        }
+        Some(self.input[position - self.offset])
+    }
+
+    // The chars from `start` up to the current position, which must not have
+    // been dropped yet.
+    fn text_since(&self, start: usize) -> String {
+        self.input[start - self.offset..self.position - self.offset].iter().collect()
+    }
+
+    // Reads the next chunk from the reader, if there is one, dropping the
+    // chars before the current token to make room. Returns false at the end
+    // of the input; a read error or invalid UTF-8 also ends the input, and is
+    // reported by `take_read_error`.
+    #[cfg(feature = "std")]
+    fn refill(&mut self) -> bool {
+        let Some(state) = &mut self.reader else {
+            return false;
+        };
+        if state.eof {
+            return false;
+        }
+
+        let consumed = self.token_start.min(self.position) - self.offset;
+        self.input.drain(..consumed);
+        self.offset += consumed;
+
+        let end = self.offset + self.input.len();
+        let end_byte = state.bytes_read - state.partial.len();
+        let stream_error = |kind, message: String| ParseError {
+            kind,
+            message,
+            position: end,
+            byte_offset: end_byte,
+        };
+        let read = loop {
+            match state.reader.read(&mut state.buffer) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                result => break result,
+            }
+        };
+        match read {
+            Ok(0) => {
+                state.eof = true;
+                if !state.partial.is_empty() {
+                    state.error = Some(stream_error(ParseErrorKind::InvalidEncoding(Encoding::Utf8), "invalid UTF-8 in input".to_string()));
+                }
+                false
+            }
+            Ok(read) => {
+                let n = match self.options.max_input_length {
+                    Some(max) => read.min(max.saturating_sub(state.bytes_read)),
+                    None => read,
+                };
+                state.bytes_read += n;
+                state.partial.extend_from_slice(&state.buffer[..n]);
+                let valid = match str::from_utf8(&state.partial) {
+                    Ok(text) => text,
+                    Err(e) if e.error_len().is_none() => {
+                        // Only the last sequence is incomplete; keep it for
+                        // the next read.
+                        str::from_utf8(&state.partial[..e.valid_up_to()]).unwrap_or_default()
+                    }
+                    Err(e) => {
+                        let valid = str::from_utf8(&state.partial[..e.valid_up_to()]).unwrap_or_default();
+                        self.input.extend(valid.chars());
+                        state.eof = true;
+                        state.error = Some(ParseError {
+                            kind: ParseErrorKind::InvalidEncoding(Encoding::Utf8),
+                            message: "invalid UTF-8 in input".to_string(),
+                            position: end + valid.chars().count(),
+                            byte_offset: end_byte + valid.len(),
+                        });
+                        return !valid.is_empty();
+                    }
+                };
+                let used = valid.len();
+                self.input.extend(valid.chars());
+                state.partial.drain(..used);
+                if n < read {
+                    state.eof = true;
+                    let max = self.options.max_input_length.unwrap_or_default();
+                    let byte_offset = state.bytes_read - state.partial.len();
+                    state.error = Some(input_too_long(max, self.offset + self.input.len(), byte_offset));
+                }
+                true
+            }
+            Err(e) => {
+                state.eof = true;
+                state.error = Some(stream_error(ParseErrorKind::Io(e.kind()), format!("failed to read input: {}", e)));
+                false
+            }
+        }
+    }
+
+    // An I/O or encoding failure hit while refilling. Input ends at that
+    // point, so it is reported in place of whatever the parser made of the
+    // truncated input.
+    #[cfg(feature = "std")]
+    pub(crate) fn take_read_error(&mut self) -> Option<ParseError> {
+        self.reader.as_mut().and_then(|state| state.error.take())
+    }
+
+    #[cfg(feature = "std")]
+    fn has_reader(&self) -> bool {
+        self.reader.is_some()
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn refill(&mut self) -> bool {
+        false
+    }
+
+    #[cfg(not(feature = "std"))]
+    pub(crate) fn take_read_error(&mut self) -> Option<ParseError> {
+        None
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn has_reader(&self) -> bool {
+        false
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let c = self.peek_char();
+        if let Some(c) = c {
+            self.last_char = (self.position, self.byte_position);
+            self.position += 1;
+            self.byte_position += c.len_utf8();
+            if let Some(out) = &mut self.echo {
+                out.push(c);
+            }
+        }
+        c
+    }
+
+    fn consume_str(&mut self, s: &str) -> Result<(), ParseError> {
+        for expected_char in s.chars() {
+            match self.next_char() {
+                Some(c) if c == expected_char => continue,
+                Some(c) if self.options.case_insensitive_literals && c.eq_ignore_ascii_case(&expected_char) => continue,
+                Some(c) => return Err(self.error(ParseErrorKind::UnexpectedChar(c), &format!("Expected '{}', found '{}'", expected_char, c))),
+                None => return Err(self.end_of_input(ParseErrorKind::UnexpectedEnd, &format!("'{}'", s))),
+            }
+        }
+        Ok(())
+    }
+
+    // Runs before each document is scanned. At the beginning of the input it
+    // also checks the size of string input against `max_input_length`
+    // (reader input is checked as it is read), and skips a byte order mark
+    // as written by some Windows tools. Positions still count the BOM, so
+    // they match the original input; anywhere else a BOM is an unexpected
+    // character.
+    pub(crate) fn begin(&mut self) -> Result<(), ParseError> {
+        if self.position != 0 {
+            return Ok(());
+        }
+        if !self.has_reader()
+            && let Some(max) = self.options.max_input_length
+        {
+            let mut bytes = 0;
+            if let Some(position) = self.input.iter().position(|c| {
+                if bytes + c.len_utf8() > max {
+                    return true;
+                }
+                bytes += c.len_utf8();
+                false
+            }) {
+                return Err(input_too_long(max, position, bytes));
+            }
+        }
+        if self.peek_char() == Some('\u{FEFF}') {
+            self.position += 1;
+            self.byte_position += '\u{FEFF}'.len_utf8();
+        }
+        Ok(())
+    }
+
+    // Fails once a string or key being scanned, which started at char
+    // `start` and byte `start_byte`, has decoded to more than
+    // `max_string_length` bytes.
+    fn check_string_length(&self, length: usize, start: usize, start_byte: usize) -> Result<(), ParseError> {
+        match self.options.max_string_length {
+            Some(max) if length > max => Err(self.error_at(
+                ParseErrorKind::StringTooLong,
+                &format!("string is longer than the maximum of {} bytes", max),
+                start,
+                start_byte,
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        // Whatever follows is the start of a new token, so nothing before it
+        // needs to stay buffered.
+        self.token_start = self.position;
+        while let Some(c) = self.peek_char() {
+            self.token_start = self.position;
+            let is_whitespace = match c {
+                ' ' | '\t' | '\n' | '\r' => true,
+                c => self.options.allow_unicode_whitespace && c.is_whitespace(),
+            };
+            if is_whitespace {
+                self.position += 1;
+                self.byte_position += c.len_utf8();
+            } else {
+                break;
+            }
+        }
+    }
+
+    // Whether only whitespace is left.
+    pub(crate) fn at_end(&mut self) -> bool {
+        self.skip_whitespace();
+        self.peek_char().is_none()
+    }
+
+    pub(crate) fn error(&self, kind: ParseErrorKind, message: &str) -> ParseError {
+        self.error_at(kind, message, self.position, self.byte_position)
+    }
+
+    // An error at the start of the last token.
+    pub(crate) fn token_error(&self, kind: ParseErrorKind, message: &str) -> ParseError {
+        self.error_at(kind, message, self.start.0, self.start.1)
+    }
+
+    pub(crate) fn error_at(&self, kind: ParseErrorKind, message: &str, position: usize, byte_offset: usize) -> ParseError {
+        ParseError {
+            kind,
+            message: message.to_string(),
+            position,
+            byte_offset,
+        }
+    }
+
+    // An error for input that ends in the middle of `what`. It points at the
+    // last char read as part of a token rather than at the end of the input,
+    // which an editor has nothing to highlight for.
+    pub(crate) fn end_of_input(&self, kind: ParseErrorKind, what: &str) -> ParseError {
+        let (position, byte_offset) = self.last_char;
+        self.error_at(kind, &format!("unexpected end of input in {}", what), position, byte_offset)
+    }
+
+    // The error for input that ends where a value should start.
+    pub(crate) fn missing_value(&self) -> ParseError {
+        let (position, byte_offset) = self.last_char;
+        self.error_at(ParseErrorKind::UnexpectedEnd, "unexpected end of input; expected a value", position, byte_offset)
+    }
+
+    // Skips ahead to the next ',' or closing bracket at the current nesting
+    // level, for error recovery.
+    pub(crate) fn skip_to_delimiter(&mut self) {
+        let mut depth = 0usize;
+        let mut in_string = false;
+        while let Some(c) = self.peek_char() {
+            if in_string {
+                match c {
+                    '\\' => {
+                        self.next_char();
+                    }
+                    '"' => in_string = false,
+                    _ => {}
+                }
+            } else {
+                match c {
+                    '"' => in_string = true,
+                    '[' | '{' => depth += 1,
+                    ']' | '}' if depth == 0 => return,
+                    ']' | '}' => depth -= 1,
+                    ',' if depth == 0 => return,
+                    _ => {}
+                }
+            }
+            self.next_char();
+        }
+    }
+
+    // Scans the next token, one that `expect` allows, and returns it with
+    // as much of its contents decoded as `decode` asks. `None` at the end of
+    // the input.
+    pub(crate) fn next_token(&mut self, expect: Expect, decode: Decode) -> Result<Option<Lexeme>, ParseError> {
+        self.skip_whitespace();
+        let Some(c) = self.peek_char() else {
+            return Ok(None);
+        };
+        self.start = (self.position, self.byte_position);
+        let punctuation = match c {
+            '{' => Some(Lexeme::LeftBrace),
+            '}' => Some(Lexeme::RightBrace),
+            '[' => Some(Lexeme::LeftBracket),
+            ']' => Some(Lexeme::RightBracket),
+            ':' => Some(Lexeme::Colon),
+            ',' => Some(Lexeme::Comma),
+            _ => None,
+        };
+        if let Some(token) = punctuation {
+            let accepted = match expect {
+                Expect::Value(closer) => matches!(c, '[' | '{') || closer == Some(c),
+                Expect::Key(closer) => closer == Some(c),
+                Expect::Punctuation(chars) => chars.contains(&c),
+                Expect::Any => true,
+            };
+            if !accepted {
+                return Ok(Some(Lexeme::Unexpected(c)));
+            }
+            self.next_char();
+            return Ok(Some(token));
+        }
+        let word = c.is_alphabetic() || c == '_' || c == '$';
+        let token = match expect {
+            Expect::Punctuation(_) => Lexeme::Unexpected(c),
+            Expect::Key(_) => match c {
+                '"' => Lexeme::String(self.scan_string(decode)?),
+                '\'' if self.options.allow_single_quotes => Lexeme::String(self.scan_string(decode)?),
+                _ if self.options.allow_unquoted_keys && word => Lexeme::Identifier(self.scan_identifier(decode)?),
+                _ => Lexeme::Unexpected(c),
+            },
+            // Without context a bare word may be a key or a literal, so
+            // literals are picked out once it is scanned.
+            Expect::Any if self.options.allow_unquoted_keys && word => {
+                let scanned = self.scan_identifier(Decode::Eager)?;
+                let text = scanned.decoded.as_deref().unwrap_or_default();
+                let literal = ["null", "true", "false"].into_iter().find(|literal| {
+                    text == *literal || (self.options.case_insensitive_literals && text.eq_ignore_ascii_case(literal))
+                });
+                match literal {
+                    Some("null") => Lexeme::Null,
+                    Some(literal) => Lexeme::Boolean(literal == "true"),
+                    None => Lexeme::Identifier(scanned),
+                }
+            }
+            Expect::Value(_) | Expect::Any => {
+                let dispatch = if self.options.case_insensitive_literals {
+                    c.to_ascii_lowercase()
+                } else {
+                    c
+                };
+                match dispatch {
+                    'n' => self.consume_str("null").map(|()| Lexeme::Null)?,
+                    't' => self.consume_str("true").map(|()| Lexeme::Boolean(true))?,
+                    'f' => self.consume_str("false").map(|()| Lexeme::Boolean(false))?,
+                    '"' => Lexeme::String(self.scan_string(decode)?),
+                    '\'' if self.options.allow_single_quotes => Lexeme::String(self.scan_string(decode)?),
+                    '0'..='9' | '-' | '+' | '.' => self.scan_number(decode)?,
+                    _ => Lexeme::Unexpected(c),
+                }
+            }
+        };
+        Ok(Some(token))
+    }
+
+    // The error for `c` where a value should start.
+    pub(crate) fn not_a_value(&mut self, c: char) -> ParseError {
+        match self.miscased_literal() {
+            Some((found, literal)) => self.error(
+                ParseErrorKind::UnexpectedChar(c),
+                &format!("found '{}'; JSON literals are lowercase: did you mean '{}'?", found, literal),
+            ),
+            None => self.error(ParseErrorKind::UnexpectedChar(c), &format!("unexpected character: {}", c)),
+        }
+    }
+
+    // Looks ahead, without consuming anything, for a word like `True` or
+    // `NULL` that is a literal in the wrong case. Returns the word and the
+    // literal it spells.
+    fn miscased_literal(&mut self) -> Option<(String, &'static str)> {
+        let mut word = String::new();
+        // One more letter than the longest literal is enough to rule it out.
+        while word.len() <= "false".len() {
+            match self.char_at(self.position + word.len()) {
+                Some(c) if c.is_ascii_alphabetic() => word.push(c),
+                _ => break,
+            }
+        }
+        let literal = ["null", "true", "false"].into_iter().find(|literal| word.eq_ignore_ascii_case(literal))?;
+        Some((word, literal))
+    }
+
+    // Scans a string literal. With `Decode::Eager` the contents are always
+    // decoded into a String; with `Lazy` that only happens once an escape is
+    // seen, and escape-free strings are described by their byte range alone.
+    fn scan_string(&mut self, decode: Decode) -> Result<ScannedString, ParseError> {
+        let quote = self.next_char();
+        let start_char = self.position;
+        let start_byte = self.byte_position;
+        let mut result = if decode == Decode::Eager { Some(String::new()) } else { None };
+        let mut length = 0;
+        while let Some(c) = self.next_char() {
+            match c {
+                c if Some(c) == quote => {
+                    return Ok(ScannedString {
+                        range: start_byte..self.byte_position - 1,
+                        decoded: result,
+                    });
+                }
+                '\\' => {
+                    let escape_start = self.position - 1;
+                    let escape_start_byte = self.byte_position - 1;
+                    let escaped_char = self.next_char()
+                        .ok_or_else(|| self.end_of_input(ParseErrorKind::UnterminatedString, "string"))?;
+                    let decoded = match escaped_char {
+                        '"' => '"',
+                        '\'' if self.options.allow_single_quotes => '\'',
+                        '\\' => '\\',
+                        '/' => '/',
+                        'b' => '\u{0008}',
+                        'f' => '\u{000C}',
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'u' => match self.scan_unicode_escape(escape_start, escape_start_byte) {
+                            Ok(c) => c,
+                            Err(error) => {
+                                if !self.tolerant {
+                                    return Err(error);
+                                }
+                                self.errors.push(error);
+                                continue;
+                            }
+                        },
+                        _ => {
+                            let error = self.error(ParseErrorKind::InvalidEscape, &format!("invalid escape sequence: \\{}", escaped_char));
+                            if !self.tolerant {
+                                return Err(error);
+                            }
+                            self.errors.push(error);
+                            continue;
+                        }
+                    };
+                    length += decoded.len_utf8();
+                    self.check_string_length(length, start_char - 1, start_byte - 1)?;
+                    if decode != Decode::Skip {
+                        result
+                            .get_or_insert_with(|| {
+                                self.input[start_char - self.offset..escape_start - self.offset].iter().collect()
+                            })
+                            .push(decoded);
+                    }
+                }
+                c if c < '\u{20}' && !self.options.allow_control_characters => {
+                    let error = self.error_at(
+                        ParseErrorKind::ControlCharacter,
+                        &format!("unescaped control character U+{:04X} in string", c as u32),
+                        self.position - 1,
+                        self.byte_position - 1,
+                    );
+                    if !self.tolerant {
+                        return Err(error);
+                    }
+                    self.errors.push(error);
+                }
+                _ => {
+                    length += c.len_utf8();
+                    self.check_string_length(length, start_char - 1, start_byte - 1)?;
+                    if let Some(result) = &mut result {
+                        result.push(c);
+                    }
+                }
+            }
+        }
+        Err(self.end_of_input(ParseErrorKind::UnterminatedString, "string"))
+    }
+
+    // Decodes the rest of a `\uXXXX` escape whose backslash is at char
+    // `escape_start` and byte `escape_start_byte`, combining a surrogate pair written as two escapes into
+    // one char. What happens to a surrogate without its partner is up to
+    // `ParserOptions::lone_surrogates`.
+    fn scan_unicode_escape(&mut self, escape_start: usize, escape_start_byte: usize) -> Result<char, ParseError> {
+        let unit = self.scan_hex4()?;
+        match unit {
+            0xD800..=0xDBFF => match self.scan_low_surrogate() {
+                Some(low) => {
+                    let c = 0x10000 + ((unit - 0xD800) << 10) + (low - 0xDC00);
+                    Ok(char::from_u32(c).expect("surrogate pairs decode to valid chars"))
+                }
+                None => self.lone_surrogate(unit, escape_start, escape_start_byte),
+            },
+            0xDC00..=0xDFFF => self.lone_surrogate(unit, escape_start, escape_start_byte),
+            _ => Ok(char::from_u32(unit).expect("non-surrogate code units are valid chars")),
+        }
+    }
+
+    fn scan_hex4(&mut self) -> Result<u32, ParseError> {
+        let mut value = 0;
+        for _ in 0..4 {
+            match self.peek_char().and_then(|c| c.to_digit(16)) {
+                Some(digit) => {
+                    self.next_char();
+                    value = value * 16 + digit;
+                }
+                None if self.peek_char().is_none() => return Err(self.end_of_input(ParseErrorKind::UnterminatedString, "string")),
+                None => return Err(self.error(ParseErrorKind::InvalidEscape, "expected four hex digits after \\u")),
+            }
+        }
+        Ok(value)
+    }
+
+    // Consumes a following `\uXXXX` escape only if it is a low surrogate,
+    // leaving anything else to be scanned normally.
+    fn scan_low_surrogate(&mut self) -> Option<u32> {
+        if self.char_at(self.position) != Some('\\') || self.char_at(self.position + 1) != Some('u') {
+            return None;
+        }
+        let mut value = 0;
+        for i in 0..4 {
+            value = value * 16 + self.char_at(self.position + 2 + i)?.to_digit(16)?;
+        }
+        if !(0xDC00..=0xDFFF).contains(&value) {
+            return None;
+        }
+        for _ in 0..6 {
+            self.next_char();
+        }
+        Some(value)
+    }
+
+    fn lone_surrogate(&self, unit: u32, escape_start: usize, escape_start_byte: usize) -> Result<char, ParseError> {
+        match self.options.lone_surrogates {
+            SurrogatePolicy::Strict => Err(self.error_at(
+                ParseErrorKind::LoneSurrogate,
+                &format!("unpaired surrogate \\u{:04X} in string", unit),
+                escape_start,
+                escape_start_byte,
+            )),
+            SurrogatePolicy::Lossy => Ok(char::REPLACEMENT_CHARACTER),
+        }
+    }
+
+    // Scans a bare ECMAScript-style identifier key: letters, digits, `_` and
+    // `$`, where the caller has already checked the first char is not a digit.
+    fn scan_identifier(&mut self, decode: Decode) -> Result<ScannedString, ParseError> {
+        let start_char = self.position;
+        let start_byte = self.byte_position;
+        while let Some(c) = self.peek_char() {
+            if c.is_alphanumeric() || c == '_' || c == '$' {
+                self.next_char();
+                self.check_string_length(self.byte_position - start_byte, start_char, start_byte)?;
+            } else {
+                break;
+            }
+        }
+        Ok(ScannedString {
+            range: start_byte..self.byte_position,
+            decoded: (decode == Decode::Eager).then(|| self.text_since(start_char)),
+        })
+    }
+
+    fn scan_number(&mut self, decode: Decode) -> Result<Lexeme, ParseError> {
+        let start_pos = self.position;
+        let start_byte = self.byte_position;
+
+        let json5 = self.options.allow_json5_numbers;
+        let negative = self.peek_char() == Some('-');
+        let sign = self.peek_char().filter(|&c| c == '-' || c == '+');
+        if sign == Some('+') && !json5 {
+            return Err(self.error(ParseErrorKind::UnexpectedChar('+'), "leading '+' is not allowed in numbers"));
+        }
+        if sign.is_some() {
+            self.next_char();
+        }
+
+        if self.options.allow_hex_numbers
+            && self.peek_char() == Some('0')
+            && matches!(self.char_at(self.position + 1), Some('x' | 'X'))
+        {
+            return self.scan_hex_number(decode, negative, start_pos, start_byte);
+        }
+
+        let mut has_integer_digits = true;
+        // Digits before the point, not counting a lone `0`, so the number is
+        // below `10^(integer_digits + exponent)`.
+        let mut integer_digits: i64 = 0;
+        // Whether the text is also valid strict JSON, as JSON5-only forms
+        // like `+1` or `.5` cannot be written back out verbatim.
+        let mut strict_form = !matches!(self.char_at(start_pos), Some('+'));
+        match self.peek_char() {
+            Some('0') => {
+                let zero = self.position;
+                let zero_byte = self.byte_position;
+                self.next_char();
+                if self.peek_char().is_some_and(|c| c.is_ascii_digit()) {
+                    return Err(self.error_at(
+                        ParseErrorKind::InvalidNumber,
+                        "leading zeros are not allowed in numbers",
+                        zero,
+                        zero_byte,
+                    ));
+                }
+            }
+            Some(c) if c.is_ascii_digit() => {
+                while let Some(c) = self.peek_char() {
+                    if c.is_ascii_digit() {
+                        self.next_char();
+                        integer_digits += 1;
+                    } else {
+                        break;
+                    }
+                }
+            }
+            Some('.') if json5 => {
+                has_integer_digits = false;
+                strict_form = false;
+            }
+            Some('.') => return Err(self.leading_decimal_point(negative)),
+            // Any other first char was a sign.
+            None => return Err(self.end_of_input(ParseErrorKind::UnexpectedEnd, "number; a digit must follow the sign")),
+            Some(c) => {
+                let message = format!("expected a digit after '{}', found '{}'", sign.unwrap_or('-'), c);
+                return Err(self.error(ParseErrorKind::InvalidNumber, &message));
+            }
+        }
+
+        if let Some('.') = self.peek_char() {
+            self.next_char(); // consume '.'
+
+            let mut has_decimal_digits = false;
+            while let Some(c) = self.peek_char() {
+                if c.is_ascii_digit() {
+                    self.next_char();
+                    has_decimal_digits = true;
+                } else {
+                    break;
+                }
+            }
+
+            // JSON5 allows `5.` and `.5`, but not a lone `.`
+            let trailing_dot_allowed = json5 && has_integer_digits;
+            strict_form &= has_decimal_digits;
+            if !has_decimal_digits && !trailing_dot_allowed && self.peek_char().is_none() {
+                return Err(self.end_of_input(ParseErrorKind::UnexpectedEnd, "number"));
+            }
+            if !has_decimal_digits && !trailing_dot_allowed {
+                return Err(self.error(ParseErrorKind::InvalidNumber, "expected digit after decimal point"));
+            }
+        }
+
+        let mut exponent: i64 = 0;
+        if let Some('e' | 'E') = self.peek_char() {
+            self.next_char(); // consume 'e' or 'E'
+
+            let negative_exponent = self.peek_char() == Some('-');
+            if let Some('+' | '-') = self.peek_char() {
+                self.next_char();
+            }
+
+            let mut has_exp_digits = false;
+            while let Some(c) = self.peek_char() {
+                if let Some(digit) = c.to_digit(10) {
+                    self.next_char();
+                    let digit = if negative_exponent { -(digit as i64) } else { digit as i64 };
+                    exponent = exponent.saturating_mul(10).saturating_add(digit);
+                    has_exp_digits = true;
+                } else {
+                    break;
+                }
+            }
+
+            if !has_exp_digits && self.peek_char().is_none() {
+                return Err(self.end_of_input(ParseErrorKind::UnexpectedEnd, "number"));
+            }
+            if !has_exp_digits {
+                return Err(self.error(ParseErrorKind::InvalidNumber, "expected digit in exponent"));
+            }
+        }
+
+        // Validation only converts a number that could be too large for an
+        // f64; one below `10^f64::MAX_10_EXP` can't be.
+        let may_overflow = integer_digits.saturating_add(exponent) > f64::MAX_10_EXP as i64;
+        if decode == Decode::Skip && !(may_overflow && self.options.number_overflow == OverflowPolicy::Error) {
+            return Ok(Lexeme::Number(0.0));
+        }
+
+        if self.options.numbers_as_strings && strict_form {
+            return Ok(Lexeme::RawNumber(ScannedString {
+                range: start_byte..self.byte_position,
+                decoded: (decode == Decode::Eager).then(|| self.text_since(start_pos)),
+            }));
+        }
+
+        let number_str = self.text_since(start_pos);
+        match number_str.parse::<f64>() {
+            Ok(num) => Ok(Lexeme::Number(self.check_overflow(num, start_pos, start_byte)?)),
+            Err(_) => Err(self.error_at(
+                ParseErrorKind::InvalidNumber,
+                &format!("invalid number format: '{}'", number_str),
+                start_pos,
+                start_byte,
+            )),
+        }
+
+
+    }
+
+    // The error for a number like `.5` or `-.5`, at the decimal point. When
+    // digits follow, the message spells out how to write the number instead.
+    fn leading_decimal_point(&mut self, negative: bool) -> ParseError {
+        let mut digits = String::new();
+        while let Some(c) = self.char_at(self.position + 1 + digits.len()).filter(char::is_ascii_digit) {
+            digits.push(c);
+        }
+        let message = if digits.is_empty() {
+            "numbers may not start with a decimal point".to_string()
+        } else {
+            let sign = if negative { "-" } else { "" };
+            format!("numbers may not start with a decimal point (write {}0.{})", sign, digits)
+        };
+        self.error(ParseErrorKind::UnexpectedChar('.'), &message)
+    }
+
+    // Scans the `0x`-prefixed part of a JSON5 hex integer literal.
+    fn scan_hex_number(&mut self, decode: Decode, negative: bool, start: usize, start_byte: usize) -> Result<Lexeme, ParseError> {
+        self.next_char();
+        self.next_char();
+
+        let mut value = 0.0;
+        let mut has_digits = false;
+        while let Some(digit) = self.peek_char().and_then(|c| c.to_digit(16)) {
+            self.next_char();
+            value = value * 16.0 + digit as f64;
+            has_digits = true;
+        }
+
+        if !has_digits && self.peek_char().is_none() {
+            return Err(self.end_of_input(ParseErrorKind::UnexpectedEnd, "number"));
+        }
+        if !has_digits {
+            return Err(self.error(ParseErrorKind::InvalidNumber, "expected hex digit after '0x'"));
+        }
+        if decode == Decode::Skip && self.options.number_overflow != OverflowPolicy::Error {
+            return Ok(Lexeme::Number(0.0));
+        }
+        let value = self.check_overflow(value, start, start_byte)?;
+        Ok(Lexeme::Number(if negative { -value } else { value }))
+    }
+
+    // Applies `number_overflow` to a number, starting at char `start` and
+    // byte `start_byte`, that was too large for an f64 and so parsed as an
+    // infinity.
+    fn check_overflow(&self, n: f64, start: usize, start_byte: usize) -> Result<f64, ParseError> {
+        if n.is_finite() {
+            return Ok(n);
+        }
+        match self.options.number_overflow {
+            OverflowPolicy::SaturateToInfinity => Ok(n),
+            OverflowPolicy::ClampToMax => Ok(f64::MAX.copysign(n)),
+            OverflowPolicy::Error => Err(self.error_at(
+                ParseErrorKind::NumberOutOfRange,
+                "number out of range",
+                start,
+                start_byte,
+            )),
+        }
+    }
+}
+
+pub(crate) fn input_too_long(max: usize, position: usize, byte_offset: usize) -> ParseError {
+    ParseError {
+        kind: ParseErrorKind::InputTooLong,
+        message: format!("input is longer than the maximum of {} bytes", max),
+        position,
+        byte_offset,
+    }
+}
+
+impl<R: Read> Iterator for Lexer<R> {
+    type Item = Result<Token, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let token = self.begin().and_then(|()| self.next_token(Expect::Any, Decode::Eager));
+        Some(match token {
+            Ok(Some(lexeme)) => Ok(match lexeme {
+                Lexeme::LeftBrace => Token::LeftBrace,
+                Lexeme::RightBrace => Token::RightBrace,
+                Lexeme::LeftBracket => Token::LeftBracket,
+                Lexeme::RightBracket => Token::RightBracket,
+                Lexeme::Colon => Token::Colon,
+                Lexeme::Comma => Token::Comma,
+                Lexeme::Null => Token::Null,
+                Lexeme::Boolean(b) => Token::Boolean(b),
+                Lexeme::Number(n) => Token::Number(n),
+                Lexeme::RawNumber(s) => Token::RawNumber(s.decoded.unwrap_or_default()),
+                Lexeme::String(s) => Token::String(s.decoded.unwrap_or_default()),
+                Lexeme::Identifier(s) => Token::Identifier(s.decoded.unwrap_or_default()),
+                Lexeme::Unexpected(c) => {
+                    let error = self.not_a_value(c);
+                    self.done = true;
+                    return Some(Err(error));
+                }
+            }),
+            Ok(None) => {
+                self.done = true;
+                return None;
+            }
+            Err(e) => {
+                self.done = true;
+                Err(e)
+            }
+        })
+    }
+}
@@ -0,0 +1,109 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "arena")]
+mod arena;
+#[cfg(feature = "tokio")]
+mod async_reader;
+#[cfg(any(feature = "cbor", feature = "msgpack"))]
+mod binary;
+mod borrowed;
+mod builder;
+#[cfg(feature = "cbor")]
+mod cbor;
+#[cfg(feature = "std")]
+mod cache;
+mod coerce;
+mod csv;
+#[cfg(feature = "serde")]
+mod de;
+mod encoding;
+mod entry;
+mod error;
+mod extract;
+mod flatten;
+mod from_json;
+mod fuzz;
+mod iter;
+mod lexer;
+mod merge;
+#[cfg(feature = "msgpack")]
+mod msgpack;
+mod options;
+mod ordering;
+mod parser;
+mod path;
+mod pointer;
+mod schema;
+mod seq;
+mod shared;
+mod spanned;
+mod to_json;
+mod value;
+mod walk;
+mod write;
+mod yaml;
+
+#[cfg(feature = "arena")]
+pub use arena::ArenaValue;
+#[cfg(feature = "arena")]
+pub use bumpalo::Bump;
+#[cfg(feature = "tokio")]
+pub use async_reader::{parse_from_async_reader, parse_from_async_reader_with_options};
+pub use borrowed::JsonValueRef;
+pub use builder::{ArrayBuilder, ObjectBuilder};
+#[cfg(feature = "cbor")]
+pub use cbor::{CborError, from_cbor, to_cbor};
+#[cfg(feature = "serde")]
+pub use de::from_value;
+#[cfg(feature = "std")]
+pub use cache::CachingParser;
+pub use csv::{CsvError, CsvOptions};
+pub use encoding::{Encoding, detect_encoding, parse_bytes};
+pub use entry::Entry;
+pub use error::{JsonError, ParseError, ParseErrorKind, SerializeError};
+pub use extract::{FieldError, FieldExtractor};
+#[cfg(feature = "derive")]
+pub use json_parser_derive::{FromJson, ToJson};
+pub use from_json::{FromJson, parse_into};
+pub use fuzz::fuzz_parse;
+pub use iter::{Child, IntoChildren};
+pub use lexer::{Lexer, Token};
+pub use merge::{ArrayMergeStrategy, MergeOptions, NullMergePolicy};
+#[cfg(feature = "msgpack")]
+pub use msgpack::{MsgpackError, from_msgpack, to_msgpack};
+pub use ordering::TotalOrd;
+pub use options::{DuplicateKeyPolicy, EscapePolicy, ExponentFormat, Format, NonFinitePolicy, OverflowPolicy, ParserOptions, SurrogatePolicy, WriteOptions};
+pub use path::{PathError, PathSegment, parse_path};
+pub use schema::SchemaError;
+pub use seq::parse_json_seq;
+#[cfg(feature = "std")]
+pub use seq::write_json_seq;
+pub use shared::{KeyInterner, SharedJson};
+pub use spanned::{SpannedKind, SpannedMember, SpannedValue};
+pub use parser::{Parser, is_valid, minify, parse_partial, parse_stream};
+#[cfg(feature = "std")]
+pub use parser::from_reader;
+pub use to_json::ToJson;
+pub use value::{JsonValue, Map};
+pub use walk::WalkControl;
+
+// Without std, these come from `alloc` instead of the prelude.
+#[cfg(not(feature = "std"))]
+mod prelude {
+    pub(crate) use alloc::{
+        boxed::Box,
+        format,
+        string::{String, ToString},
+        vec,
+        vec::Vec,
+    };
+}
+
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+#[path = "derive_support.rs"]
+pub mod __private;
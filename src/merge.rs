@@ -0,0 +1,103 @@
+use crate::JsonValue;
+
+/// How `JsonValue::deep_merge` combines an array with another array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArrayMergeStrategy {
+    /// The other array replaces this one: `[1, 2]` merged with `[3]` is
+    /// `[3]`.
+    #[default]
+    Replace,
+    /// The other array's elements are appended: `[1, 2, 3]`.
+    Concat,
+    /// Elements at the same index are merged, and any elements past the
+    /// end of this array are appended: `[3, 2]`.
+    IndexWise,
+}
+
+/// How `JsonValue::deep_merge` treats a `null` in the value merged in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NullMergePolicy {
+    /// `null` replaces the existing value like any other value.
+    #[default]
+    Overwrite,
+    /// `null` leaves the existing value alone, and an object member whose
+    /// value is `null` is not added. Nulls inside arrays that are appended
+    /// are kept, since leaving them out would move the elements after them.
+    Ignore,
+}
+
+/// Settings for `JsonValue::deep_merge`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MergeOptions {
+    pub arrays: ArrayMergeStrategy,
+    pub nulls: NullMergePolicy,
+}
+
+impl JsonValue {
+    /// Merges `other` into this value with the default array strategy,
+    /// `Replace`; see `merge_with`.
+    pub fn merge(&mut self, other: JsonValue) {
+        self.merge_with(other, ArrayMergeStrategy::default());
+    }
+
+    /// Merges `other` into this value, combining arrays as `strategy` says
+    /// and letting `null` overwrite; see `deep_merge`.
+    pub fn merge_with(&mut self, other: JsonValue, strategy: ArrayMergeStrategy) {
+        self.deep_merge(
+            &other,
+            MergeOptions {
+                arrays: strategy,
+                ..Default::default()
+            },
+        );
+    }
+
+    /// Merges `other` into this value, as when layering configuration
+    /// files. Two objects merge member by member, recursively, and two
+    /// arrays combine as `options.arrays` says. In every other case `other`
+    /// replaces this value, except that `options.nulls` can make `null`
+    /// leave it alone.
+    ///
+    /// Unlike an RFC 7386 merge patch, `null` never deletes members.
+    ///
+    /// ```
+    /// # use json_parser::{ArrayMergeStrategy, MergeOptions, NullMergePolicy, Parser};
+    /// let mut config = Parser::new(r#"{"hosts": ["a", "b"], "retry": {"count": 3, "delay": 1}}"#).parse().unwrap();
+    /// let layer = Parser::new(r#"{"hosts": ["c"], "retry": {"count": null, "delay": 5}}"#).parse().unwrap();
+    /// let options = MergeOptions {
+    ///     arrays: ArrayMergeStrategy::Concat,
+    ///     nulls: NullMergePolicy::Ignore,
+    /// };
+    /// config.deep_merge(&layer, options);
+    /// assert_eq!(config, Parser::new(r#"{"hosts": ["a", "b", "c"], "retry": {"count": 3, "delay": 5}}"#).parse().unwrap());
+    /// ```
+    pub fn deep_merge(&mut self, other: &JsonValue, options: MergeOptions) {
+        match (self, other) {
+            (_, JsonValue::Null) if options.nulls == NullMergePolicy::Ignore => {}
+            (JsonValue::Object(target), JsonValue::Object(source)) => {
+                for (key, value) in source {
+                    match target.get_mut(key) {
+                        Some(existing) => existing.deep_merge(value, options),
+                        None if value.is_null() && options.nulls == NullMergePolicy::Ignore => {}
+                        None => {
+                            target.insert(key.clone(), value.clone());
+                        }
+                    }
+                }
+            }
+            (JsonValue::Array(target), JsonValue::Array(source)) => match options.arrays {
+                ArrayMergeStrategy::Replace => target.clone_from(source),
+                ArrayMergeStrategy::Concat => target.extend_from_slice(source),
+                ArrayMergeStrategy::IndexWise => {
+                    for (existing, value) in target.iter_mut().zip(source) {
+                        existing.deep_merge(value, options);
+                    }
+                    if source.len() > target.len() {
+                        target.extend_from_slice(&source[target.len()..]);
+                    }
+                }
+            },
+            (target, other) => target.clone_from(other),
+        }
+    }
+}
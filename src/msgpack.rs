@@ -0,0 +1,307 @@
+use core::{error::Error, fmt, str};
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{
+    JsonValue, Map,
+    binary::{Cursor, MAX_DEPTH, UnexpectedEnd},
+    value::integer_value,
+};
+
+/// Why `from_msgpack` failed. Offsets are byte offsets into the input of
+/// the item at fault.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MsgpackError {
+    /// The input ended partway through an item.
+    UnexpectedEnd,
+    /// Bytes left over after the first complete item.
+    TrailingData { offset: usize },
+    /// Bytes that are not valid MessagePack.
+    Malformed { offset: usize, message: &'static str },
+    /// Valid MessagePack with no JSON equivalent, such as binary data, an
+    /// extension type or a map key that is not a string.
+    Unsupported { offset: usize, what: &'static str },
+    /// Arrays and maps nested more than 512 deep.
+    TooDeep { offset: usize },
+}
+
+impl fmt::Display for MsgpackError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MsgpackError::UnexpectedEnd => write!(f, "unexpected end of MessagePack input"),
+            MsgpackError::TrailingData { offset } => {
+                write!(f, "unexpected data after the MessagePack item at offset {}", offset)
+            }
+            MsgpackError::Malformed { offset, message } => {
+                write!(f, "malformed MessagePack at offset {}: {}", offset, message)
+            }
+            MsgpackError::Unsupported { offset, what } => {
+                write!(f, "{} at offset {} has no JSON equivalent", what, offset)
+            }
+            MsgpackError::TooDeep { offset } => write!(f, "MessagePack nested too deeply at offset {}", offset),
+        }
+    }
+}
+
+impl Error for MsgpackError {}
+
+impl From<UnexpectedEnd> for MsgpackError {
+    fn from(_: UnexpectedEnd) -> Self {
+        MsgpackError::UnexpectedEnd
+    }
+}
+
+/// Encodes the value as MessagePack.
+///
+/// A number that is a whole number within the range of an i64 or a u64 is
+/// written as an integer, in the smallest format that holds it: a positive
+/// or negative fixint, then 8, 16, 32 or 64 bits, unsigned for positive
+/// numbers and signed for negative ones. Every other number, `-0.0`
+/// included, is a float 64. A `RawNumber` is encoded by its numeric value.
+/// Strings, arrays and maps use the smallest header for their length, and
+/// map keys are written in sorted order.
+///
+/// # Panics
+///
+/// If a string, array or object is longer than MessagePack allows, which
+/// is 2^32 - 1 bytes or items.
+///
+/// ```
+/// # use json_parser::{Parser, from_msgpack, to_msgpack};
+/// let value = Parser::new(r#"{"compact": true, "schema": 0}"#).parse().unwrap();
+/// let bytes = to_msgpack(&value);
+/// assert_eq!(bytes, b"\x82\xa7compact\xc3\xa6schema\x00");
+/// assert_eq!(from_msgpack(&bytes).unwrap(), value);
+/// ```
+pub fn to_msgpack(value: &JsonValue) -> Vec<u8> {
+    let mut out = Vec::new();
+    encode(&mut out, value);
+    out
+}
+
+fn encode(out: &mut Vec<u8>, value: &JsonValue) {
+    match value {
+        JsonValue::Null => out.push(0xc0),
+        JsonValue::Boolean(b) => out.push(if *b { 0xc3 } else { 0xc2 }),
+        JsonValue::Number(n) => encode_number(out, *n),
+        JsonValue::RawNumber(s) => match (s.parse::<u64>(), s.parse::<i64>()) {
+            (Ok(n), _) => encode_unsigned(out, n),
+            // "-0" parses as the integer 0, and is left to `encode_number`
+            // to keep its sign.
+            (_, Ok(n)) if n < 0 => encode_signed(out, n),
+            _ => encode_number(out, s.parse().unwrap_or_default()),
+        },
+        JsonValue::String(s) => encode_str(out, s),
+        JsonValue::Array(items) => {
+            write_length(out, items.len(), 0x90, [0xdc, 0xdd]);
+            for item in items {
+                encode(out, item);
+            }
+        }
+        JsonValue::Object(map) => {
+            write_length(out, map.len(), 0x80, [0xde, 0xdf]);
+            let mut members: Vec<_> = map.iter().collect();
+            members.sort_unstable_by(|a, b| a.0.cmp(b.0));
+            for (key, value) in members {
+                encode_str(out, key);
+                encode(out, value);
+            }
+        }
+    }
+}
+
+fn encode_number(out: &mut Vec<u8>, n: f64) {
+    // -0.0 stays a float so that its sign survives.
+    let integral = n % 1.0 == 0.0 && !(n == 0.0 && n.is_sign_negative());
+    if integral && (0.0..18_446_744_073_709_551_616.0).contains(&n) {
+        encode_unsigned(out, n as u64);
+    } else if integral && (-9_223_372_036_854_775_808.0..0.0).contains(&n) {
+        encode_signed(out, n as i64);
+    } else {
+        out.push(0xcb);
+        out.extend_from_slice(&n.to_be_bytes());
+    }
+}
+
+fn encode_unsigned(out: &mut Vec<u8>, n: u64) {
+    match n {
+        0..=0x7f => out.push(n as u8),
+        0x80..=0xff => out.extend_from_slice(&[0xcc, n as u8]),
+        0x100..=0xffff => {
+            out.push(0xcd);
+            out.extend_from_slice(&(n as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(0xce);
+            out.extend_from_slice(&(n as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(0xcf);
+            out.extend_from_slice(&n.to_be_bytes());
+        }
+    }
+}
+
+fn encode_signed(out: &mut Vec<u8>, n: i64) {
+    if n >= 0 {
+        return encode_unsigned(out, n as u64);
+    }
+    match n {
+        -32..=-1 => out.push(n as u8),
+        -0x80..=-33 => out.extend_from_slice(&[0xd0, n as u8]),
+        -0x8000..=-0x81 => {
+            out.push(0xd1);
+            out.extend_from_slice(&(n as i16).to_be_bytes());
+        }
+        -0x8000_0000..=-0x8001 => {
+            out.push(0xd2);
+            out.extend_from_slice(&(n as i32).to_be_bytes());
+        }
+        _ => {
+            out.push(0xd3);
+            out.extend_from_slice(&n.to_be_bytes());
+        }
+    }
+}
+
+fn encode_str(out: &mut Vec<u8>, s: &str) {
+    match s.len() {
+        len @ 0..=31 => out.push(0xa0 | len as u8),
+        len @ 32..=0xff => out.extend_from_slice(&[0xd9, len as u8]),
+        len => write_length(out, len, 0xa0, [0xda, 0xdb]),
+    }
+    out.extend_from_slice(s.as_bytes());
+}
+
+// Writes the header of a string, array or map of `len` items: the `fixed`
+// marker with the length in its low bits when it fits in four, otherwise
+// the 16 or 32 bit marker from `wide` followed by the length.
+fn write_length(out: &mut Vec<u8>, len: usize, fixed: u8, wide: [u8; 2]) {
+    let len = u32::try_from(len).expect("MessagePack lengths are at most 2^32 - 1");
+    match len {
+        0..=15 => out.push(fixed | len as u8),
+        16..=0xffff => {
+            out.push(wide[0]);
+            out.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        _ => {
+            out.push(wide[1]);
+            out.extend_from_slice(&len.to_be_bytes());
+        }
+    }
+}
+
+/// Decodes a single MessagePack item covering all of `bytes`.
+///
+/// Every integer format decodes to a `Number` when the integer is exactly
+/// an f64, as all within ±2^53 are, and otherwise to a `RawNumber` with its
+/// decimal digits, so no precision is lost: `2^53 + 1` becomes
+/// `RawNumber("9007199254740993")`. Float 32 and float 64 both become
+/// numbers. A repeated map key keeps its last value. Binary data, extension
+/// types (timestamps included) and map keys that are not strings are
+/// errors.
+///
+/// ```
+/// # use json_parser::{JsonValue, from_msgpack};
+/// // [1, -33, 9007199254740993]
+/// let value = from_msgpack(b"\x93\x01\xd0\xdf\xcf\x00\x20\x00\x00\x00\x00\x00\x01").unwrap();
+/// assert_eq!(value.to_string(), "[1, -33, 9007199254740993]");
+/// assert!(from_msgpack(b"\xc4\x01\x00").is_err()); // binary data
+/// ```
+pub fn from_msgpack(bytes: &[u8]) -> Result<JsonValue, MsgpackError> {
+    let mut decoder = Decoder { input: Cursor::new(bytes) };
+    let value = decoder.decode(0)?;
+    if !decoder.input.at_end() {
+        return Err(MsgpackError::TrailingData { offset: decoder.input.position() });
+    }
+    Ok(value)
+}
+
+struct Decoder<'a> {
+    input: Cursor<'a>,
+}
+
+impl<'a> Decoder<'a> {
+    // Reads a big-endian unsigned integer of `width` bytes.
+    fn read_uint(&mut self, width: usize) -> Result<u64, MsgpackError> {
+        Ok(self.input.take(width)?.iter().fold(0, |n, &b| n << 8 | b as u64))
+    }
+
+    fn decode(&mut self, depth: usize) -> Result<JsonValue, MsgpackError> {
+        let offset = self.input.position();
+        let [marker] = self.input.take_array()?;
+        Ok(match marker {
+            0x00..=0x7f => JsonValue::Number(marker as f64),
+            0x80..=0x8f => self.map((marker & 0x0f) as usize, offset, depth)?,
+            0x90..=0x9f => self.array((marker & 0x0f) as usize, offset, depth)?,
+            0xa0..=0xbf => self.string((marker & 0x1f) as usize, offset)?,
+            0xc0 => JsonValue::Null,
+            0xc1 => return Err(MsgpackError::Malformed { offset, message: "0xc1 is never used" }),
+            0xc2 => JsonValue::Boolean(false),
+            0xc3 => JsonValue::Boolean(true),
+            0xc4..=0xc6 => return Err(MsgpackError::Unsupported { offset, what: "binary data" }),
+            0xc7..=0xc9 | 0xd4..=0xd8 => return Err(MsgpackError::Unsupported { offset, what: "an extension type" }),
+            0xca => JsonValue::Number(f32::from_be_bytes(self.input.take_array()?) as f64),
+            0xcb => JsonValue::Number(f64::from_be_bytes(self.input.take_array()?)),
+            0xcc..=0xcf => integer_value(self.read_uint(1 << (marker - 0xcc))? as i128),
+            0xd0 => integer_value(i8::from_be_bytes(self.input.take_array()?) as i128),
+            0xd1 => integer_value(i16::from_be_bytes(self.input.take_array()?) as i128),
+            0xd2 => integer_value(i32::from_be_bytes(self.input.take_array()?) as i128),
+            0xd3 => integer_value(i64::from_be_bytes(self.input.take_array()?) as i128),
+            0xd9..=0xdb => {
+                let len = self.read_uint(1 << (marker - 0xd9))?;
+                self.string(self.length(len)?, offset)?
+            }
+            0xdc | 0xdd => {
+                let len = self.read_uint(2 << (marker - 0xdc))?;
+                self.array(self.length(len)?, offset, depth)?
+            }
+            0xde | 0xdf => {
+                let len = self.read_uint(2 << (marker - 0xde))?;
+                self.map(self.length(len)?, offset, depth)?
+            }
+            0xe0..=0xff => JsonValue::Number(marker as i8 as f64),
+        })
+    }
+
+    // Converts a length from the input, which only a 16-bit target could
+    // fail to hold, and where it would not fit in memory anyway.
+    fn length(&self, len: u64) -> Result<usize, MsgpackError> {
+        usize::try_from(len).map_err(|_| MsgpackError::UnexpectedEnd)
+    }
+
+    fn string(&mut self, len: usize, offset: usize) -> Result<JsonValue, MsgpackError> {
+        let text = str::from_utf8(self.input.take(len)?)
+            .map_err(|_| MsgpackError::Malformed { offset, message: "invalid UTF-8 in a string" })?;
+        Ok(JsonValue::String(text.to_string()))
+    }
+
+    fn array(&mut self, len: usize, offset: usize, depth: usize) -> Result<JsonValue, MsgpackError> {
+        if depth >= MAX_DEPTH {
+            return Err(MsgpackError::TooDeep { offset });
+        }
+        let mut items = Vec::with_capacity(self.input.capacity(len as u64));
+        for _ in 0..len {
+            items.push(self.decode(depth + 1)?);
+        }
+        Ok(JsonValue::Array(items))
+    }
+
+    fn map(&mut self, len: usize, offset: usize, depth: usize) -> Result<JsonValue, MsgpackError> {
+        if depth >= MAX_DEPTH {
+            return Err(MsgpackError::TooDeep { offset });
+        }
+        let mut map = Map::new();
+        for _ in 0..len {
+            let key_offset = self.input.position();
+            let JsonValue::String(key) = self.decode(depth + 1)? else {
+                return Err(MsgpackError::Unsupported {
+                    offset: key_offset,
+                    what: "a map key that is not a string",
+                });
+            };
+            map.insert(key, self.decode(depth + 1)?);
+        }
+        Ok(JsonValue::Object(map))
+    }
+}
@@ -0,0 +1,319 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+
+// How deeply arrays and objects may nest unless the options say otherwise,
+// so hostile input cannot exhaust the stack.
+pub(crate) const DEFAULT_MAX_DEPTH: usize = 512;
+
+/// Switches for accepting input that strict JSON rejects. The default value
+/// is strict, spec-conforming parsing; `ParserOptions::json5()` turns on the
+/// JSON5 extensions.
+#[derive(Debug, Clone)]
+pub struct ParserOptions {
+    /// Accept raw control characters (U+0000 to U+001F) inside strings
+    /// instead of requiring them to be escaped.
+    pub allow_control_characters: bool,
+
+    /// Accept `null`, `true` and `false` in any letter case (`TRUE`, `Null`).
+    /// This is not conforming JSON and exists only for legacy producers.
+    pub case_insensitive_literals: bool,
+
+    /// Accept `'single quoted'` strings and keys, as in JSON5. Inside them a
+    /// `"` needs no escape, and `\'` is accepted in strings of either kind.
+    pub allow_single_quotes: bool,
+
+    /// Accept bare identifier keys like `{name: "x"}`, as in JSON5. A key
+    /// may contain letters, digits, `_` and `$`, and may not start with a
+    /// digit.
+    pub allow_unquoted_keys: bool,
+
+    /// Accept JSON5 hexadecimal integers such as `0xFF` or `-0x1a`.
+    pub allow_hex_numbers: bool,
+
+    /// Accept the JSON5 number forms `+5`, `.5` and `5.`.
+    pub allow_json5_numbers: bool,
+
+    /// Keep numbers as `JsonValue::RawNumber` holding their exact text
+    /// instead of converting them to f64, so they pass through unchanged.
+    /// The grammar is still checked. Hex and other JSON5-only forms are not
+    /// valid JSON to write back, so they are still converted.
+    pub numbers_as_strings: bool,
+
+    /// Treat any Unicode whitespace, such as a no-break space or form feed,
+    /// as insignificant whitespace. Strict JSON only allows space, tab, line
+    /// feed and carriage return.
+    pub allow_unicode_whitespace: bool,
+
+    /// Reject input longer than this many bytes. Input from a reader is
+    /// rejected as soon as the limit is passed, without reading the rest.
+    pub max_input_length: Option<usize>,
+
+    /// Reject any string or key longer than this many bytes of UTF-8 once
+    /// escapes are decoded. Scanning stops as soon as the limit is passed,
+    /// so an oversized string is never fully buffered.
+    pub max_string_length: Option<usize>,
+
+    /// Reject any array with more than this many elements. The error points
+    /// at the array's opening bracket.
+    pub max_array_length: Option<usize>,
+
+    /// Reject any object with more than this many members. The error points
+    /// at the object's opening brace.
+    pub max_object_members: Option<usize>,
+
+    /// Reject documents with more than this many values in total, counting
+    /// every scalar, array and object, nested or not.
+    pub max_nodes: Option<usize>,
+
+    /// Reject arrays and objects nested more than this many deep, counting
+    /// the outermost as 1. The parser recurses once per level, so the
+    /// default of 512 keeps deeply nested input from overflowing the stack;
+    /// `None` lifts the limit for trusted input. The error points at the
+    /// bracket or brace that went too deep.
+    pub max_depth: Option<usize>,
+
+    /// What to do with a number too large for an f64, such as `1e400`.
+    /// Numbers kept as `RawNumber`s are never converted, so this does not
+    /// apply to them.
+    pub number_overflow: OverflowPolicy,
+
+    /// What to do with a `\u` escape for half of a UTF-16 surrogate pair
+    /// that is missing its other half, such as `"\udead"`.
+    pub lone_surrogates: SurrogatePolicy,
+
+    /// What to do when an object has the same key more than once.
+    pub duplicate_keys: DuplicateKeyPolicy,
+}
+
+/// Handling of a key that appears more than once in the same object, such
+/// as `{"a": 1, "a": 2}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicateKeyPolicy {
+    /// Keep the first value: `{"a": 1}`.
+    FirstWins,
+    /// Keep the last value: `{"a": 2}`.
+    #[default]
+    LastWins,
+    /// Fail with `ParseErrorKind::DuplicateKey` at the repeated key.
+    Reject,
+    /// Gather every value of the key into an array, in order:
+    /// `{"a": [1, 2]}`. A key that appears only once keeps its value as is.
+    Collect,
+}
+
+/// Handling of unpaired surrogates in `\u` escapes. Passing them through is
+/// not an option, since a Rust `String` cannot hold one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SurrogatePolicy {
+    /// Fail with an error at the escape.
+    #[default]
+    Strict,
+    /// Decode the escape as U+FFFD REPLACEMENT CHARACTER.
+    Lossy,
+}
+
+/// Handling of numbers whose magnitude is too large for an f64. Numbers too
+/// small for one are not affected; they parse as zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Fail with an error at the number, since an infinity could not be
+    /// written back out as JSON.
+    #[default]
+    Error,
+    /// Parse them as positive or negative infinity.
+    SaturateToInfinity,
+    /// Parse them as `f64::MAX` or `f64::MIN`.
+    ClampToMax,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        ParserOptions {
+            allow_control_characters: false,
+            case_insensitive_literals: false,
+            allow_single_quotes: false,
+            allow_unquoted_keys: false,
+            allow_hex_numbers: false,
+            allow_json5_numbers: false,
+            numbers_as_strings: false,
+            allow_unicode_whitespace: false,
+            max_input_length: None,
+            max_string_length: None,
+            max_array_length: None,
+            max_object_members: None,
+            max_nodes: None,
+            max_depth: Some(DEFAULT_MAX_DEPTH),
+            number_overflow: OverflowPolicy::default(),
+            lone_surrogates: SurrogatePolicy::default(),
+            duplicate_keys: DuplicateKeyPolicy::default(),
+        }
+    }
+}
+
+impl ParserOptions {
+    /// Options accepting the JSON5 extensions this parser supports: single
+    /// quoted strings, unquoted keys, hex numbers, the relaxed decimal forms
+    /// and Unicode whitespace.
+    pub fn json5() -> Self {
+        ParserOptions {
+            allow_single_quotes: true,
+            allow_unquoted_keys: true,
+            allow_hex_numbers: true,
+            allow_json5_numbers: true,
+            allow_unicode_whitespace: true,
+            ..Default::default()
+        }
+    }
+}
+
+/// Layout and number formatting for `JsonValue::to_string_with`. The default
+/// is single-line output whose numbers parse back to exactly the same f64.
+#[derive(Debug, Clone, Default)]
+pub struct WriteOptions {
+    /// Spaces per nesting level for multi-line output, or `None` to write
+    /// everything on one line.
+    pub indent: Option<usize>,
+
+    /// Text to repeat once per nesting level for multi-line output, such as
+    /// `"\t"`. Setting it turns on multi-line output and overrides `indent`.
+    pub indent_string: Option<String>,
+
+    /// Round numbers to at most this many significant digits. Output is no
+    /// longer guaranteed to round-trip once this is set.
+    pub max_significant_digits: Option<usize>,
+
+    /// When numbers are written in exponent form (`1.5e+21`).
+    pub exponent: ExponentFormat,
+
+    /// Write whole numbers as `1.0` rather than `1`, for consumers that
+    /// tell integers and floats apart by the decimal point. Numbers in
+    /// exponent form are left as they are.
+    pub integral_trailing_zero: bool,
+
+    /// What to write for NaN and the infinities, which JSON has no way to
+    /// represent.
+    pub non_finite: NonFinitePolicy,
+
+    /// Write object members sorted by key instead of in the map's iteration
+    /// order, which for a `HashMap` changes from run to run.
+    pub sort_keys: bool,
+
+    /// Leave out the spaces after commas and colons, so single-line output
+    /// has no whitespace at all. In multi-line output only the space after
+    /// colons is affected.
+    pub compact: bool,
+
+    /// Write `/` in strings and keys as `\/`, for legacy consumers that
+    /// embed JSON in HTML `<script>` tags and expect `</` never to appear.
+    pub escape_solidus: bool,
+
+    /// Characters to write as `\u` escapes beyond those JSON requires.
+    pub escape: EscapePolicy,
+}
+
+/// Which characters in strings and keys `WriteOptions` writes as `\u`
+/// escapes on top of quotes, backslashes and control characters, which are
+/// always escaped. Characters above U+FFFF are escaped as a surrogate pair.
+///
+/// ```
+/// # use json_parser::{EscapePolicy, JsonValue, WriteOptions};
+/// let options = WriteOptions {
+///     escape: EscapePolicy::Custom(|c| !c.is_ascii()),
+///     ..Default::default()
+/// };
+/// assert_eq!(JsonValue::from("é😀").to_string_with(&options), r#""\u00e9\ud83d\ude00""#);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub enum EscapePolicy {
+    /// Nothing else.
+    #[default]
+    Minimal,
+    /// U+2028 LINE SEPARATOR and U+2029 PARAGRAPH SEPARATOR, which end a
+    /// line in JavaScript engines older than ES2019, so the output can be
+    /// pasted into a script.
+    JsSafe,
+    /// Every character the function returns `true` for.
+    Custom(fn(char) -> bool),
+}
+
+impl EscapePolicy {
+    pub(crate) fn escapes(&self, c: char) -> bool {
+        match self {
+            EscapePolicy::Minimal => false,
+            EscapePolicy::JsSafe => matches!(c, '\u{2028}' | '\u{2029}'),
+            EscapePolicy::Custom(f) => f(c),
+        }
+    }
+}
+
+/// The layouts `JsonValue::serialize` can produce. Each is a preset of
+/// `WriteOptions`, which it converts into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// One line without any whitespace: `{"a":[1,2]}`.
+    Compact,
+    /// One value per line, each nesting level indented by `indent` spaces.
+    Pretty { indent: usize },
+    /// Like `Compact` with object keys sorted, so equal values always
+    /// serialize to the same text.
+    Canonical,
+}
+
+impl WriteOptions {
+    // Whether containers are written one element per line.
+    pub(crate) fn multi_line(&self) -> bool {
+        self.indent.is_some() || self.indent_string.is_some()
+    }
+}
+
+impl From<Format> for WriteOptions {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::Compact => WriteOptions {
+                compact: true,
+                ..Default::default()
+            },
+            Format::Pretty { indent } => WriteOptions {
+                indent: Some(indent),
+                ..Default::default()
+            },
+            Format::Canonical => WriteOptions {
+                compact: true,
+                sort_keys: true,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// How NaN and infinite numbers are serialized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NonFinitePolicy {
+    /// Fail with `SerializeError::NonFinite`. Methods that cannot fail, like
+    /// `to_string_with`, write `null` instead.
+    #[default]
+    Error,
+    /// Write `null`, as JavaScript's `JSON.stringify` does.
+    Null,
+    /// Write the JSON5 tokens `NaN`, `Infinity` and `-Infinity`. The output
+    /// is not valid JSON.
+    Literal,
+}
+
+/// When a number is written in exponent form.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExponentFormat {
+    /// Use exponent form for non-zero magnitudes below `small` or at least
+    /// `large`. The default, `1e-6` and `1e21`, matches JavaScript.
+    Auto { small: f64, large: f64 },
+    /// Always use exponent form, even for `0e+0`.
+    Always,
+    /// Never use exponent form; `1e300` is written out as 301 digits.
+    Never,
+}
+
+impl Default for ExponentFormat {
+    fn default() -> Self {
+        ExponentFormat::Auto { small: 1e-6, large: 1e21 }
+    }
+}
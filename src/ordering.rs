@@ -0,0 +1,149 @@
+use core::cmp::Ordering;
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::JsonValue;
+
+/// A `JsonValue` ordered by `JsonValue::total_cmp`, for sorting with
+/// `sort_unstable` or using values as `BTreeMap` keys.
+///
+/// `JsonValue` itself only has `PartialEq`, which compares numbers as
+/// `f64` does: `-0.0 == 0.0`, and NaN equals nothing, itself included. Here
+/// equality is the total order's instead, so a NaN equals a NaN with the
+/// same sign, `-0.0` does not equal `0.0`, and `==` always agrees with
+/// `cmp` as `Ord` requires.
+///
+/// ```
+/// # use json_parser::{JsonValue, TotalOrd};
+/// let mut values = vec![TotalOrd(JsonValue::Number(f64::NAN)), TotalOrd(0.0.into()), TotalOrd((-0.0).into())];
+/// values.sort_unstable();
+/// assert!(values[0].0.as_f64().is_some_and(|n| n == 0.0 && n.is_sign_negative()));
+/// assert!(values[2].0.as_f64().is_some_and(f64::is_nan));
+/// assert!(values[2] == TotalOrd(JsonValue::Number(f64::NAN)));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TotalOrd(pub JsonValue);
+
+impl From<JsonValue> for TotalOrd {
+    fn from(value: JsonValue) -> Self {
+        TotalOrd(value)
+    }
+}
+
+impl PartialEq for TotalOrd {
+    fn eq(&self, other: &TotalOrd) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for TotalOrd {}
+
+impl Ord for TotalOrd {
+    fn cmp(&self, other: &TotalOrd) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl PartialOrd for TotalOrd {
+    fn partial_cmp(&self, other: &TotalOrd) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl JsonValue {
+    /// A total order over all values, for sorting mixed arrays
+    /// deterministically.
+    ///
+    /// Values of different types are ordered by type: null < booleans <
+    /// numbers < strings < arrays < objects. Within a type, `false` < `true`;
+    /// numbers compare with `f64::total_cmp`, so `-0.0` sorts before `0.0`
+    /// and NaN after every other number (a NaN with the sign bit set, before
+    /// them); a `RawNumber` compares by its value, sorting after an equal
+    /// `Number` and by its text among equal `RawNumber`s; strings compare
+    /// lexicographically by code point; arrays compare element by element, a
+    /// prefix sorting first; and objects compare as their lists of (key,
+    /// value) pairs sorted by key. `TotalOrd` wraps a value in this order.
+    ///
+    /// ```
+    /// # use json_parser::{JsonValue, Parser};
+    /// let mut items = match Parser::new(r#"["b", 2, null, "a", true, 1]"#).parse().unwrap() {
+    ///     JsonValue::Array(items) => items,
+    ///     _ => unreachable!(),
+    /// };
+    /// items.sort_by(JsonValue::total_cmp);
+    /// assert_eq!(JsonValue::Array(items).to_string(), r#"[null, true, 1, 2, "a", "b"]"#);
+    /// ```
+    pub fn total_cmp(&self, other: &JsonValue) -> Ordering {
+        match (self, other) {
+            (JsonValue::Boolean(a), JsonValue::Boolean(b)) => a.cmp(b),
+            (JsonValue::Number(a), JsonValue::Number(b)) => a.total_cmp(b),
+            (JsonValue::Number(_) | JsonValue::RawNumber(_), JsonValue::Number(_) | JsonValue::RawNumber(_)) => {
+                number_cmp(self, other)
+            }
+            (JsonValue::String(a), JsonValue::String(b)) => a.cmp(b),
+            (JsonValue::Array(a), JsonValue::Array(b)) => a
+                .iter()
+                .zip(b)
+                .map(|(x, y)| x.total_cmp(y))
+                .find(|o| o.is_ne())
+                .unwrap_or(a.len().cmp(&b.len())),
+            (JsonValue::Object(a), JsonValue::Object(b)) => {
+                let mut a: Vec<_> = a.iter().collect();
+                let mut b: Vec<_> = b.iter().collect();
+                a.sort_by(|x, y| x.0.cmp(y.0));
+                b.sort_by(|x, y| x.0.cmp(y.0));
+                a.iter()
+                    .zip(&b)
+                    .map(|((ka, va), (kb, vb))| ka.cmp(kb).then_with(|| va.total_cmp(vb)))
+                    .find(|o| o.is_ne())
+                    .unwrap_or(a.len().cmp(&b.len()))
+            }
+            _ => self.type_rank().cmp(&other.type_rank()),
+        }
+    }
+
+    /// Compares two values by the nodes at JSON Pointer `pointer` in each,
+    /// using `total_cmp`. A value missing the node sorts before one that has
+    /// it. Handy for sorting an array of objects by a field:
+    ///
+    /// ```
+    /// # use json_parser::{JsonValue, Parser};
+    /// let mut users = match Parser::new(r#"[{"age": 40}, {"age": 25}, {}]"#).parse().unwrap() {
+    ///     JsonValue::Array(users) => users,
+    ///     _ => unreachable!(),
+    /// };
+    /// users.sort_by(|a, b| a.compare_by_pointer(b, "/age"));
+    /// assert_eq!(JsonValue::Array(users).to_string(), r#"[{}, {"age": 25}, {"age": 40}]"#);
+    /// ```
+    pub fn compare_by_pointer(&self, other: &JsonValue, pointer: &str) -> Ordering {
+        match (self.pointer(pointer), other.pointer(pointer)) {
+            (Some(a), Some(b)) => a.total_cmp(b),
+            (a, b) => a.is_some().cmp(&b.is_some()),
+        }
+    }
+
+    fn type_rank(&self) -> u8 {
+        match self {
+            JsonValue::Null => 0,
+            JsonValue::Boolean(_) => 1,
+            JsonValue::Number(_) | JsonValue::RawNumber(_) => 2,
+            JsonValue::String(_) => 3,
+            JsonValue::Array(_) => 4,
+            JsonValue::Object(_) => 5,
+        }
+    }
+}
+
+// Orders a Number and a RawNumber, or two RawNumbers, by value, breaking ties
+// so that values that are not `==` never compare equal.
+fn number_cmp(a: &JsonValue, b: &JsonValue) -> Ordering {
+    let value = |v: &JsonValue| v.as_f64().unwrap_or(f64::NAN);
+    value(a).total_cmp(&value(b)).then_with(|| raw_text(a).cmp(&raw_text(b)))
+}
+
+fn raw_text(value: &JsonValue) -> Option<&str> {
+    match value {
+        JsonValue::RawNumber(s) => Some(s),
+        _ => None,
+    }
+}
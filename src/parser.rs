@@ -0,0 +1,697 @@
+use alloc::collections::BTreeSet;
+use core::{borrow::Borrow, hash::Hash, ops::Range};
+#[cfg(feature = "std")]
+use std::io::{self, Read};
+
+#[cfg(not(feature = "std"))]
+use crate::lexer::io::{self, Read};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{
+    JsonValue, Lexer, Map, ParseError, ParseErrorKind, ParserOptions,
+    lexer::{Decode, Expect, Lexeme, ScannedString},
+    options::DuplicateKeyPolicy,
+};
+
+// The grammar is implemented once; a `Build` impl decides what kind of tree
+// the parsed values are assembled into.
+pub(crate) trait Build {
+    type Value;
+    type Array;
+    type Object;
+
+    /// Whether escape-free strings should be handed over as a byte range
+    /// instead of being copied into a `String`.
+    const BORROWS_STRINGS: bool;
+
+    fn null(&mut self) -> Self::Value;
+    fn boolean(&mut self, b: bool) -> Self::Value;
+    fn number(&mut self, n: f64) -> Self::Value;
+    fn raw_number(&mut self, text: ScannedString) -> Self::Value;
+    fn string(&mut self, s: ScannedString) -> Self::Value;
+    fn new_array(&mut self) -> Self::Array;
+    fn push(&mut self, array: &mut Self::Array, value: Self::Value);
+    fn array(&mut self, items: Self::Array) -> Self::Value;
+    fn new_object(&mut self) -> Self::Object;
+    /// Adds a member, combined with any earlier member of the same key as
+    /// `policy` says; see `insert_member`. Returns the key as an error for a
+    /// duplicate that `policy` rejects.
+    fn insert(
+        &mut self,
+        object: &mut Self::Object,
+        key: ScannedString,
+        value: Self::Value,
+        policy: DuplicateKeyPolicy,
+        collected: &mut Vec<String>,
+    ) -> Result<(), String>;
+    fn object(&mut self, object: Self::Object) -> Self::Value;
+
+    /// Called with every complete value and the byte range it was parsed
+    /// from. Only builders that record source positions need it.
+    fn spanned(&mut self, value: Self::Value, _span: Range<usize>) -> Self::Value {
+        value
+    }
+    /// Called with the byte range of an object key, quotes included, just
+    /// before its member is inserted.
+    fn key_span(&mut self, _span: Range<usize>) {}
+}
+
+struct Owned;
+
+impl Build for Owned {
+    type Value = JsonValue;
+    type Array = Vec<JsonValue>;
+    type Object = Map<String, JsonValue>;
+
+    const BORROWS_STRINGS: bool = false;
+
+    fn null(&mut self) -> JsonValue {
+        JsonValue::Null
+    }
+
+    fn boolean(&mut self, b: bool) -> JsonValue {
+        JsonValue::Boolean(b)
+    }
+
+    fn number(&mut self, n: f64) -> JsonValue {
+        JsonValue::Number(n)
+    }
+
+    fn raw_number(&mut self, text: ScannedString) -> JsonValue {
+        JsonValue::RawNumber(text.decoded.unwrap_or_default())
+    }
+
+    fn string(&mut self, s: ScannedString) -> JsonValue {
+        JsonValue::String(s.decoded.unwrap_or_default())
+    }
+
+    fn new_array(&mut self) -> Self::Array {
+        Vec::new()
+    }
+
+    fn push(&mut self, array: &mut Self::Array, value: JsonValue) {
+        array.push(value);
+    }
+
+    fn array(&mut self, items: Self::Array) -> JsonValue {
+        JsonValue::Array(items)
+    }
+
+    fn new_object(&mut self) -> Self::Object {
+        Map::new()
+    }
+
+    fn insert(
+        &mut self,
+        object: &mut Self::Object,
+        key: ScannedString,
+        value: JsonValue,
+        policy: DuplicateKeyPolicy,
+        collected: &mut Vec<String>,
+    ) -> Result<(), String> {
+        insert_member(object, key.decoded.unwrap_or_default(), value, policy, collected, |existing, value, first| {
+            if first {
+                *existing = JsonValue::Array(vec![existing.take()]);
+            }
+            if let JsonValue::Array(items) = existing {
+                items.push(value);
+            }
+        })
+    }
+
+    fn object(&mut self, object: Self::Object) -> JsonValue {
+        JsonValue::Object(object)
+    }
+}
+
+// Adds `key: value` to `object`, combining it with an earlier member of the
+// same key as `policy` says. `collected` holds the keys of this object that
+// `Collect` has already turned into arrays, so an array value is told apart
+// from one made of repeats; `gather` appends a repeat, first wrapping the
+// existing value in an array if `first` is set.
+pub(crate) fn insert_member<K: Borrow<str> + Hash + Ord, V>(
+    object: &mut Map<K, V>,
+    key: K,
+    value: V,
+    policy: DuplicateKeyPolicy,
+    collected: &mut Vec<String>,
+    gather: impl FnOnce(&mut V, V, bool),
+) -> Result<(), String> {
+    let Some(existing) = object.get_mut(key.borrow()) else {
+        object.insert(key, value);
+        return Ok(());
+    };
+    match policy {
+        DuplicateKeyPolicy::FirstWins => {}
+        DuplicateKeyPolicy::LastWins => *existing = value,
+        DuplicateKeyPolicy::Reject => return Err(key.borrow().to_string()),
+        DuplicateKeyPolicy::Collect => {
+            let first = !collected.iter().any(|k| k.as_str() == key.borrow());
+            if first {
+                collected.push(key.borrow().to_string());
+            }
+            gather(existing, value, first);
+        }
+    }
+    Ok(())
+}
+
+/// A JSON parser over a string, or over an `io::Read` source when `R` is set
+/// (see `with_reader`). It pulls tokens from a `Lexer` and assembles them
+/// into values by recursive descent.
+pub struct Parser<R = io::Empty> {
+    lexer: Lexer<R>,
+    // Set by `parse_partial`: the first error stops parsing, and stops the
+    // lexer so that every open container closes.
+    partial: bool,
+    build: bool,
+    // Values parsed so far in the current document, for `max_nodes`.
+    nodes: usize,
+    // Arrays and objects open around the current position, for `max_depth`.
+    depth: usize,
+}
+
+impl Parser {
+    pub fn new(input: &str) -> Self {
+        Parser::with_options(input, ParserOptions::default())
+    }
+
+    pub fn with_options(input: &str, options: ParserOptions) -> Self {
+        Parser::build_parser(Lexer::with_options(input, options))
+    }
+
+    /// Starts over on a new `input`, keeping the options. The buffer the
+    /// previous input was decoded into is reused, so a long-lived parser
+    /// only allocates when an input is larger than any before it.
+    ///
+    /// ```
+    /// # use json_parser::Parser;
+    /// let mut parser = Parser::new("[1, 2]");
+    /// assert!(parser.parse().is_ok());
+    /// parser.reset("{\"a\": true}");
+    /// assert_eq!(parser.parse().unwrap().to_string(), r#"{"a": true}"#);
+    /// ```
+    pub fn reset(&mut self, input: &str) {
+        self.lexer.reset(input);
+        self.partial = false;
+        self.build = true;
+        self.nodes = 0;
+    }
+}
+
+impl<R: Read> Parser<R> {
+    /// Creates a parser that pulls its input from `reader` in fixed-size
+    /// chunks as it goes, so memory use is bounded by the largest single
+    /// token rather than by the size of the document. Positions are counted
+    /// from the start of the stream.
+    #[cfg(feature = "std")]
+    pub fn with_reader(reader: R, options: ParserOptions) -> Self {
+        Parser::build_parser(Lexer::with_reader(reader, options))
+    }
+
+    fn build_parser(lexer: Lexer<R>) -> Self {
+        Parser {
+            lexer,
+            partial: false,
+            build: true,
+            nodes: 0,
+            depth: 0,
+        }
+    }
+
+    /// The current position as a char index into the input. After
+    /// `parse_one` this is just past the end of the parsed value.
+    pub fn position(&self) -> usize {
+        self.lexer.position()
+    }
+
+    /// The current position as a byte offset into the input string.
+    pub fn byte_position(&self) -> usize {
+        self.lexer.byte_position()
+    }
+
+    // Runs before each document is parsed.
+    fn begin(&mut self) -> Result<(), ParseError> {
+        self.nodes = 0;
+        self.depth = 0;
+        self.lexer.begin()
+    }
+
+    // How much of each string and number the lexer has to decode for `B`.
+    fn decode<B: Build>(&self) -> Decode {
+        if !self.build {
+            Decode::Skip
+        } else if B::BORROWS_STRINGS {
+            Decode::Lazy
+        } else {
+            Decode::Eager
+        }
+    }
+
+    // In tolerant mode the error is recorded and parsing skips ahead to the next
+    // ',' or closing bracket at the current nesting level; otherwise it is returned.
+    fn recover(&mut self, error: ParseError) -> Result<(), ParseError> {
+        if self.partial {
+            if !self.lexer.stopped {
+                self.lexer.errors.push(error);
+                self.lexer.stopped = true;
+            }
+            return Ok(());
+        }
+        // Past the node cap every further value would fail again, so stop.
+        let node_cap_hit = self.lexer.options.max_nodes.is_some_and(|max| self.nodes > max);
+        if !self.lexer.tolerant || node_cap_hit {
+            return Err(error);
+        }
+        self.lexer.errors.push(error);
+        self.lexer.skip_to_delimiter();
+        Ok(())
+    }
+
+    /// Parses the whole input, collecting every error instead of stopping at the
+    /// first one. Broken values are replaced with `Null` so the returned value is
+    /// a best-effort view of the document.
+    pub fn parse_tolerant(&mut self) -> (JsonValue, Vec<ParseError>) {
+        self.lexer.tolerant = true;
+        self.lexer.errors.clear();
+
+        if let Err(e) = self.begin() {
+            self.lexer.tolerant = false;
+            return (JsonValue::Null, vec![e]);
+        }
+        let result = match self.parse_value(&mut Owned) {
+            Ok(value) => value,
+            Err(e) => {
+                self.lexer.errors.push(e);
+                JsonValue::Null
+            }
+        };
+        if !self.lexer.at_end() {
+            let error = self.lexer.error(ParseErrorKind::TrailingData, "unexpected trailing characters");
+            self.lexer.errors.push(error);
+        }
+
+        if let Some(e) = self.lexer.take_read_error() {
+            self.lexer.errors.push(e);
+        }
+
+        self.lexer.tolerant = false;
+        (result, core::mem::take(&mut self.lexer.errors))
+    }
+
+    /// Parses until the first error and returns the value built up to that
+    /// point along with the error, or `None` if the whole input parsed. Open
+    /// arrays and objects are closed where parsing stopped, keeping every
+    /// element and member that was complete; the one that failed is left
+    /// out. If no value could be started at all the result is `Null`.
+    pub fn parse_partial(&mut self) -> (JsonValue, Option<ParseError>) {
+        self.partial = true;
+        self.lexer.errors.clear();
+
+        let result = match self.begin() {
+            Ok(()) => match self.parse_value(&mut Owned) {
+                Ok(value) => value,
+                Err(e) => {
+                    self.lexer.errors.push(e);
+                    JsonValue::Null
+                }
+            },
+            Err(e) => {
+                self.lexer.errors.push(e);
+                JsonValue::Null
+            }
+        };
+        if !self.lexer.at_end() {
+            let error = self.lexer.error(ParseErrorKind::TrailingData, "unexpected trailing characters");
+            self.lexer.errors.push(error);
+        }
+
+        // A failed read is what really ended the input, so it wins over the
+        // end-of-input error it caused.
+        let error = self.lexer.take_read_error().or_else(|| self.lexer.errors.pop());
+        self.partial = false;
+        self.lexer.stopped = false;
+        self.lexer.errors.clear();
+        (result, error)
+    }
+
+    /// Runs the same grammar checks as `parse` without building a value tree,
+    /// so no strings, arrays or maps are allocated for the document.
+    pub fn validate(&mut self) -> Result<(), ParseError> {
+        self.build = false;
+        let result = self.parse();
+        self.build = true;
+        result.map(|_| ())
+    }
+
+    /// Parses one value starting at the current position and stops right
+    /// after it, leaving any following input unread.
+    pub fn parse_one(&mut self) -> Result<JsonValue, ParseError> {
+        self.begin()?;
+        let result = self.parse_value(&mut Owned);
+        self.lexer.take_read_error().map_or(result, Err)
+    }
+
+    pub fn parse(&mut self) -> Result<JsonValue, ParseError> {
+        self.parse_with(&mut Owned)
+    }
+
+    pub(crate) fn parse_with<B: Build>(&mut self, builder: &mut B) -> Result<B::Value, ParseError> {
+        let result = self.parse_document(builder);
+        self.lexer.take_read_error().map_or(result, Err)
+    }
+
+    fn parse_document<B: Build>(&mut self, builder: &mut B) -> Result<B::Value, ParseError> {
+        self.begin()?;
+        let result = self.parse_value(builder)?;
+        if !self.lexer.at_end() {
+            return Err(self.lexer.error(ParseErrorKind::TrailingData, "unexpected trailing characters"));
+        }
+        Ok(result)
+    }
+
+    fn parse_value<B: Build>(&mut self, builder: &mut B) -> Result<B::Value, ParseError> {
+        let token = self.lexer.next_token(Expect::Value(None), self.decode::<B>())?;
+        self.value_from(builder, token)
+    }
+
+    // Parses the value that `token`, just taken from the lexer, starts.
+    fn value_from<B: Build>(&mut self, builder: &mut B, token: Option<Lexeme>) -> Result<B::Value, ParseError> {
+        let Some(token) = token else {
+            return Err(self.lexer.missing_value());
+        };
+        let (start, start_byte) = self.lexer.token_start();
+        self.nodes += 1;
+        if let Some(max) = self.lexer.options.max_nodes
+            && self.nodes > max
+        {
+            return Err(self.lexer.token_error(
+                ParseErrorKind::TooManyNodes,
+                &format!("document has more than the maximum of {} values", max),
+            ));
+        }
+        let value = match token {
+            Lexeme::Null => builder.null(),
+            Lexeme::Boolean(b) => builder.boolean(b),
+            Lexeme::Number(n) => builder.number(n),
+            Lexeme::RawNumber(text) => builder.raw_number(text),
+            Lexeme::String(s) => builder.string(s),
+            Lexeme::LeftBracket | Lexeme::LeftBrace => {
+                if let Some(max) = self.lexer.options.max_depth
+                    && self.depth >= max
+                {
+                    return Err(self.lexer.error_at(
+                        ParseErrorKind::DepthExceeded,
+                        &format!("arrays and objects are nested more than the maximum of {} deep", max),
+                        start,
+                        start_byte,
+                    ));
+                }
+                // Every builder's arrays and objects are parsed here, so this
+                // is the one place the nesting has to be counted.
+                self.depth += 1;
+                let value = match token {
+                    Lexeme::LeftBracket => self.parse_array(builder, start, start_byte),
+                    _ => self.parse_object(builder, start, start_byte),
+                };
+                self.depth -= 1;
+                value?
+            }
+            Lexeme::Unexpected(c) => return Err(self.lexer.not_a_value(c)),
+            _ => unreachable!("the lexer only returns tokens that start a value here"),
+        };
+        Ok(builder.spanned(value, start_byte..self.lexer.byte_position()))
+    }
+
+    // Parses the rest of an array whose `[` was at char `start` and byte
+    // `start_byte`.
+    fn parse_array<B: Build>(&mut self, builder: &mut B, start: usize, start_byte: usize) -> Result<B::Value, ParseError> {
+        let decode = self.decode::<B>();
+        let mut elements = builder.new_array();
+        let mut count = 0;
+
+        let mut next = self.lexer.next_token(Expect::Value(Some(']')), decode);
+        if let Ok(Some(Lexeme::RightBracket)) = next {
+            return Ok(builder.array(elements));
+        }
+
+        loop {
+            let value = match next {
+                Ok(None) => {
+                    self.recover(self.lexer.end_of_input(ParseErrorKind::UnterminatedArray, "array"))?;
+                    break;
+                }
+                Ok(token) => self.value_from(builder, token),
+                Err(e) => Err(e),
+            };
+            let value = match value {
+                Ok(value) => value,
+                Err(e) => {
+                    self.recover(e)?;
+                    if self.lexer.stopped {
+                        break;
+                    }
+                    builder.null()
+                }
+            };
+            if self.build {
+                builder.push(&mut elements, value);
+            }
+            count += 1;
+            if let Some(max) = self.lexer.options.max_array_length
+                && count > max
+            {
+                return Err(self.lexer.error_at(
+                    ParseErrorKind::TooManyElements,
+                    &format!("array has more than the maximum of {} elements", max),
+                    start,
+                    start_byte,
+                ));
+            }
+
+            match self.lexer.next_token(Expect::Punctuation(&[',', ']']), decode)? {
+                Some(Lexeme::Comma) => {
+                    next = self.lexer.next_token(Expect::Value(Some(']')), decode);
+                    if let Ok(Some(Lexeme::RightBracket)) = next {
+                        self.recover(self.lexer.token_error(ParseErrorKind::TrailingComma, "unexptected trailing comma in array"))?;
+                        break;
+                    }
+                }
+                Some(Lexeme::RightBracket) => break,
+                Some(Lexeme::Unexpected(c)) => {
+                    self.recover(self.lexer.error(ParseErrorKind::UnexpectedChar(c), &format!("expected ',' or ']' in array, found '{}'", c)))?;
+                    match self.lexer.next_token(Expect::Punctuation(&[',', ']']), decode)? {
+                        Some(Lexeme::Comma) => next = self.lexer.next_token(Expect::Value(None), decode),
+                        _ => break,
+                    }
+                }
+                _ => {
+                    self.recover(self.lexer.end_of_input(ParseErrorKind::UnterminatedArray, "array"))?;
+                    break;
+                }
+            }
+        }
+
+        Ok(builder.array(elements))
+    }
+
+    // Parses the rest of an object whose `{` was at char `start` and byte
+    // `start_byte`.
+    fn parse_object<B: Build>(&mut self, builder: &mut B, start: usize, start_byte: usize) -> Result<B::Value, ParseError> {
+        // `validate` builds nothing, but to reject repeated keys as `parse`
+        // does it still decodes the keys, into `seen`.
+        let check_keys = !self.build && self.lexer.options.duplicate_keys == DuplicateKeyPolicy::Reject;
+        let key_decode = if check_keys { Decode::Eager } else { self.decode::<B>() };
+        let mut object = builder.new_object();
+        let mut collected = Vec::new();
+        let mut seen = BTreeSet::new();
+        let mut count = 0;
+
+        let mut next = self.lexer.next_token(Expect::Key(Some('}')), key_decode);
+        if let Ok(Some(Lexeme::RightBrace)) = next {
+            return Ok(builder.object(object));
+        }
+
+        loop {
+            match next {
+                Ok(None) => {
+                    self.recover(self.lexer.end_of_input(ParseErrorKind::UnterminatedObject, "object"))?;
+                    break;
+                }
+                Ok(Some(key)) => {
+                    let seen = check_keys.then_some(&mut seen);
+                    if let Err(e) = self.parse_member(builder, key, &mut object, &mut collected, seen) {
+                        self.recover(e)?;
+                    }
+                }
+                Err(e) => self.recover(e)?,
+            }
+            count += 1;
+            if let Some(max) = self.lexer.options.max_object_members
+                && count > max
+            {
+                return Err(self.lexer.error_at(
+                    ParseErrorKind::TooManyMembers,
+                    &format!("object has more than the maximum of {} members", max),
+                    start,
+                    start_byte,
+                ));
+            }
+
+            match self.lexer.next_token(Expect::Punctuation(&[',', '}']), key_decode)? {
+                Some(Lexeme::Comma) => {
+                    next = self.lexer.next_token(Expect::Key(Some('}')), key_decode);
+                    if let Ok(Some(Lexeme::RightBrace)) = next {
+                        self.recover(self.lexer.token_error(ParseErrorKind::TrailingComma, "unexpoected trailing comma in object"))?;
+                        break;
+                    }
+                }
+                Some(Lexeme::RightBrace) => break,
+                Some(Lexeme::Unexpected(c)) => {
+                    self.recover(self.lexer.error(ParseErrorKind::UnexpectedChar(c), &format!("expected ',' oor '}}' in object, found '{}'", c)))?;
+                    match self.lexer.next_token(Expect::Punctuation(&[',', '}']), key_decode)? {
+                        Some(Lexeme::Comma) => next = self.lexer.next_token(Expect::Key(None), key_decode),
+                        _ => break,
+                    }
+                }
+                _ => {
+                    self.recover(self.lexer.end_of_input(ParseErrorKind::UnterminatedObject, "object"))?;
+                    break;
+                }
+            }
+        }
+
+        Ok(builder.object(object))
+    }
+
+    // Parses the rest of a member whose first token is `key`. `seen` holds
+    // the keys so far when `validate` has to reject repeats itself.
+    fn parse_member<B: Build>(
+        &mut self,
+        builder: &mut B,
+        key: Lexeme,
+        object: &mut B::Object,
+        collected: &mut Vec<String>,
+        seen: Option<&mut BTreeSet<String>>,
+    ) -> Result<(), ParseError> {
+        let (Lexeme::String(key) | Lexeme::Identifier(key)) = key else {
+            return Err(self.lexer.error(ParseErrorKind::InvalidKey, "object keys must be strings"));
+        };
+        let (key_position, key_byte) = self.lexer.token_start();
+        let key_end = self.lexer.byte_position();
+
+        match self.lexer.next_token(Expect::Punctuation(&[':']), Decode::Skip)? {
+            Some(Lexeme::Unexpected(c)) => {
+                return Err(self.lexer.error(ParseErrorKind::UnexpectedChar(c), &format!("expected ':' after object key, found '{}'", c)));
+            }
+            Some(_) => {}
+            None => return Err(self.lexer.end_of_input(ParseErrorKind::UnterminatedObject, "object")),
+        }
+
+        let value = match self.lexer.next_token(Expect::Value(None), self.decode::<B>()) {
+            Ok(None) => return Err(self.lexer.end_of_input(ParseErrorKind::UnterminatedObject, "object")),
+            Ok(token) => self.value_from(builder, token),
+            Err(e) => Err(e),
+        };
+        let value = match value {
+            Ok(value) => value,
+            Err(e) => {
+                self.recover(e)?;
+                if self.lexer.stopped {
+                    return Ok(());
+                }
+                builder.null()
+            }
+        };
+
+        builder.key_span(key_byte..key_end);
+        let repeated = if let Some(seen) = seen {
+            let key = key.decoded.unwrap_or_default();
+            if seen.contains(&key) {
+                Err(key)
+            } else {
+                seen.insert(key);
+                Ok(())
+            }
+        } else if self.build {
+            builder.insert(object, key, value, self.lexer.options.duplicate_keys, collected)
+        } else {
+            Ok(())
+        };
+        if let Err(key) = repeated {
+            return Err(self.lexer.error_at(
+                ParseErrorKind::DuplicateKey,
+                &format!("duplicate key '{}' in object", key),
+                key_position,
+                key_byte,
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Returns whether `input` is a single well-formed JSON document.
+pub fn is_valid(input: &str) -> bool {
+    Parser::new(input).validate().is_ok()
+}
+
+/// Strips insignificant whitespace from `input` while validating it. Every
+/// token is copied through verbatim, so number spellings like `1.50` and key
+/// order are preserved exactly.
+pub fn minify(input: &str) -> Result<String, ParseError> {
+    let mut parser = Parser::new(input);
+    parser.lexer.echo = Some(String::with_capacity(input.len()));
+    parser.validate()?;
+    Ok(parser.lexer.echo.take().unwrap_or_default())
+}
+
+/// Parses `input` until the first error, returning the value built up to
+/// that point along with the error. See `Parser::parse_partial`.
+///
+/// ```
+/// # use json_parser::{JsonValue, parse_partial};
+/// let (value, error) = parse_partial("[1, 2, tr");
+/// assert_eq!(value, JsonValue::Array(vec![1.into(), 2.into()]));
+/// assert_eq!(error.unwrap().message, "unexpected end of input in 'true'");
+///
+/// assert!(parse_partial("[1, 2]").1.is_none());
+/// ```
+pub fn parse_partial(input: &str) -> (JsonValue, Option<ParseError>) {
+    Parser::new(input).parse_partial()
+}
+
+/// Parses a sequence of JSON values written one after another, like
+/// `{"a": 1}{"b": 2} [3]`, one value per call to `next`. Whitespace between
+/// values is skipped but no separator is required, except between values
+/// such as two numbers that would otherwise run together. Iteration ends at
+/// the end of the input, or after the first error.
+///
+/// ```
+/// # use json_parser::{JsonValue, parse_stream};
+/// let values: Vec<JsonValue> = parse_stream("{}{}[] 1").collect::<Result<_, _>>().unwrap();
+/// assert_eq!(values.len(), 4);
+/// ```
+pub fn parse_stream(input: &str) -> impl Iterator<Item = Result<JsonValue, ParseError>> + use<> {
+    let mut parser = Parser::new(input);
+    let mut failed = false;
+    core::iter::from_fn(move || {
+        if failed {
+            return None;
+        }
+        if parser.lexer.at_end() {
+            return None;
+        }
+        let result = parser.parse_one();
+        failed = result.is_err();
+        Some(result)
+    })
+}
+
+/// Parses a single JSON document from `reader`, reading it in chunks as
+/// parsing proceeds rather than loading it all up front. Read failures and
+/// invalid UTF-8 are reported as a `ParseError` at the position in the stream
+/// where they occurred.
+#[cfg(feature = "std")]
+pub fn from_reader<R: Read>(reader: R) -> Result<JsonValue, ParseError> {
+    Parser::with_reader(reader, ParserOptions::default()).parse()
+}
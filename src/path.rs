@@ -0,0 +1,162 @@
+use core::{error::Error, fmt};
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::JsonValue;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PathSegment::Key(key) => write!(f, "{:?}", key),
+            PathSegment::Index(i) => write!(f, "[{}]", i),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathError {
+    /// The path itself is malformed; `position` is a char index into it.
+    Syntax { position: usize, message: String },
+    /// Segment number `index` (0-based) did not match anything in the value.
+    NotFound { index: usize, segment: PathSegment },
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PathError::Syntax { position, message } => {
+                write!(f, "invalid path at position {}: {}", position, message)
+            }
+            PathError::NotFound { index, segment } => {
+                write!(f, "path segment {} ({}) not found", index, segment)
+            }
+        }
+    }
+}
+
+impl Error for PathError {}
+
+/// Splits a path like `servers[0].host` or `["weird.key"].x` into segments.
+///
+/// Keys after a `.` (or at the start) run until the next `.` or `[`. A
+/// bracketed number is an array index and a bracketed double-quoted string
+/// is a key, which may contain `.`, `[` and `]`; inside the quotes `\"` and
+/// `\\` are the only escapes. The empty path refers to the value itself.
+pub fn parse_path(path: &str) -> Result<Vec<PathSegment>, PathError> {
+    let chars: Vec<char> = path.chars().collect();
+    let mut segments = Vec::new();
+    let mut pos = 0;
+    let syntax = |position: usize, message: &str| PathError::Syntax {
+        position,
+        message: message.to_string(),
+    };
+
+    while pos < chars.len() {
+        match chars[pos] {
+            '[' => {
+                pos += 1;
+                if chars.get(pos) == Some(&'"') {
+                    pos += 1;
+                    let mut key = String::new();
+                    loop {
+                        match chars.get(pos) {
+                            Some('"') => break,
+                            Some('\\') => match chars.get(pos + 1) {
+                                Some(&c @ ('"' | '\\')) => {
+                                    key.push(c);
+                                    pos += 1;
+                                }
+                                _ => return Err(syntax(pos, "invalid escape in quoted key")),
+                            },
+                            Some(&c) => key.push(c),
+                            None => return Err(syntax(pos, "unterminated quoted key")),
+                        }
+                        pos += 1;
+                    }
+                    pos += 1;
+                    segments.push(PathSegment::Key(key));
+                } else {
+                    let start = pos;
+                    while chars.get(pos).is_some_and(|c| c.is_ascii_digit()) {
+                        pos += 1;
+                    }
+                    let digits: String = chars[start..pos].iter().collect();
+                    let index = digits
+                        .parse()
+                        .map_err(|_| syntax(start, "expected an array index or quoted key after '['"))?;
+                    segments.push(PathSegment::Index(index));
+                }
+                if chars.get(pos) != Some(&']') {
+                    return Err(syntax(pos, "expected ']'"));
+                }
+                pos += 1;
+            }
+            c => {
+                if c == '.' {
+                    if segments.is_empty() {
+                        return Err(syntax(pos, "path cannot start with '.'"));
+                    }
+                    pos += 1;
+                }
+                let start = pos;
+                while pos < chars.len() && chars[pos] != '.' && chars[pos] != '[' {
+                    pos += 1;
+                }
+                if start == pos {
+                    return Err(syntax(pos, "empty key"));
+                }
+                segments.push(PathSegment::Key(chars[start..pos].iter().collect()));
+            }
+        }
+    }
+
+    Ok(segments)
+}
+
+impl JsonValue {
+    /// Looks up a value by a dotted path such as `servers[0].host`, or
+    /// `["weird.key"]` for keys containing dots; see `parse_path` for the
+    /// syntax, and `pointer` for RFC 6901 JSON Pointer syntax instead. Returns
+    /// `None` if the path is malformed or does not match; see `try_get_path`
+    /// for the reason.
+    pub fn get_path(&self, path: &str) -> Option<&JsonValue> {
+        self.try_get_path(path).ok()
+    }
+
+    pub fn get_path_mut(&mut self, path: &str) -> Option<&mut JsonValue> {
+        self.try_get_path_mut(path).ok()
+    }
+
+    /// Like `get_path`, but reports which segment failed to parse or match.
+    pub fn try_get_path(&self, path: &str) -> Result<&JsonValue, PathError> {
+        let mut current = self;
+        for (index, segment) in parse_path(path)?.into_iter().enumerate() {
+            current = match (current, &segment) {
+                (JsonValue::Object(o), PathSegment::Key(key)) => o.get(key),
+                (JsonValue::Array(a), PathSegment::Index(i)) => a.get(*i),
+                _ => None,
+            }
+            .ok_or(PathError::NotFound { index, segment })?;
+        }
+        Ok(current)
+    }
+
+    pub fn try_get_path_mut(&mut self, path: &str) -> Result<&mut JsonValue, PathError> {
+        let mut current = self;
+        for (index, segment) in parse_path(path)?.into_iter().enumerate() {
+            current = match (current, &segment) {
+                (JsonValue::Object(o), PathSegment::Key(key)) => o.get_mut(key),
+                (JsonValue::Array(a), PathSegment::Index(i)) => a.get_mut(*i),
+                _ => None,
+            }
+            .ok_or(PathError::NotFound { index, segment })?;
+        }
+        Ok(current)
+    }
+}
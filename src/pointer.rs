@@ -0,0 +1,135 @@
+use alloc::borrow::Cow;
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::JsonValue;
+
+impl JsonValue {
+    /// Looks up a value by JSON Pointer (RFC 6901), such as `/servers/0/host`.
+    /// The empty pointer refers to the whole value; in a key, `~1` stands for
+    /// `/` and `~0` for `~`. Returns `None` if the pointer is malformed or
+    /// does not match. `get_path` accepts the friendlier `a.b[0]` notation.
+    ///
+    /// ```
+    /// # use json_parser::Parser;
+    /// let value = Parser::new(r#"{"a/b": [10, {"~x": true}]}"#).parse().unwrap();
+    /// assert_eq!(value.pointer("/a~1b/0"), Some(&10.into()));
+    /// assert_eq!(value.pointer("/a~1b/1/~0x"), Some(&true.into()));
+    /// assert!(value.pointer("/a~1b/01").is_none());
+    /// ```
+    pub fn pointer(&self, pointer: &str) -> Option<&JsonValue> {
+        let mut current = self;
+        for token in pointer_tokens(pointer)? {
+            current = match current {
+                JsonValue::Object(o) => o.get(token?.as_ref())?,
+                JsonValue::Array(a) => a.get(array_index(&token?)?)?,
+                _ => return None,
+            };
+        }
+        Some(current)
+    }
+
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut JsonValue> {
+        let mut current = self;
+        for token in pointer_tokens(pointer)? {
+            current = match current {
+                JsonValue::Object(o) => o.get_mut(token?.as_ref())?,
+                JsonValue::Array(a) => a.get_mut(array_index(&token?)?)?,
+                _ => return None,
+            };
+        }
+        Some(current)
+    }
+
+    /// Like `pointer`, but returns `default` when nothing is found.
+    pub fn pointer_or<'a>(&'a self, pointer: &str, default: &'a JsonValue) -> &'a JsonValue {
+        self.pointer(pointer).unwrap_or(default)
+    }
+
+    /// The number at `pointer`, or `default` if there is no node there or it
+    /// is not a number. The other `_at` methods work the same way for their
+    /// types, which makes reading settings with fallbacks a one-liner:
+    ///
+    /// ```
+    /// # use json_parser::Parser;
+    /// let config = Parser::new(r#"{"server": {"port": 8080, "host": "example.com"}}"#).parse().unwrap();
+    /// assert_eq!(config.f64_at("/server/port", 80.0), 8080.0);
+    /// assert_eq!(config.f64_at("/server/timeout", 30.0), 30.0);
+    /// assert_eq!(config.str_at("/server/host", "localhost"), "example.com");
+    /// assert_eq!(config.str_at("/server/port", "n/a"), "n/a");
+    /// assert!(!config.bool_at("/server/tls", false));
+    /// ```
+    pub fn f64_at(&self, pointer: &str, default: f64) -> f64 {
+        self.pointer(pointer).and_then(JsonValue::as_f64).unwrap_or(default)
+    }
+
+    /// The integer at `pointer`, as `as_i64` converts it, or `default`.
+    pub fn i64_at(&self, pointer: &str, default: i64) -> i64 {
+        self.pointer(pointer).and_then(JsonValue::as_i64).unwrap_or(default)
+    }
+
+    /// The boolean at `pointer`, or `default`.
+    pub fn bool_at(&self, pointer: &str, default: bool) -> bool {
+        match self.pointer(pointer) {
+            Some(JsonValue::Boolean(b)) => *b,
+            _ => default,
+        }
+    }
+
+    /// The string at `pointer`, or `default`.
+    pub fn str_at<'a>(&'a self, pointer: &str, default: &'a str) -> &'a str {
+        match self.pointer(pointer) {
+            Some(JsonValue::String(s)) => s,
+            _ => default,
+        }
+    }
+}
+
+// Splits a pointer into unescaped reference tokens. `None` if the pointer is
+// not empty and does not start with `/`; an item is `None` if it contains an
+// invalid `~` escape.
+pub(crate) fn pointer_tokens(pointer: &str) -> Option<impl Iterator<Item = Option<Cow<'_, str>>>> {
+    let rest = match pointer {
+        "" => None,
+        _ => Some(pointer.strip_prefix('/')?),
+    };
+    Some(rest.into_iter().flat_map(|rest| rest.split('/')).map(unescape_token))
+}
+
+fn unescape_token(token: &str) -> Option<Cow<'_, str>> {
+    if !token.contains('~') {
+        return Some(Cow::Borrowed(token));
+    }
+    let mut out = String::with_capacity(token.len());
+    let mut chars = token.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '~' => match chars.next()? {
+                '0' => out.push('~'),
+                '1' => out.push('/'),
+                _ => return None,
+            },
+            c => out.push(c),
+        }
+    }
+    Some(Cow::Owned(out))
+}
+
+// Array indices are plain decimal with no leading zeros.
+pub(crate) fn array_index(token: &str) -> Option<usize> {
+    let valid = token.chars().all(|c| c.is_ascii_digit()) && (token == "0" || !token.starts_with('0'));
+    if valid { token.parse().ok() } else { None }
+}
+
+// Appends `/segment` to a pointer, escaping `~` and `/` as JSON Pointer
+// requires.
+pub(crate) fn push_pointer_segment(pointer: &mut String, segment: &str) {
+    pointer.push('/');
+    for c in segment.chars() {
+        match c {
+            '~' => pointer.push_str("~0"),
+            '/' => pointer.push_str("~1"),
+            c => pointer.push(c),
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use core::{error::Error, fmt};
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{JsonValue, pointer::push_pointer_segment};
+
+/// One way in which a value failed to match a schema.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaError {
+    /// JSON Pointer to the offending value, `""` for the root.
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let path = if self.path.is_empty() { "/" } else { &self.path };
+        write!(f, "{}: {}", path, self.message)
+    }
+}
+
+impl Error for SchemaError {}
+
+impl JsonValue {
+    /// Checks the value against a JSON Schema, collecting every violation
+    /// rather than stopping at the first.
+    ///
+    /// Only a subset of JSON Schema is understood: `type` (a name or a list
+    /// of names, including `"integer"`), `required`, `properties`, `items`,
+    /// `enum`, `minimum`, `maximum`, `minLength` and `maxLength`. Other
+    /// keywords are ignored. The schemas `true` and `false` accept and reject
+    /// everything respectively.
+    ///
+    /// ```
+    /// # use json_parser::Parser;
+    /// let schema = Parser::new(r#"{
+    ///     "type": "object",
+    ///     "required": ["name"],
+    ///     "properties": {"age": {"type": "integer", "minimum": 0}}
+    /// }"#).parse().unwrap();
+    /// let value = Parser::new(r#"{"age": -1}"#).parse().unwrap();
+    /// let errors = value.validate_schema(&schema).unwrap_err();
+    /// assert_eq!(errors.len(), 2);
+    /// assert!(errors.iter().any(|e| e.path == "/age"));
+    /// ```
+    pub fn validate_schema(&self, schema: &JsonValue) -> Result<(), Vec<SchemaError>> {
+        let mut errors = Vec::new();
+        check(self, schema, &mut String::new(), &mut errors);
+        if errors.is_empty() { Ok(()) } else { Err(errors) }
+    }
+}
+
+fn check(value: &JsonValue, schema: &JsonValue, path: &mut String, errors: &mut Vec<SchemaError>) {
+    let mut fail = |message: String| {
+        errors.push(SchemaError {
+            path: path.clone(),
+            message,
+        })
+    };
+    let schema = match schema {
+        JsonValue::Boolean(false) => return fail("no value is allowed here".to_string()),
+        JsonValue::Object(schema) => schema,
+        _ => return,
+    };
+
+    let is_type = |name: &JsonValue| matches!(name, JsonValue::String(name) if has_type(value, name));
+    match schema.get("type") {
+        Some(name @ JsonValue::String(_)) if !is_type(name) => {
+            fail(format!("expected {}, found {}", name, value.type_name()))
+        }
+        Some(JsonValue::Array(names)) if !names.iter().any(is_type) => {
+            let names: Vec<String> = names.iter().map(JsonValue::to_string).collect();
+            fail(format!("expected one of {}, found {}", names.join(", "), value.type_name()))
+        }
+        _ => {}
+    }
+
+    if let Some(JsonValue::Array(allowed)) = schema.get("enum")
+        && !allowed.contains(value)
+    {
+        fail(format!("{} is not one of the allowed values", value))
+    }
+
+    if let Some(n) = value.as_f64() {
+        if let Some(min) = schema.get("minimum").and_then(JsonValue::as_f64)
+            && n < min
+        {
+            fail(format!("{} is less than the minimum of {}", value, JsonValue::Number(min)))
+        }
+        if let Some(max) = schema.get("maximum").and_then(JsonValue::as_f64)
+            && n > max
+        {
+            fail(format!("{} is greater than the maximum of {}", value, JsonValue::Number(max)))
+        }
+    }
+
+    if let JsonValue::String(s) = value {
+        let length = s.chars().count();
+        if let Some(min) = schema.get("minLength").and_then(JsonValue::as_u64)
+            && (length as u64) < min
+        {
+            fail(format!("string of length {} is shorter than {}", length, min))
+        }
+        if let Some(max) = schema.get("maxLength").and_then(JsonValue::as_u64)
+            && length as u64 > max
+        {
+            fail(format!("string of length {} is longer than {}", length, max))
+        }
+    }
+
+    if let JsonValue::Object(object) = value {
+        if let Some(JsonValue::Array(required)) = schema.get("required") {
+            for key in required {
+                if let JsonValue::String(key) = key
+                    && !object.contains_key(key)
+                {
+                    fail(format!("missing required property '{}'", key))
+                }
+            }
+        }
+        if let Some(JsonValue::Object(properties)) = schema.get("properties") {
+            for (key, property_schema) in properties {
+                if let Some(property) = object.get(key) {
+                    let len = path.len();
+                    push_pointer_segment(path, key);
+                    check(property, property_schema, path, errors);
+                    path.truncate(len);
+                }
+            }
+        }
+    }
+
+    if let (JsonValue::Array(items), Some(item_schema)) = (value, schema.get("items")) {
+        for (i, item) in items.iter().enumerate() {
+            let len = path.len();
+            push_pointer_segment(path, &i.to_string());
+            check(item, item_schema, path, errors);
+            path.truncate(len);
+        }
+    }
+}
+
+fn has_type(value: &JsonValue, name: &str) -> bool {
+    match (name, value) {
+        ("integer", _) => value.as_f64().is_some_and(|n| n % 1.0 == 0.0),
+        _ => value.type_name() == name,
+    }
+}
@@ -0,0 +1,106 @@
+use core::str;
+#[cfg(feature = "std")]
+use std::io;
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{Encoding, JsonValue, ParseError, ParseErrorKind, Parser, encoding::invalid};
+
+// The ASCII record separator that starts every record of a JSON text
+// sequence.
+const RS: u8 = 0x1E;
+
+/// Parses a JSON text sequence (RFC 7464), where each value is preceded by
+/// a record separator byte (0x1E) and followed by a line feed. Each record
+/// is parsed on its own, so a broken record is reported as an error for
+/// that item only and the records after it are still read. Records holding
+/// nothing but whitespace are skipped. Error positions are counted from the
+/// start of `bytes`.
+///
+/// As the RFC requires, a record that is a number, `true`, `false` or
+/// `null` with no whitespace after it is reported as possibly truncated,
+/// since `12` may be what is left of `1234`.
+///
+/// ```
+/// # use json_parser::{JsonValue, parse_json_seq};
+/// let input = b"\x1e{\"a\": 1}\n\x1e[tru\n\x1e\"escaped \\u001e\"\n";
+/// let records: Vec<_> = parse_json_seq(input).collect();
+/// assert_eq!(records.len(), 3);
+/// assert!(records[0].is_ok() && records[1].is_err());
+/// assert_eq!(records[2].as_ref().unwrap(), &JsonValue::from("escaped \u{1e}"));
+/// ```
+pub fn parse_json_seq(bytes: &[u8]) -> impl Iterator<Item = Result<JsonValue, ParseError>> + '_ {
+    let mut byte_offset = 0;
+    let mut position = 0;
+    bytes.split(|&b| b == RS).enumerate().filter_map(move |(i, record)| {
+        let (start, start_char) = (byte_offset, position);
+        byte_offset += record.len() + 1;
+        // Counting the bytes that start a UTF-8 sequence counts chars
+        // without decoding, and stays consistent for invalid input.
+        position += record.iter().filter(|&&b| (b as i8) >= -0x40).count() + 1;
+        if record.iter().all(u8::is_ascii_whitespace) {
+            return None;
+        }
+        let result = if i == 0 {
+            let found = String::from_utf8_lossy(record).trim_start().chars().next().unwrap_or_default();
+            Err(ParseError {
+                kind: ParseErrorKind::UnexpectedChar(found),
+                message: "expected a record separator (0x1E) before the first record".to_string(),
+                position: 0,
+                byte_offset: 0,
+            })
+        } else {
+            parse_record(record)
+        };
+        Some(result.map_err(|mut e| {
+            e.position += start_char;
+            e.byte_offset += start;
+            e
+        }))
+    })
+}
+
+fn parse_record(record: &[u8]) -> Result<JsonValue, ParseError> {
+    let text = str::from_utf8(record).map_err(|e| {
+        let valid = str::from_utf8(&record[..e.valid_up_to()]).unwrap_or_default();
+        invalid(Encoding::Utf8, valid, "invalid byte sequence")
+    })?;
+    let value = Parser::new(text).parse()?;
+    let bare = matches!(
+        value,
+        JsonValue::Number(_) | JsonValue::RawNumber(_) | JsonValue::Boolean(_) | JsonValue::Null
+    );
+    if bare && !text.ends_with(|c: char| c.is_ascii_whitespace()) {
+        return Err(ParseError {
+            kind: ParseErrorKind::UnexpectedEnd,
+            message: "record may be truncated: a top-level number, true, false or null must be followed by whitespace"
+                .to_string(),
+            position: text.chars().count(),
+            byte_offset: text.len(),
+        });
+    }
+    Ok(value)
+}
+
+/// Writes `values` as a JSON text sequence (RFC 7464): each one is preceded
+/// by a record separator byte and written on a single line, as by
+/// `to_writer`, followed by a line feed.
+///
+/// ```
+/// # use json_parser::{JsonValue, write_json_seq};
+/// let mut out = Vec::new();
+/// write_json_seq(&mut out, &[JsonValue::from(1), JsonValue::from("a")]).unwrap();
+/// assert_eq!(out, b"\x1e1\n\x1e\"a\"\n");
+/// ```
+#[cfg(feature = "std")]
+pub fn write_json_seq<'a, W: io::Write>(
+    writer: &mut W,
+    values: impl IntoIterator<Item = &'a JsonValue>,
+) -> io::Result<()> {
+    for value in values {
+        writer.write_all(&[RS])?;
+        value.to_writer(writer)?;
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
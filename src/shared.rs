@@ -0,0 +1,222 @@
+#[cfg(any(not(feature = "std"), feature = "btree_map"))]
+use alloc::collections::BTreeSet as Set;
+use alloc::sync::Arc;
+#[cfg(all(feature = "std", not(feature = "btree_map")))]
+use std::collections::HashSet as Set;
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{
+    JsonValue, Map, ParseError, Parser,
+    options::DuplicateKeyPolicy,
+    lexer::ScannedString,
+    parser::{Build, insert_member},
+};
+
+/// An immutable value whose strings and containers are reference counted, so
+/// cloning it, or any subtree of it, is a few pointer copies rather than a
+/// deep copy. It is `Send` and `Sync`, which makes it suitable for handing
+/// one parsed document to many threads.
+///
+/// ```
+/// # use json_parser::Parser;
+/// let shared = Parser::new(r#"{"items": [1, 2, 3]}"#).parse().unwrap().into_shared();
+/// let items = shared.get("items").unwrap().clone(); // no copy of the array
+/// let worker = std::thread::spawn(move || items.get_index(2).cloned());
+/// assert_eq!(worker.join().unwrap().unwrap().to_owned().as_f64(), Some(3.0));
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub enum SharedJson {
+    Null,
+    Boolean(bool),
+    Number(f64),
+    RawNumber(Arc<str>),
+    String(Arc<str>),
+    Array(Arc<[SharedJson]>),
+    Object(Arc<Map<Arc<str>, SharedJson>>),
+}
+
+impl SharedJson {
+    /// Looks up `key` if this is an object.
+    pub fn get(&self, key: &str) -> Option<&SharedJson> {
+        match self {
+            SharedJson::Object(o) => o.get(key),
+            _ => None,
+        }
+    }
+
+    /// Looks up `index` if this is an array.
+    pub fn get_index(&self, index: usize) -> Option<&SharedJson> {
+        match self {
+            SharedJson::Array(a) => a.get(index),
+            _ => None,
+        }
+    }
+
+    /// Copies the value into an owned, mutable `JsonValue`.
+    pub fn to_owned(&self) -> JsonValue {
+        match self {
+            SharedJson::Null => JsonValue::Null,
+            SharedJson::Boolean(b) => JsonValue::Boolean(*b),
+            SharedJson::Number(n) => JsonValue::Number(*n),
+            SharedJson::RawNumber(s) => JsonValue::RawNumber(s.to_string()),
+            SharedJson::String(s) => JsonValue::String(s.to_string()),
+            SharedJson::Array(a) => JsonValue::Array(a.iter().map(SharedJson::to_owned).collect()),
+            SharedJson::Object(o) => JsonValue::Object(o.iter().map(|(k, v)| (k.to_string(), v.to_owned())).collect()),
+        }
+    }
+}
+
+impl JsonValue {
+    /// Converts into a `SharedJson` whose clones share structure.
+    pub fn into_shared(self) -> SharedJson {
+        match self {
+            JsonValue::Null => SharedJson::Null,
+            JsonValue::Boolean(b) => SharedJson::Boolean(b),
+            JsonValue::Number(n) => SharedJson::Number(n),
+            JsonValue::RawNumber(s) => SharedJson::RawNumber(s.into()),
+            JsonValue::String(s) => SharedJson::String(s.into()),
+            JsonValue::Array(a) => SharedJson::Array(a.into_iter().map(JsonValue::into_shared).collect()),
+            JsonValue::Object(o) => {
+                SharedJson::Object(Arc::new(o.into_iter().map(|(k, v)| (k.into(), v.into_shared())).collect()))
+            }
+        }
+    }
+}
+
+impl From<JsonValue> for SharedJson {
+    fn from(value: JsonValue) -> Self {
+        value.into_shared()
+    }
+}
+
+/// A set of object keys that parsed documents share instead of each holding
+/// its own copy, for use with `Parser::parse_shared`. Keep one around to
+/// share keys across any number of parses; it only grows, so `clear` it if
+/// the documents stop having keys in common.
+#[derive(Debug, Clone, Default)]
+pub struct KeyInterner {
+    keys: Set<Arc<str>>,
+}
+
+impl KeyInterner {
+    pub fn new() -> Self {
+        KeyInterner::default()
+    }
+
+    /// Returns the shared copy of `key`, adding it if it is new.
+    pub fn intern(&mut self, key: &str) -> Arc<str> {
+        if let Some(shared) = self.keys.get(key) {
+            return shared.clone();
+        }
+        let shared: Arc<str> = key.into();
+        self.keys.insert(shared.clone());
+        shared
+    }
+
+    /// The number of distinct keys held.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Forgets every key. Documents already parsed keep theirs.
+    pub fn clear(&mut self) {
+        self.keys.clear();
+    }
+}
+
+struct Interning<'i> {
+    interner: &'i mut KeyInterner,
+}
+
+impl Build for Interning<'_> {
+    type Value = SharedJson;
+    type Array = Vec<SharedJson>;
+    type Object = Map<Arc<str>, SharedJson>;
+
+    const BORROWS_STRINGS: bool = false;
+
+    fn null(&mut self) -> SharedJson {
+        SharedJson::Null
+    }
+
+    fn boolean(&mut self, b: bool) -> SharedJson {
+        SharedJson::Boolean(b)
+    }
+
+    fn number(&mut self, n: f64) -> SharedJson {
+        SharedJson::Number(n)
+    }
+
+    fn raw_number(&mut self, text: ScannedString) -> SharedJson {
+        SharedJson::RawNumber(text.decoded.unwrap_or_default().into())
+    }
+
+    fn string(&mut self, s: ScannedString) -> SharedJson {
+        SharedJson::String(s.decoded.unwrap_or_default().into())
+    }
+
+    fn new_array(&mut self) -> Self::Array {
+        Vec::new()
+    }
+
+    fn push(&mut self, array: &mut Self::Array, value: SharedJson) {
+        array.push(value);
+    }
+
+    fn array(&mut self, items: Self::Array) -> SharedJson {
+        SharedJson::Array(items.into())
+    }
+
+    fn new_object(&mut self) -> Self::Object {
+        Map::new()
+    }
+
+    fn insert(
+        &mut self,
+        object: &mut Self::Object,
+        key: ScannedString,
+        value: SharedJson,
+        policy: DuplicateKeyPolicy,
+        collected: &mut Vec<String>,
+    ) -> Result<(), String> {
+        let key = self.interner.intern(key.decoded.as_deref().unwrap_or_default());
+        insert_member(object, key, value, policy, collected, |existing, value, first| {
+            let mut items = match existing {
+                SharedJson::Array(items) if !first => items.to_vec(),
+                _ => vec![existing.clone()],
+            };
+            items.push(value);
+            *existing = SharedJson::Array(items.into());
+        })
+    }
+
+    fn object(&mut self, object: Self::Object) -> SharedJson {
+        SharedJson::Object(Arc::new(object))
+    }
+}
+
+impl Parser {
+    /// Parses straight into a `SharedJson`, taking object keys from
+    /// `interner` so that every object with a given key shares one copy of
+    /// it. For a large array of objects with the same fields this stores
+    /// each field name once rather than once per object.
+    ///
+    /// ```
+    /// # use json_parser::{KeyInterner, Parser, SharedJson};
+    /// let mut interner = KeyInterner::new();
+    /// let doc = Parser::new(r#"[{"id": 1, "name": "a"}, {"id": 2, "name": "b"}]"#)
+    ///     .parse_shared(&mut interner)
+    ///     .unwrap();
+    /// Parser::new(r#"{"id": 3}"#).parse_shared(&mut interner).unwrap();
+    /// assert_eq!(interner.len(), 2);
+    /// assert_eq!(doc.get_index(1).and_then(|o| o.get("name")), Some(&SharedJson::String("b".into())));
+    /// ```
+    pub fn parse_shared(&mut self, interner: &mut KeyInterner) -> Result<SharedJson, ParseError> {
+        self.parse_with(&mut Interning { interner })
+    }
+}
@@ -0,0 +1,239 @@
+use alloc::collections::BTreeSet;
+use core::ops::Range;
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{
+    JsonValue, Map, ParseError, Parser,
+    options::DuplicateKeyPolicy,
+    lexer::ScannedString,
+    parser::Build,
+    pointer::{array_index, pointer_tokens},
+};
+
+/// A parsed value that remembers where in the input it came from, for
+/// tooling such as editors and linters that has to point back at the
+/// source. Produced by `Parser::parse_spanned`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpannedValue {
+    /// Byte range of the value in the input, from its first character to
+    /// just past its last, so a string's span includes its quotes and a
+    /// container's its brackets.
+    pub span: Range<usize>,
+    pub kind: SpannedKind,
+}
+
+/// The value itself, mirroring `JsonValue`. Objects are a list of members
+/// in source order rather than a map, so every occurrence of a repeated key
+/// keeps its position.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpannedKind {
+    Null,
+    Boolean(bool),
+    Number(f64),
+    RawNumber(String),
+    String(String),
+    Array(Vec<SpannedValue>),
+    Object(Vec<SpannedMember>),
+}
+
+/// One `key: value` member of a spanned object.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpannedMember {
+    pub key: String,
+    /// Byte range of the key in the input, quotes included.
+    pub key_span: Range<usize>,
+    pub value: SpannedValue,
+}
+
+impl SpannedValue {
+    /// Looks up `key` if this is an object. Of repeated keys the last one
+    /// wins, as it does for `Parser::parse` by default.
+    pub fn get(&self, key: &str) -> Option<&SpannedValue> {
+        self.member(key).map(|m| &m.value)
+    }
+
+    /// Looks up the member with `key` if this is an object, for its
+    /// `key_span`. Of repeated keys the last one wins.
+    pub fn member(&self, key: &str) -> Option<&SpannedMember> {
+        match &self.kind {
+            SpannedKind::Object(members) => members.iter().rev().find(|m| m.key == key),
+            _ => None,
+        }
+    }
+
+    /// Looks up `index` if this is an array.
+    pub fn get_index(&self, index: usize) -> Option<&SpannedValue> {
+        match &self.kind {
+            SpannedKind::Array(items) => items.get(index),
+            _ => None,
+        }
+    }
+
+    /// Looks up a value by JSON Pointer, as `JsonValue::pointer` does.
+    ///
+    /// ```
+    /// # use json_parser::Parser;
+    /// let input = r#"{"servers": [{"host": "a"}, {"host": 42}]}"#;
+    /// let doc = Parser::new(input).parse_spanned().unwrap();
+    /// let host = doc.pointer("/servers/1/host").unwrap();
+    /// assert_eq!(&input[host.span.clone()], "42");
+    /// ```
+    pub fn pointer(&self, pointer: &str) -> Option<&SpannedValue> {
+        let mut current = self;
+        for token in pointer_tokens(pointer)? {
+            current = match &current.kind {
+                SpannedKind::Object(_) => current.get(token?.as_ref())?,
+                SpannedKind::Array(items) => items.get(array_index(&token?)?)?,
+                _ => return None,
+            };
+        }
+        Some(current)
+    }
+
+    /// Drops the spans, giving the value `Parser::parse` would have built
+    /// with the default options. Of repeated keys the last one wins
+    /// whatever `ParserOptions::duplicate_keys` was when parsing:
+    /// `FirstWins` and `Collect` are not applied here.
+    pub fn to_value(&self) -> JsonValue {
+        match &self.kind {
+            SpannedKind::Null => JsonValue::Null,
+            SpannedKind::Boolean(b) => JsonValue::Boolean(*b),
+            SpannedKind::Number(n) => JsonValue::Number(*n),
+            SpannedKind::RawNumber(s) => JsonValue::RawNumber(s.clone()),
+            SpannedKind::String(s) => JsonValue::String(s.clone()),
+            SpannedKind::Array(items) => JsonValue::Array(items.iter().map(SpannedValue::to_value).collect()),
+            SpannedKind::Object(members) => {
+                let mut map = Map::new();
+                for m in members {
+                    map.insert(m.key.clone(), m.value.to_value());
+                }
+                JsonValue::Object(map)
+            }
+        }
+    }
+}
+
+// Builds `SpannedValue`s. Values are made with an empty span, which
+// `spanned` fills in once the parser knows where they end; the span of the
+// key being inserted arrives just before `insert`.
+#[derive(Default)]
+struct Spanning {
+    key_span: Range<usize>,
+}
+
+// An object being built. Under `DuplicateKeyPolicy::Reject` its keys so far
+// are also kept in `keys`, so a repeat is found without a scan.
+struct SpannedObject {
+    members: Vec<SpannedMember>,
+    keys: BTreeSet<String>,
+}
+
+fn unspanned(kind: SpannedKind) -> SpannedValue {
+    SpannedValue { span: 0..0, kind }
+}
+
+impl Build for Spanning {
+    type Value = SpannedValue;
+    type Array = Vec<SpannedValue>;
+    type Object = SpannedObject;
+
+    const BORROWS_STRINGS: bool = false;
+
+    fn null(&mut self) -> SpannedValue {
+        unspanned(SpannedKind::Null)
+    }
+
+    fn boolean(&mut self, b: bool) -> SpannedValue {
+        unspanned(SpannedKind::Boolean(b))
+    }
+
+    fn number(&mut self, n: f64) -> SpannedValue {
+        unspanned(SpannedKind::Number(n))
+    }
+
+    fn raw_number(&mut self, text: ScannedString) -> SpannedValue {
+        unspanned(SpannedKind::RawNumber(text.decoded.unwrap_or_default()))
+    }
+
+    fn string(&mut self, s: ScannedString) -> SpannedValue {
+        unspanned(SpannedKind::String(s.decoded.unwrap_or_default()))
+    }
+
+    fn new_array(&mut self) -> Self::Array {
+        Vec::new()
+    }
+
+    fn push(&mut self, array: &mut Self::Array, value: SpannedValue) {
+        array.push(value);
+    }
+
+    fn array(&mut self, items: Self::Array) -> SpannedValue {
+        unspanned(SpannedKind::Array(items))
+    }
+
+    fn new_object(&mut self) -> Self::Object {
+        SpannedObject {
+            members: Vec::new(),
+            keys: BTreeSet::new(),
+        }
+    }
+
+    fn insert(
+        &mut self,
+        object: &mut Self::Object,
+        key: ScannedString,
+        value: SpannedValue,
+        policy: DuplicateKeyPolicy,
+        _collected: &mut Vec<String>,
+    ) -> Result<(), String> {
+        let key = key.decoded.unwrap_or_default();
+        if policy == DuplicateKeyPolicy::Reject && !object.keys.insert(key.clone()) {
+            return Err(key);
+        }
+        object.members.push(SpannedMember {
+            key,
+            key_span: self.key_span.clone(),
+            value,
+        });
+        Ok(())
+    }
+
+    fn object(&mut self, object: Self::Object) -> SpannedValue {
+        unspanned(SpannedKind::Object(object.members))
+    }
+
+    fn spanned(&mut self, mut value: SpannedValue, span: Range<usize>) -> SpannedValue {
+        value.span = span;
+        value
+    }
+
+    fn key_span(&mut self, span: Range<usize>) {
+        self.key_span = span;
+    }
+}
+
+impl Parser {
+    /// Parses into a `SpannedValue`, recording the byte range of every value
+    /// and key. Ranges are offsets into the input as given, so they can be
+    /// used to slice it; a leading byte order mark counts towards them.
+    ///
+    /// Every member of an object is kept, in source order, so
+    /// `ParserOptions::duplicate_keys` only matters when it is `Reject`:
+    /// `FirstWins` and `Collect` are ignored, and `SpannedValue::to_value`
+    /// lets the last of repeated keys win.
+    ///
+    /// ```
+    /// # use json_parser::Parser;
+    /// let input = r#"{"name": "demo", "tags": [1, true]}"#;
+    /// let doc = Parser::new(input).parse_spanned().unwrap();
+    /// assert_eq!(doc.span, 0..input.len());
+    /// let tags = doc.member("tags").unwrap();
+    /// assert_eq!(&input[tags.key_span.clone()], r#""tags""#);
+    /// assert_eq!(&input[tags.value.span.clone()], "[1, true]");
+    /// assert_eq!(tags.value.get_index(1).unwrap().span, 29..33);
+    /// ```
+    pub fn parse_spanned(&mut self) -> Result<SpannedValue, ParseError> {
+        self.parse_with(&mut Spanning::default())
+    }
+}
@@ -0,0 +1,131 @@
+use alloc::collections::BTreeMap;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{JsonValue, ObjectBuilder};
+
+/// Conversion from a Rust type into a `JsonValue`, the counterpart of
+/// `FromJson`. The result can be written out with `Display` or
+/// `to_string_pretty`.
+///
+/// ```
+/// use json_parser::{JsonValue, ObjectBuilder, ToJson};
+///
+/// struct Point {
+///     x: f64,
+///     label: Option<String>,
+/// }
+///
+/// impl ToJson for Point {
+///     fn to_json(&self) -> JsonValue {
+///         ObjectBuilder::new()
+///             .insert_json("x", &self.x)
+///             .insert_json_some("label", &self.label)
+///             .build()
+///     }
+/// }
+///
+/// let point = Point { x: 1.5, label: None };
+/// assert_eq!(point.to_json().to_string(), r#"{"x": 1.5}"#);
+/// ```
+pub trait ToJson {
+    fn to_json(&self) -> JsonValue;
+}
+
+impl ToJson for JsonValue {
+    fn to_json(&self) -> JsonValue {
+        self.clone()
+    }
+}
+
+impl ToJson for bool {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::Boolean(*self)
+    }
+}
+
+macro_rules! impl_to_json_number {
+    ($($t:ty),*) => {
+        $(
+            impl ToJson for $t {
+                fn to_json(&self) -> JsonValue {
+                    JsonValue::Number(*self as f64)
+                }
+            }
+        )*
+    };
+}
+
+impl_to_json_number!(f32, f64, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl ToJson for str {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::String(self.to_string())
+    }
+}
+
+impl ToJson for String {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::String(self.clone())
+    }
+}
+
+impl<T: ToJson + ?Sized> ToJson for &T {
+    fn to_json(&self) -> JsonValue {
+        (**self).to_json()
+    }
+}
+
+/// `None` becomes `null`; use `ObjectBuilder::insert_json_some` to leave the
+/// key out instead.
+impl<T: ToJson> ToJson for Option<T> {
+    fn to_json(&self) -> JsonValue {
+        match self {
+            Some(value) => value.to_json(),
+            None => JsonValue::Null,
+        }
+    }
+}
+
+impl<T: ToJson> ToJson for [T] {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::Array(self.iter().map(ToJson::to_json).collect())
+    }
+}
+
+impl<T: ToJson> ToJson for Vec<T> {
+    fn to_json(&self) -> JsonValue {
+        self.as_slice().to_json()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: ToJson> ToJson for HashMap<String, T> {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::Object(self.iter().map(|(k, v)| (k.clone(), v.to_json())).collect())
+    }
+}
+
+impl<T: ToJson> ToJson for BTreeMap<String, T> {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::Object(self.iter().map(|(k, v)| (k.clone(), v.to_json())).collect())
+    }
+}
+
+impl ObjectBuilder {
+    /// Adds a member converted with `ToJson`.
+    pub fn insert_json<T: ToJson + ?Sized>(self, key: impl Into<String>, value: &T) -> Self {
+        self.insert(key, value.to_json())
+    }
+
+    /// Adds a member converted with `ToJson` for `Some`, and leaves the key
+    /// out entirely for `None`.
+    pub fn insert_json_some<T: ToJson>(self, key: impl Into<String>, value: &Option<T>) -> Self {
+        match value {
+            Some(value) => self.insert_json(key, value),
+            None => self,
+        }
+    }
+}
@@ -0,0 +1,593 @@
+use alloc::collections::BTreeMap;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::{collections::HashMap, io};
+
+#[cfg(feature = "std")]
+use crate::write::IoWriter;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{
+    JsonError, SerializeError,
+    options::{Format, NonFinitePolicy, WriteOptions},
+    write::{WriteError, write_value},
+};
+
+/// The map behind `JsonValue::Object`: a `HashMap`, whose iteration order
+/// is arbitrary and differs between runs, or, with the `btree_map` feature
+/// or without the `std` feature, a `BTreeMap`, whose members iterate in key
+/// order.
+#[cfg(all(feature = "std", not(feature = "btree_map")))]
+pub type Map<K, V> = HashMap<K, V>;
+#[cfg(any(not(feature = "std"), feature = "btree_map"))]
+pub type Map<K, V> = BTreeMap<K, V>;
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub enum JsonValue {
+    #[default]
+    Null,
+    Boolean(bool),
+    Number(f64),
+    /// A number kept as the exact text it was written as, produced when
+    /// `ParserOptions::numbers_as_strings` is set. It is written back out
+    /// unchanged, so no precision is lost passing it through.
+    RawNumber(String),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Map<String, JsonValue>),
+}
+
+/// Writes compact JSON, as `to_string_with` does with default options except
+/// that object keys are sorted, so a value always prints the same way. NaN
+/// and infinite numbers, which JSON cannot represent, are written as `null`;
+/// `to_json_string` rejects them instead.
+impl fmt::Display for JsonValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_to(f)
+    }
+}
+
+// Largest integer n such that every integer in -n..=n has an exact f64
+// representation (JavaScript's Number.MAX_SAFE_INTEGER).
+pub(crate) const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+// An integer read from a binary format: a `Number` if it is exactly an f64,
+// as every integer within ±2^53 and some beyond are, and otherwise a
+// `RawNumber` of its digits so that none are lost.
+#[cfg(any(feature = "cbor", feature = "msgpack"))]
+pub(crate) fn integer_value(n: i128) -> JsonValue {
+    let float = n as f64;
+    if float as i128 == n { JsonValue::Number(float) } else { JsonValue::RawNumber(n.to_string()) }
+}
+
+impl JsonValue {
+    /// Returns the number, converting a `RawNumber` from its text.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            JsonValue::Number(n) => Some(*n),
+            JsonValue::RawNumber(s) => s.parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, JsonValue::Null)
+    }
+
+    pub fn is_boolean(&self) -> bool {
+        matches!(self, JsonValue::Boolean(_))
+    }
+
+    /// Whether this is a `Number` or a `RawNumber`.
+    pub fn is_number(&self) -> bool {
+        matches!(self, JsonValue::Number(_) | JsonValue::RawNumber(_))
+    }
+
+    pub fn is_string(&self) -> bool {
+        matches!(self, JsonValue::String(_))
+    }
+
+    pub fn is_array(&self) -> bool {
+        matches!(self, JsonValue::Array(_))
+    }
+
+    pub fn is_object(&self) -> bool {
+        matches!(self, JsonValue::Object(_))
+    }
+
+    /// Whether this is a number with no fractional part, such as `5` or
+    /// `5.0`. This holds for whole numbers of any magnitude, including ones
+    /// like `1e300` that are too large for `as_i64`.
+    pub fn is_integer(&self) -> bool {
+        self.as_f64().is_some_and(|n| n % 1.0 == 0.0)
+    }
+
+    /// Returns the number as an `i64` only if it is a whole number within
+    /// ±(2^53 - 1). Beyond that range neighbouring integers share an f64, so
+    /// the stored value may already differ from the one in the input.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self.as_f64() {
+            Some(n) if n % 1.0 == 0.0 && n.abs() <= MAX_SAFE_INTEGER => Some(n as i64),
+            _ => None,
+        }
+    }
+
+    /// Like `as_i64`, but also `None` for negative numbers.
+    pub fn as_u64(&self) -> Option<u64> {
+        match self.as_i64() {
+            Some(n) if n >= 0 => Some(n as u64),
+            _ => None,
+        }
+    }
+
+    /// Number of elements in an array or members in an object; 0 for any
+    /// other value. Strings count as 0 too, rather than picking between their
+    /// length in chars and in bytes.
+    pub fn len(&self) -> usize {
+        match self {
+            JsonValue::Array(a) => a.len(),
+            JsonValue::Object(o) => o.len(),
+            _ => 0,
+        }
+    }
+
+    /// Whether `len` is 0, which is also the case for every scalar.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Total number of values in the tree, counting this one, every array
+    /// element and every object member value (keys are not counted).
+    pub fn node_count(&self) -> usize {
+        match self {
+            JsonValue::Array(a) => 1 + a.iter().map(JsonValue::node_count).sum::<usize>(),
+            JsonValue::Object(o) => 1 + o.values().map(JsonValue::node_count).sum::<usize>(),
+            _ => 1,
+        }
+    }
+
+    /// Maximum container nesting: 0 for a scalar, 1 for `[]` or `{"a": 1}`,
+    /// 2 for `[[1]]`, and so on.
+    pub fn depth(&self) -> usize {
+        match self {
+            JsonValue::Array(a) => 1 + a.iter().map(JsonValue::depth).max().unwrap_or(0),
+            JsonValue::Object(o) => 1 + o.values().map(JsonValue::depth).max().unwrap_or(0),
+            _ => 0,
+        }
+    }
+
+    /// Returns whether `subset` describes part of this value. An object
+    /// matches if every key in `subset` is present and its value contains the
+    /// subset's value, so extra keys are ignored. An array matches if each of
+    /// the subset's elements is contained by a distinct element, in the same
+    /// order but not necessarily adjacent: `[1, 3]` is contained in
+    /// `[1, 2, 3]`, while `[3, 1]` is not. Other values must be equal.
+    ///
+    /// ```
+    /// # use json_parser::Parser;
+    /// let response = Parser::new(r#"{"status": "ok", "items": [{"id": 1, "tags": []}, {"id": 2}]}"#)
+    ///     .parse()
+    ///     .unwrap();
+    /// let expected = Parser::new(r#"{"items": [{"id": 2}]}"#).parse().unwrap();
+    /// assert!(response.contains(&expected));
+    /// ```
+    pub fn contains(&self, subset: &JsonValue) -> bool {
+        match (self, subset) {
+            (JsonValue::Object(o), JsonValue::Object(sub)) => sub
+                .iter()
+                .all(|(key, value)| o.get(key).is_some_and(|v| v.contains(value))),
+            (JsonValue::Array(a), JsonValue::Array(sub)) => {
+                // Matching each wanted element as early as possible leaves the
+                // most room for the rest.
+                let mut items = a.iter();
+                sub.iter().all(|wanted| items.any(|item| item.contains(wanted)))
+            }
+            _ => self == subset,
+        }
+    }
+
+    /// Moves the value out, leaving `Null` in its place.
+    pub fn take(&mut self) -> JsonValue {
+        core::mem::take(self)
+    }
+
+    /// Clones the value, giving up with `JsonError::TooManyNodes` as soon as
+    /// the copy would hold more than `max_nodes` values, counted as by
+    /// `node_count`. Nothing beyond the budget is ever allocated, so an
+    /// untrusted tree can be copied defensively.
+    ///
+    /// ```
+    /// # use json_parser::Parser;
+    /// let value = Parser::new("[1, [2, 3]]").parse().unwrap();
+    /// assert_eq!(value.try_clone(5).unwrap(), value);
+    /// assert!(value.try_clone(4).is_err());
+    /// ```
+    pub fn try_clone(&self, max_nodes: usize) -> Result<JsonValue, JsonError> {
+        let mut remaining = max_nodes;
+        self.clone_within(&mut remaining).ok_or(JsonError::TooManyNodes { max: max_nodes })
+    }
+
+    fn clone_within(&self, remaining: &mut usize) -> Option<JsonValue> {
+        *remaining = remaining.checked_sub(1)?;
+        Some(match self {
+            JsonValue::Array(a) => {
+                let mut items = Vec::with_capacity(a.len().min(*remaining));
+                for item in a {
+                    items.push(item.clone_within(remaining)?);
+                }
+                JsonValue::Array(items)
+            }
+            JsonValue::Object(o) => {
+                let mut members = Map::new();
+                for (key, value) in o {
+                    members.insert(key.clone(), value.clone_within(remaining)?);
+                }
+                JsonValue::Object(members)
+            }
+            other => other.clone(),
+        })
+    }
+
+    /// Calls `f` on every value in the tree, this one included, so it can
+    /// be changed in place. Children are visited before their container, so
+    /// `f` sees them already transformed, and whatever `f` puts in place of
+    /// a value is not visited again.
+    ///
+    /// ```
+    /// # use json_parser::{JsonValue, Parser};
+    /// let mut value = Parser::new(r#"{"email": "a@b.c", "tags": ["x@y.z", "ok"]}"#).parse().unwrap();
+    /// value.transform(|v| {
+    ///     if let JsonValue::String(s) = v && s.contains('@') {
+    ///         *v = JsonValue::from("REDACTED");
+    ///     }
+    /// });
+    /// assert_eq!(value, Parser::new(r#"{"email": "REDACTED", "tags": ["REDACTED", "ok"]}"#).parse().unwrap());
+    /// ```
+    pub fn transform<F: FnMut(&mut JsonValue)>(&mut self, mut f: F) {
+        self.transform_with(&mut f);
+    }
+
+    fn transform_with<F: FnMut(&mut JsonValue)>(&mut self, f: &mut F) {
+        match self {
+            JsonValue::Array(items) => items.iter_mut().for_each(|item| item.transform_with(f)),
+            JsonValue::Object(members) => members.values_mut().for_each(|value| value.transform_with(f)),
+            _ => {}
+        }
+        f(self);
+    }
+
+    /// Replaces every object key in the tree, at any depth, with `f` applied
+    /// to it. If two keys of one object are renamed to the same key, the
+    /// member whose original key sorts last wins and the other is dropped.
+    ///
+    /// ```
+    /// # use json_parser::Parser;
+    /// let mut value = Parser::new(r#"{"user_id": 1, "home_address": {"zip_code": "123"}}"#).parse().unwrap();
+    /// value.rename_keys(|key| {
+    ///     let mut words = key.split('_');
+    ///     let first = words.next().unwrap_or_default().to_string();
+    ///     words.fold(first, |camel, word| camel + &word[..1].to_uppercase() + &word[1..])
+    /// });
+    /// assert_eq!(value, Parser::new(r#"{"userId": 1, "homeAddress": {"zipCode": "123"}}"#).parse().unwrap());
+    /// ```
+    pub fn rename_keys<F: Fn(&str) -> String>(&mut self, f: F) {
+        self.transform(|value| {
+            if let JsonValue::Object(object) = value {
+                let mut members: Vec<_> = core::mem::take(object).into_iter().collect();
+                members.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+                *object = members.into_iter().map(|(key, value)| (f(&key), value)).collect();
+            }
+        });
+    }
+
+    /// Removes and returns the member `key` of an object. Returns `None` if
+    /// the key is absent or the value is not an object.
+    pub fn remove(&mut self, key: &str) -> Option<JsonValue> {
+        match self {
+            JsonValue::Object(o) => o.remove(key),
+            _ => None,
+        }
+    }
+
+    /// Removes and returns element `index` of an array, shifting the later
+    /// elements down. Returns `None` if the index is out of bounds or the
+    /// value is not an array.
+    pub fn remove_index(&mut self, index: usize) -> Option<JsonValue> {
+        match self {
+            JsonValue::Array(a) if index < a.len() => Some(a.remove(index)),
+            _ => None,
+        }
+    }
+
+    /// Keeps only the members of an object for which `f(key, value)`
+    /// returns `true`. Returns `false`, changing nothing, if the value is
+    /// not an object.
+    pub fn retain_entries<F: FnMut(&str, &JsonValue) -> bool>(&mut self, mut f: F) -> bool {
+        match self {
+            JsonValue::Object(o) => {
+                o.retain(|key, value| f(key, value));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Keeps only the elements of an array for which `f(index, element)`
+    /// returns `true`, in their original order. Indices are those from
+    /// before any element was removed. Returns `false`, changing nothing, if
+    /// the value is not an array.
+    pub fn retain_elements<F: FnMut(usize, &JsonValue) -> bool>(&mut self, mut f: F) -> bool {
+        match self {
+            JsonValue::Array(a) => {
+                let mut index = 0;
+                a.retain(|item| {
+                    index += 1;
+                    f(index - 1, item)
+                });
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Like `retain_entries`, applied to every object in the tree, including
+    /// those inside arrays. Members are filtered before their values are
+    /// visited, so `f` never sees anything inside a member it dropped.
+    /// Returns `false`, changing nothing, if the value is neither an array
+    /// nor an object.
+    ///
+    /// ```
+    /// # use json_parser::Parser;
+    /// let mut doc = Parser::new(r#"{"_internal_id": 7, "items": [{"name": "a", "_internal_rank": 1}]}"#).parse().unwrap();
+    /// doc.retain_recursive(|key, _| !key.starts_with("_internal"));
+    /// assert_eq!(doc.to_string(), r#"{"items": [{"name": "a"}]}"#);
+    /// ```
+    pub fn retain_recursive<F: FnMut(&str, &JsonValue) -> bool>(&mut self, mut f: F) -> bool {
+        self.retain_with(&mut f)
+    }
+
+    fn retain_with<F: FnMut(&str, &JsonValue) -> bool>(&mut self, f: &mut F) -> bool {
+        match self {
+            JsonValue::Array(items) => {
+                items.iter_mut().for_each(|item| {
+                    item.retain_with(f);
+                });
+                true
+            }
+            JsonValue::Object(members) => {
+                members.retain(|key, value| f(key, value));
+                members.values_mut().for_each(|value| {
+                    value.retain_with(f);
+                });
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Serializes the value across multiple lines, indenting each nesting
+    /// level by `indent` spaces.
+    pub fn to_string_pretty(&self, indent: usize) -> String {
+        self.to_string_with(&WriteOptions {
+            indent: Some(indent),
+            ..Default::default()
+        })
+    }
+
+    /// Like `to_string_pretty`, indenting each nesting level with `indent`,
+    /// such as `"\t"` or four spaces.
+    ///
+    /// ```
+    /// # use json_parser::Parser;
+    /// let value = Parser::new(r#"{"a": [1, {}]}"#).parse().unwrap();
+    /// assert_eq!(value.to_string_pretty_with("\t"), "{\n\t\"a\": [\n\t\t1,\n\t\t{}\n\t]\n}");
+    /// ```
+    pub fn to_string_pretty_with(&self, indent: &str) -> String {
+        self.to_string_with(&WriteOptions {
+            indent_string: Some(indent.to_string()),
+            ..Default::default()
+        })
+    }
+
+    /// Serializes the value in one of the preset layouts of `Format`.
+    ///
+    /// ```
+    /// # use json_parser::{Format, Parser};
+    /// let value = Parser::new(r#"{"b": [1, 2], "a": null}"#).parse().unwrap();
+    /// assert_eq!(value.serialize(Format::Canonical), r#"{"a":null,"b":[1,2]}"#);
+    /// assert_eq!(value.serialize(Format::Pretty { indent: 2 }).lines().count(), 7);
+    /// ```
+    pub fn serialize(&self, format: Format) -> String {
+        self.to_string_with(&format.into())
+    }
+
+    /// Serializes the value with the layout and number formatting chosen in
+    /// `options`. With `NonFinitePolicy::Error` NaN and infinite numbers are
+    /// written as `null`, as this cannot fail; see `to_json_string_with`.
+    ///
+    /// ```
+    /// # use json_parser::{JsonValue, WriteOptions};
+    /// let options = WriteOptions {
+    ///     integral_trailing_zero: true,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(JsonValue::Number(3.0).to_string_with(&options), "3.0");
+    /// ```
+    pub fn to_string_with(&self, options: &WriteOptions) -> String {
+        let mut out = String::new();
+        self.write_to_with(&mut out, options).expect("writing to a String cannot fail");
+        out
+    }
+
+    /// Writes the value into any `fmt::Write` exactly as `Display` formats
+    /// it, without needing `std::io`.
+    ///
+    /// ```
+    /// # use json_parser::Parser;
+    /// let value = Parser::new(r#"{"b": 1, "a": [true]}"#).parse().unwrap();
+    /// let mut out = String::from("value = ");
+    /// value.write_to(&mut out).unwrap();
+    /// assert_eq!(out, r#"value = {"a": [true], "b": 1}"#);
+    /// ```
+    pub fn write_to<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        let options = WriteOptions {
+            sort_keys: true,
+            ..Default::default()
+        };
+        self.write_to_with(w, &options)
+    }
+
+    /// Like `write_to`, formatted as by `to_string_with`. The only error is
+    /// one returned by `w`.
+    pub fn write_to_with<W: fmt::Write>(&self, w: &mut W, options: &WriteOptions) -> fmt::Result {
+        let lenient;
+        let options = match options.non_finite {
+            NonFinitePolicy::Error => {
+                lenient = WriteOptions {
+                    non_finite: NonFinitePolicy::Null,
+                    ..options.clone()
+                };
+                &lenient
+            }
+            _ => options,
+        };
+        write_value(w, self, options, 0).map_err(|_| fmt::Error)
+    }
+
+    /// Serializes the value like `to_string`, but fails on NaN and infinite
+    /// numbers rather than writing something that is not valid JSON.
+    ///
+    /// ```
+    /// # use json_parser::{JsonValue, SerializeError};
+    /// let value = JsonValue::from(vec![1.0, f64::NAN]);
+    /// assert!(matches!(value.to_json_string(), Err(SerializeError::NonFinite(n)) if n.is_nan()));
+    /// assert_eq!(value.to_string(), "[1, null]");
+    /// ```
+    pub fn to_json_string(&self) -> Result<String, SerializeError> {
+        self.to_json_string_with(&WriteOptions::default())
+    }
+
+    /// Like `to_string_with`, except that with `NonFinitePolicy::Error` NaN
+    /// and infinite numbers are an error.
+    pub fn to_json_string_with(&self, options: &WriteOptions) -> Result<String, SerializeError> {
+        let mut out = String::new();
+        match write_value(&mut out, self, options, 0) {
+            Ok(()) => Ok(out),
+            Err(WriteError::Value(e)) => Err(e),
+            Err(WriteError::Fmt) => unreachable!("writing to a String cannot fail"),
+        }
+    }
+
+    /// Writes the value to `writer` exactly as `to_string` would format it,
+    /// without building the whole text in memory first. Many small writes are
+    /// made, so wrap unbuffered targets like `File` in a `BufWriter`. A NaN
+    /// or infinite number fails with an `InvalidData` error wrapping a
+    /// `SerializeError`, unless `WriteOptions::non_finite` allows it.
+    ///
+    /// ```
+    /// # use json_parser::Parser;
+    /// let value = Parser::new(r#"[1, "two", null]"#).parse().unwrap();
+    /// let mut bytes = Vec::new();
+    /// value.to_writer(&mut bytes).unwrap();
+    /// assert_eq!(bytes, value.to_string().into_bytes());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn to_writer<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        let options = WriteOptions {
+            sort_keys: true,
+            ..Default::default()
+        };
+        self.to_writer_with(writer, &options)
+    }
+
+    /// Like `to_writer`, laid out as by `to_string_pretty`.
+    #[cfg(feature = "std")]
+    pub fn to_writer_pretty<W: io::Write>(&self, writer: &mut W, indent: usize) -> io::Result<()> {
+        self.to_writer_with(
+            writer,
+            &WriteOptions {
+                indent: Some(indent),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Like `to_writer`, formatted as by `to_string_with`.
+    #[cfg(feature = "std")]
+    pub fn to_writer_with<W: io::Write>(&self, writer: &mut W, options: &WriteOptions) -> io::Result<()> {
+        let mut out = IoWriter::new(writer);
+        let result = write_value(&mut out, self, options, 0);
+        out.finish(result)
+    }
+
+    /// The name of the value's JSON type, for messages: `"null"`,
+    /// `"boolean"`, `"number"`, `"string"`, `"array"` or `"object"`. A
+    /// `RawNumber` is a `"number"`.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            JsonValue::Null => "null",
+            JsonValue::Boolean(_) => "boolean",
+            JsonValue::Number(_) | JsonValue::RawNumber(_) => "number",
+            JsonValue::String(_) => "string",
+            JsonValue::Array(_) => "array",
+            JsonValue::Object(_) => "object",
+        }
+    }
+}
+
+impl From<bool> for JsonValue {
+    fn from(b: bool) -> Self {
+        JsonValue::Boolean(b)
+    }
+}
+
+macro_rules! impl_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for JsonValue {
+                fn from(n: $t) -> Self {
+                    JsonValue::Number(n as f64)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_number!(f32, f64, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl From<&str> for JsonValue {
+    fn from(s: &str) -> Self {
+        JsonValue::String(s.to_string())
+    }
+}
+
+impl From<String> for JsonValue {
+    fn from(s: String) -> Self {
+        JsonValue::String(s)
+    }
+}
+
+impl<T: Into<JsonValue>> From<Option<T>> for JsonValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(JsonValue::Null, Into::into)
+    }
+}
+
+impl<T: Into<JsonValue>> From<Vec<T>> for JsonValue {
+    fn from(items: Vec<T>) -> Self {
+        JsonValue::Array(items.into_iter().map(Into::into).collect())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Into<JsonValue>> From<HashMap<String, T>> for JsonValue {
+    fn from(map: HashMap<String, T>) -> Self {
+        JsonValue::Object(map.into_iter().map(|(k, v)| (k, v.into())).collect())
+    }
+}
+
+impl<T: Into<JsonValue>> From<BTreeMap<String, T>> for JsonValue {
+    fn from(map: BTreeMap<String, T>) -> Self {
+        JsonValue::Object(map.into_iter().map(|(k, v)| (k, v.into())).collect())
+    }
+}
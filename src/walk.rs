@@ -0,0 +1,145 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{JsonValue, pointer::push_pointer_segment};
+
+/// What `JsonValue::walk` does after visiting a node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WalkControl {
+    /// Go on into the node's children, then on to the rest.
+    Continue,
+    /// Leave out the node's children but go on with the rest.
+    SkipChildren,
+    /// End the walk.
+    Stop,
+}
+
+impl JsonValue {
+    /// Visits every node depth-first, each before its children, passing its
+    /// JSON Pointer (`""` for this value, `/a/0/b` below it) along with it.
+    /// Array elements are visited in order and object members in the map's
+    /// iteration order.
+    ///
+    /// ```
+    /// # use json_parser::{Parser, WalkControl};
+    /// let doc = Parser::new(r#"{"a/b": [1, {"skip": [2]}], "c": 3}"#).parse().unwrap();
+    /// let mut pointers = Vec::new();
+    /// doc.walk(|pointer, value| {
+    ///     pointers.push(pointer.to_string());
+    ///     match value.pointer("/skip") {
+    ///         Some(_) => WalkControl::SkipChildren,
+    ///         None => WalkControl::Continue,
+    ///     }
+    /// });
+    /// pointers.sort();
+    /// assert_eq!(pointers, ["", "/a~1b", "/a~1b/0", "/a~1b/1", "/c"]);
+    /// ```
+    pub fn walk(&self, mut f: impl FnMut(&str, &JsonValue) -> WalkControl) {
+        self.walk_at(&mut String::new(), &mut f);
+    }
+
+    /// Like `walk`, with mutable access to each node. Children are visited
+    /// as they are after the callback has run on their parent, so a node
+    /// replaced there has its new children walked.
+    pub fn walk_mut(&mut self, mut f: impl FnMut(&str, &mut JsonValue) -> WalkControl) {
+        self.walk_mut_at(&mut String::new(), &mut f);
+    }
+
+    /// Calls `f` on every leaf, meaning every value that is not an array or
+    /// object, depth-first. A leaf that `f` turns into an array or object is
+    /// not walked into, so `f` never sees the values it created.
+    ///
+    /// ```
+    /// # use json_parser::{JsonValue, Parser};
+    /// let mut doc = Parser::new(r#"{"price": 9.999, "tags": [" a ", 1.234]}"#).parse().unwrap();
+    /// doc.map_values_mut(|leaf| match leaf {
+    ///     JsonValue::Number(n) => *n = (*n * 100.0).round() / 100.0,
+    ///     JsonValue::String(s) => *s = s.trim().to_string(),
+    ///     _ => {}
+    /// });
+    /// assert_eq!(doc.to_string(), r#"{"price": 10, "tags": ["a", 1.23]}"#);
+    /// ```
+    pub fn map_values_mut(&mut self, mut f: impl FnMut(&mut JsonValue)) {
+        self.map_leaves(&mut f);
+    }
+
+    /// Like `map_values_mut`, consuming the value and returning the result.
+    pub fn map_values(mut self, f: impl FnMut(&mut JsonValue)) -> JsonValue {
+        self.map_values_mut(f);
+        self
+    }
+
+    fn map_leaves(&mut self, f: &mut impl FnMut(&mut JsonValue)) {
+        match self {
+            JsonValue::Array(items) => items.iter_mut().for_each(|item| item.map_leaves(f)),
+            JsonValue::Object(members) => members.values_mut().for_each(|value| value.map_leaves(f)),
+            leaf => f(leaf),
+        }
+    }
+
+    // Returns whether the walk was stopped.
+    fn walk_at(&self, pointer: &mut String, f: &mut impl FnMut(&str, &JsonValue) -> WalkControl) -> bool {
+        match f(pointer, self) {
+            WalkControl::Continue => {}
+            WalkControl::SkipChildren => return false,
+            WalkControl::Stop => return true,
+        }
+        let len = pointer.len();
+        match self {
+            JsonValue::Array(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    push_pointer_segment(pointer, &i.to_string());
+                    let stopped = item.walk_at(pointer, f);
+                    pointer.truncate(len);
+                    if stopped {
+                        return true;
+                    }
+                }
+            }
+            JsonValue::Object(members) => {
+                for (key, value) in members {
+                    push_pointer_segment(pointer, key);
+                    let stopped = value.walk_at(pointer, f);
+                    pointer.truncate(len);
+                    if stopped {
+                        return true;
+                    }
+                }
+            }
+            _ => {}
+        }
+        false
+    }
+
+    fn walk_mut_at(&mut self, pointer: &mut String, f: &mut impl FnMut(&str, &mut JsonValue) -> WalkControl) -> bool {
+        match f(pointer, self) {
+            WalkControl::Continue => {}
+            WalkControl::SkipChildren => return false,
+            WalkControl::Stop => return true,
+        }
+        let len = pointer.len();
+        match self {
+            JsonValue::Array(items) => {
+                for (i, item) in items.iter_mut().enumerate() {
+                    push_pointer_segment(pointer, &i.to_string());
+                    let stopped = item.walk_mut_at(pointer, f);
+                    pointer.truncate(len);
+                    if stopped {
+                        return true;
+                    }
+                }
+            }
+            JsonValue::Object(members) => {
+                for (key, value) in members.iter_mut() {
+                    push_pointer_segment(pointer, key);
+                    let stopped = value.walk_mut_at(pointer, f);
+                    pointer.truncate(len);
+                    if stopped {
+                        return true;
+                    }
+                }
+            }
+            _ => {}
+        }
+        false
+    }
+}
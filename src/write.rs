@@ -0,0 +1,219 @@
+use core::fmt::{self, Write};
+#[cfg(feature = "std")]
+use std::io;
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{
+    JsonValue, SerializeError,
+    options::{ExponentFormat, NonFinitePolicy, WriteOptions},
+};
+
+// Why serializing stopped: the output failed, or the value itself could
+// not be written.
+#[derive(Debug)]
+pub(crate) enum WriteError {
+    Fmt,
+    Value(SerializeError),
+}
+
+impl From<fmt::Error> for WriteError {
+    fn from(_: fmt::Error) -> Self {
+        WriteError::Fmt
+    }
+}
+
+// Lets the `fmt::Write` based serializer write into an `io::Write`. The
+// formatting machinery can only report that something failed, so the actual
+// I/O error is kept here for the caller.
+#[cfg(feature = "std")]
+pub(crate) struct IoWriter<W> {
+    inner: W,
+    error: Option<io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<W: io::Write> IoWriter<W> {
+    pub(crate) fn new(inner: W) -> Self {
+        IoWriter { inner, error: None }
+    }
+
+    pub(crate) fn finish(self, result: Result<(), WriteError>) -> io::Result<()> {
+        match (result, self.error) {
+            (Ok(()), _) => Ok(()),
+            (Err(WriteError::Value(e)), _) => Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+            (Err(WriteError::Fmt), Some(e)) => Err(e),
+            (Err(WriteError::Fmt), None) => Err(io::Error::other("formatting failed")),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: io::Write> Write for IoWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            fmt::Error
+        })
+    }
+}
+
+// Serializes `value`, which sits `level` containers deep, according to
+// `options`. Unless an indent is set the output is on one line, spaced
+// like `Display`.
+pub(crate) fn write_value<W: Write>(
+    out: &mut W,
+    value: &JsonValue,
+    options: &WriteOptions,
+    level: usize,
+) -> Result<(), WriteError> {
+    match value {
+        JsonValue::Null => out.write_str("null")?,
+        JsonValue::Boolean(b) => write!(out, "{}", b)?,
+        JsonValue::Number(n) => write_number(out, *n, options)?,
+        JsonValue::RawNumber(s) => out.write_str(s)?,
+        JsonValue::String(s) => write_escaped(out, s, options)?,
+        JsonValue::Array(a) if a.is_empty() => out.write_str("[]")?,
+        JsonValue::Object(o) if o.is_empty() => out.write_str("{}")?,
+        JsonValue::Array(a) => {
+            out.write_char('[')?;
+            for (i, item) in a.iter().enumerate() {
+                write_separator(out, options, i, level + 1)?;
+                write_value(out, item, options, level + 1)?;
+            }
+            write_closing(out, options, level)?;
+            out.write_char(']')?
+        }
+        JsonValue::Object(o) => {
+            out.write_char('{')?;
+            let mut members: Vec<_> = o.iter().collect();
+            if options.sort_keys {
+                members.sort_unstable_by(|a, b| a.0.cmp(b.0));
+            }
+            for (i, (key, value)) in members.into_iter().enumerate() {
+                write_separator(out, options, i, level + 1)?;
+                write_escaped(out, key, options)?;
+                out.write_str(if options.compact { ":" } else { ": " })?;
+                write_value(out, value, options, level + 1)?;
+            }
+            write_closing(out, options, level)?;
+            out.write_char('}')?
+        }
+    }
+    Ok(())
+}
+
+// Goes before the `index`th element of a container: a comma unless it is the
+// first, then either a space or a newline and indentation.
+fn write_separator<W: Write>(out: &mut W, options: &WriteOptions, index: usize, level: usize) -> fmt::Result {
+    if index > 0 {
+        out.write_char(',')?;
+    }
+    if options.multi_line() {
+        out.write_char('\n')?;
+        write_indent(out, options, level)
+    } else if index > 0 && !options.compact {
+        out.write_char(' ')
+    } else {
+        Ok(())
+    }
+}
+
+fn write_closing<W: Write>(out: &mut W, options: &WriteOptions, level: usize) -> fmt::Result {
+    if options.multi_line() {
+        out.write_char('\n')?;
+        write_indent(out, options, level)
+    } else {
+        Ok(())
+    }
+}
+
+fn write_indent<W: Write>(out: &mut W, options: &WriteOptions, level: usize) -> fmt::Result {
+    for _ in 0..level {
+        match &options.indent_string {
+            Some(unit) => out.write_str(unit)?,
+            None => {
+                for _ in 0..options.indent.unwrap_or(0) {
+                    out.write_char(' ')?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+// Rust's float formatting already prints the shortest digits that parse back
+// to the same f64; it just never switches to exponent form on its own, so
+// 1e300 would come out as a 301 digit integer. By default, like JavaScript,
+// very large and very small magnitudes use exponent notation instead, and
+// positive exponents carry an explicit sign (`1e+21`) so output matches
+// `JSON.stringify` byte for byte.
+pub(crate) fn write_number<W: Write>(out: &mut W, n: f64, options: &WriteOptions) -> Result<(), WriteError> {
+    // Rounding to fewer digits first means the shortest representation of
+    // the result has at most that many.
+    let n = match options.max_significant_digits {
+        Some(digits) if n.is_finite() => format!("{:.*e}", digits.max(1) - 1, n).parse().unwrap_or(n),
+        _ => n,
+    };
+    if !n.is_finite() {
+        return write_non_finite(out, n, options.non_finite);
+    }
+
+    let magnitude = n.abs();
+    let exponent_form = match options.exponent {
+        ExponentFormat::Auto { small, large } => n != 0.0 && (magnitude < small || magnitude >= large),
+        ExponentFormat::Always => true,
+        ExponentFormat::Never => false,
+    };
+    if !exponent_form {
+        let text = n.to_string();
+        out.write_str(&text)?;
+        if options.integral_trailing_zero && !text.contains('.') {
+            out.write_str(".0")?;
+        }
+        return Ok(());
+    }
+
+    let text = format!("{:e}", n);
+    match text.split_once('e') {
+        Some((mantissa, exponent)) if !exponent.starts_with('-') => write!(out, "{}e+{}", mantissa, exponent)?,
+        _ => out.write_str(&text)?,
+    }
+    Ok(())
+}
+
+fn write_non_finite<W: Write>(out: &mut W, n: f64, policy: NonFinitePolicy) -> Result<(), WriteError> {
+    let token = match policy {
+        NonFinitePolicy::Error => return Err(WriteError::Value(SerializeError::NonFinite(n))),
+        NonFinitePolicy::Null => "null",
+        NonFinitePolicy::Literal if n.is_nan() => "NaN",
+        NonFinitePolicy::Literal if n > 0.0 => "Infinity",
+        NonFinitePolicy::Literal => "-Infinity",
+    };
+    Ok(out.write_str(token)?)
+}
+
+// Writes `s` as a quoted JSON string literal.
+pub(crate) fn write_escaped<W: Write>(out: &mut W, s: &str, options: &WriteOptions) -> fmt::Result {
+    out.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => out.write_str("\\\"")?,
+            '\\' => out.write_str("\\\\")?,
+            '/' if options.escape_solidus => out.write_str("\\/")?,
+            '\n' => out.write_str("\\n")?,
+            '\r' => out.write_str("\\r")?,
+            '\t' => out.write_str("\\t")?,
+            '\u{08}' => out.write_str("\\b")?,
+            '\u{0C}' => out.write_str("\\f")?,
+            c if c < '\u{20}' => write!(out, "\\u{:04x}", c as u32)?,
+            c if options.escape.escapes(c) => {
+                for unit in c.encode_utf16(&mut [0; 2]) {
+                    write!(out, "\\u{:04x}", unit)?;
+                }
+            }
+            _ => out.write_char(c)?,
+        }
+    }
+    out.write_char('"')
+}
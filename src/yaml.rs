@@ -0,0 +1,169 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::JsonValue;
+
+impl JsonValue {
+    /// Writes the value as YAML for people to read. The output sticks to a
+    /// subset of YAML 1.2 that YAML 1.1 parsers read the same way: block
+    /// mappings and sequences indented by two spaces, `[]` and `{}` for
+    /// empty containers, and object keys in sorted order.
+    ///
+    /// Strings are left unquoted only when they cannot be mistaken for
+    /// anything else. Strings that look like numbers, `null`, booleans
+    /// (including `yes`, `no`, `on` and `off`), or that have leading or
+    /// trailing spaces, `: `, ` #` or a leading indicator character, are
+    /// double-quoted. Multi-line strings are written as literal blocks.
+    ///
+    /// ```
+    /// # use json_parser::Parser;
+    /// let value = Parser::new(r#"{"name": "no", "ports": [80, 443], "note": "a: b", "tags": []}"#).parse().unwrap();
+    /// assert_eq!(value.to_yaml_string(), "name: \"no\"\nnote: \"a: b\"\nports:\n  - 80\n  - 443\ntags: []\n");
+    /// ```
+    pub fn to_yaml_string(&self) -> String {
+        let mut out = String::new();
+        for line in yaml_lines(self) {
+            out.push_str(&line);
+            out.push('\n');
+        }
+        out
+    }
+}
+
+// The lines of `value` without any indentation of its own. Scalars and
+// empty containers are written on the first line, which the caller puts
+// after `key: ` or `- `; further lines belong to a literal block.
+fn yaml_lines(value: &JsonValue) -> Vec<String> {
+    match value {
+        JsonValue::Null => vec!["null".to_string()],
+        JsonValue::Boolean(b) => vec![b.to_string()],
+        JsonValue::Number(n) if n.is_nan() => vec![".nan".to_string()],
+        JsonValue::Number(n) if n.is_infinite() => vec![if *n > 0.0 { ".inf" } else { "-.inf" }.to_string()],
+        JsonValue::Number(_) => vec![yaml_number(&value.to_string())],
+        JsonValue::RawNumber(s) => vec![yaml_number(s)],
+        JsonValue::String(s) => string_lines(s),
+        JsonValue::Array(a) if a.is_empty() => vec!["[]".to_string()],
+        JsonValue::Object(o) if o.is_empty() => vec!["{}".to_string()],
+        JsonValue::Array(a) => {
+            let mut lines = Vec::new();
+            for item in a {
+                for (i, line) in yaml_lines(item).into_iter().enumerate() {
+                    lines.push(if i == 0 { format!("- {}", line) } else { indented(line) });
+                }
+            }
+            lines
+        }
+        JsonValue::Object(o) => {
+            let mut members: Vec<_> = o.iter().collect();
+            members.sort_unstable_by(|a, b| a.0.cmp(b.0));
+            let mut lines = Vec::new();
+            for (key, value) in members {
+                let key = quoted_if_needed(key);
+                let mut value_lines = yaml_lines(value).into_iter();
+                if is_block_container(value) {
+                    lines.push(format!("{}:", key));
+                } else if let Some(first) = value_lines.next() {
+                    lines.push(format!("{}: {}", key, first));
+                }
+                lines.extend(value_lines.map(indented));
+            }
+            lines
+        }
+    }
+}
+
+// YAML 1.1 only reads exponent forms as floats with a decimal point and a
+// signed exponent, as in `1.0e+21`; YAML 1.2 reads those the same way.
+fn yaml_number(text: &str) -> String {
+    let Some((mantissa, exponent)) = text.split_once(['e', 'E']) else {
+        return text.to_string();
+    };
+    let point = if mantissa.contains('.') { "" } else { ".0" };
+    let sign = if exponent.starts_with(['+', '-']) { "" } else { "+" };
+    format!("{}{}e{}{}", mantissa, point, sign, exponent)
+}
+
+fn is_block_container(value: &JsonValue) -> bool {
+    match value {
+        JsonValue::Array(a) => !a.is_empty(),
+        JsonValue::Object(o) => !o.is_empty(),
+        _ => false,
+    }
+}
+
+// Empty lines stay empty rather than getting trailing spaces, which inside
+// a literal block would be read as content.
+fn indented(line: String) -> String {
+    if line.is_empty() { line } else { format!("  {}", line) }
+}
+
+// A string as a literal block (`|`) when it spans lines and nothing in it
+// needs escaping. The chomping indicator keeps its trailing line breaks
+// exact: `|-` for none, `|` for one and `|+` for more.
+fn string_lines(s: &str) -> Vec<String> {
+    let body = s.trim_end_matches('\n');
+    let blockable = body.contains('\n')
+        && !body.starts_with([' ', '\t', '\n'])
+        && !body.chars().any(|c| c != '\n' && c != '\t' && needs_escape(c));
+    if !blockable {
+        return vec![quoted_if_needed(s)];
+    }
+    let trailing = s.len() - body.len();
+    let header = match trailing {
+        0 => "|-",
+        1 => "|",
+        _ => "|+",
+    };
+    let mut lines = vec![header.to_string()];
+    lines.extend(body.split('\n').map(|line| indented(line.to_string())));
+    lines.extend((1..trailing).map(|_| String::new()));
+    lines
+}
+
+fn quoted_if_needed(s: &str) -> String {
+    if is_plain_safe(s) {
+        return s.to_string();
+    }
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            c if needs_escape(c) => out.push_str(&format!("\\u{:04X}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+// Characters that cannot appear as they are in a YAML scalar, or that some
+// parsers treat as line breaks or byte order marks.
+fn needs_escape(c: char) -> bool {
+    c.is_control() || matches!(c, '\u{2028}' | '\u{2029}' | '\u{FEFF}')
+}
+
+// Words that YAML 1.1 or 1.2 resolves to something other than a string.
+const RESERVED: [&str; 11] = ["null", "~", "true", "false", "yes", "no", "on", "off", "y", "n", "<<"];
+
+fn is_plain_safe(s: &str) -> bool {
+    let Some(first) = s.chars().next() else {
+        return false;
+    };
+    if RESERVED.iter().any(|word| s.eq_ignore_ascii_case(word)) {
+        return false;
+    }
+    // Indicators have a meaning of their own at the start of a scalar.
+    if "-?:,[]{}#&*!|>'\"%@`".contains(first) || first.is_whitespace() || s.ends_with(char::is_whitespace) {
+        return false;
+    }
+    // Anything starting like a number might be read as one, whether as a
+    // float, an integer in another base or a YAML 1.1 sexagesimal or date.
+    let unsigned = s.strip_prefix('+').unwrap_or(s);
+    if unsigned.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+        return false;
+    }
+    !(s.contains(": ") || s.contains(" #") || s.ends_with(':') || s.chars().any(|c| c == '\t' || needs_escape(c)))
+}
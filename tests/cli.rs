@@ -0,0 +1,116 @@
+// Runs the built `json_parser` binary the way a shell would, checking exit
+// codes, stdout and the `file:line:col` errors on stderr.
+use std::{
+    env, fs,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+struct Run {
+    code: i32,
+    stdout: String,
+    stderr: String,
+}
+
+fn run(args: &[&str], stdin: &str) -> Run {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_json_parser"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // The binary exits on a usage error without reading its input, which
+    // closes the pipe under us.
+    let _ = child.stdin.take().unwrap().write_all(stdin.as_bytes());
+    let output = child.wait_with_output().unwrap();
+    Run {
+        code: output.status.code().unwrap(),
+        stdout: String::from_utf8(output.stdout).unwrap(),
+        stderr: String::from_utf8(output.stderr).unwrap(),
+    }
+}
+
+// A file in the temp directory holding `contents`, named per test so tests
+// running in parallel don't share one.
+fn temp_file(name: &str, contents: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("json_parser_cli_{}_{}.json", std::process::id(), name));
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn validate_exit_codes() {
+    let ok = run(&["validate"], r#"{"a": [1, 2]}"#);
+    assert_eq!((ok.code, ok.stdout.as_str(), ok.stderr.as_str()), (0, "", ""));
+
+    let bad = run(&["validate"], "{\"a\":\n [1,, 2]}");
+    assert_eq!(bad.code, 1);
+    assert_eq!(bad.stdout, "");
+    assert_eq!(bad.stderr, "error: <stdin>:2:5: unexpected character: ,\n");
+}
+
+#[test]
+fn format_and_minify_from_stdin() {
+    // One member per object, since members come out in the map's order.
+    let input = r#"{"a":[1, {"b":null}]}"#;
+    let formatted = run(&["format", "--indent", "2"], input);
+    assert_eq!(formatted.code, 0);
+    assert_eq!(formatted.stdout, "{\n  \"a\": [\n    1,\n    {\n      \"b\": null\n    }\n  ]\n}\n");
+
+    // `-` reads stdin just as leaving the file out does.
+    for args in [&["minify"][..], &["minify", "-"]] {
+        let minified = run(args, input);
+        assert_eq!(minified.code, 0);
+        assert_eq!(minified.stdout, "{\"a\":[1,{\"b\":null}]}\n");
+    }
+
+    let bad = run(&["format", "-"], "[1, 2");
+    assert_eq!(bad.code, 1);
+    assert_eq!(bad.stdout, "");
+    assert!(bad.stderr.starts_with("error: <stdin>:1:5: unexpected end of input"), "{}", bad.stderr);
+}
+
+#[test]
+fn files_are_named_in_errors() {
+    let good = temp_file("good", "[true, null]");
+    let bad = temp_file("bad", "[true,\n  nul]");
+
+    let ok = run(&["minify", good.to_str().unwrap()], "");
+    assert_eq!((ok.code, ok.stdout.as_str()), (0, "[true,null]\n"));
+
+    let failed = run(&["validate", bad.to_str().unwrap()], "");
+    assert_eq!(failed.code, 1);
+    let prefix = format!("error: {}:2:7: ", bad.display());
+    assert!(failed.stderr.starts_with(&prefix), "{}", failed.stderr);
+
+    fs::remove_file(&good).unwrap();
+    fs::remove_file(&bad).unwrap();
+}
+
+#[test]
+fn missing_file_is_a_usage_error() {
+    let path = env::temp_dir().join("json_parser_cli_does_not_exist.json");
+    let missing = run(&["format", path.to_str().unwrap()], "");
+    assert_eq!(missing.code, 2);
+    assert_eq!(missing.stdout, "");
+    let prefix = format!("error: {}: ", path.display());
+    assert!(missing.stderr.starts_with(&prefix), "{}", missing.stderr);
+}
+
+#[test]
+fn bad_arguments_exit_with_2() {
+    for args in [
+        &[][..],
+        &["frob"],
+        &["validate", "--indent", "2"],
+        &["format", "--indent", "wide"],
+        &["minify", "a.json", "b.json"],
+    ] {
+        let usage = run(args, "{}");
+        assert_eq!(usage.code, 2, "{:?}", args);
+        assert_eq!(usage.stdout, "");
+        assert!(usage.stderr.starts_with("error: ") && usage.stderr.contains("usage:"), "{}", usage.stderr);
+    }
+}